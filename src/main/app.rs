@@ -16,22 +16,28 @@
 
 use std::cell::OnceCell;
 
+use adw::prelude::*;
 use adw::subclass::prelude::*;
 use gio::subclass::prelude::ApplicationImpl;
-use glib::{clone, ParamSpec, Properties};
+use glib::{clone, KeyFile, ParamSpec, Properties};
 use gtk::glib::WeakRef;
 use gtk::prelude::*;
 use gtk::{gio, glib, FileChooserAction, FileChooserNative};
 
-use crate::main::appstream::utils;
+use crate::main::appstream::{utils, CollectionEntry, SkCollection, SkPackageAppstream};
+use crate::main::error::Error;
+use crate::main::flatpak::installation::SkRemote;
+use crate::main::flatpak::package::SkPackageExt;
 use crate::main::flatpak::sideload::SkSideloadKind;
-use crate::main::i18n::i18n;
+use crate::main::i18n::{i18n, i18n_f};
+use crate::main::task::SkTask;
 use crate::main::ui::about_dialog;
 use crate::main::ui::debug::SkDebugWindow;
 use crate::main::ui::main::SkApplicationWindow;
 use crate::main::ui::sideload::SkSideloadWindow;
 use crate::main::worker::SkWorker;
-use crate::shared::config;
+use crate::shared::task::AppstreamLookup;
+use crate::shared::{config, path};
 
 mod imp {
     use super::*;
@@ -43,6 +49,7 @@ mod imp {
         worker: SkWorker,
 
         window: OnceCell<WeakRef<SkApplicationWindow>>,
+        settings: OnceCell<gio::Settings>,
     }
 
     #[glib::object_subclass]
@@ -148,19 +155,16 @@ mod imp {
             debug!("Application -> open");
 
             for file in files {
-                let sideload_kind = SkSideloadKind::determine_kind(file);
-
-                if sideload_kind == SkSideloadKind::Ref {
-                    // TODO: Check if the FlatpakRef file is for a already added remote
-                    let is_known_remote = false;
-                    if is_known_remote {
-                        self.obj().activate();
-                        // TODO: Open app details page for flatpak ref
-                        return;
+                let fut = clone!(
+                    #[weak(rename_to = this)]
+                    self,
+                    #[strong]
+                    file,
+                    async move {
+                        this.open_file(&file).await;
                     }
-                }
-
-                let _ = self.create_sideload_window(file);
+                );
+                crate::main::spawn_future_local(fut);
             }
         }
     }
@@ -170,24 +174,207 @@ mod imp {
             // Wait till worker is ready, otherwise there's a chance that responses get lost
             self.worker.wait_ready().await;
 
-            // Show initial view if there's no appstream data available to display
-            if !utils::check_appstream_silo_exists() {
-                debug!("No appstream data available, trigger update.");
-                let task = self
-                    .worker
-                    .update_appstream()
-                    .await
-                    .expect("Unable to spawn update appstream task");
+            let has_appstream_data = utils::check_appstream_silo_exists();
+            let task = self
+                .worker
+                .update_appstream()
+                .await
+                .expect("Unable to spawn update appstream task");
 
+            if has_appstream_data {
+                debug!("Appstream data available, update it in the background.");
+            } else {
+                // Show initial view if there's no appstream data available to display
+                debug!("No appstream data available, trigger update.");
                 if let Some(window) = self.app_window() {
                     window.show_initial_view(&task);
                 }
+            }
+
+            // Show the collections of the existing appstream data right away, and refresh
+            // them once the sync is done
+            let collections = SkCollection::load_entries(&self.worker).await;
+            if has_appstream_data {
+                self.show_collections(&collections).await;
+            }
+
+            self.check_appstream_sync(&task).await;
+            self.show_collections(&collections).await;
+        }
+
+        async fn show_collections(&self, entries: &[CollectionEntry]) {
+            let collections = SkCollection::resolve_all(&self.worker, entries).await;
+            if let Some(window) = self.app_window() {
+                window.show_collections(&collections);
+            }
+        }
+
+        /// Notifies the user about remotes for which the appstream data
+        /// couldn't get updated
+        async fn check_appstream_sync(&self, task: &SkTask) {
+            if task.await_result().await.is_err() {
+                return;
+            }
+
+            let messages: Vec<String> = task
+                .result_appstream_sync()
+                .unwrap_or_default()
+                .iter()
+                .filter(|result| result.is_failed())
+                .filter_map(|result| {
+                    let remote = SkRemote::new(&result.remote);
+                    utils::remote_sync_status_text(&remote)
+                })
+                .collect();
+
+            if messages.is_empty() {
+                return;
+            }
+
+            if let Some(window) = self.app_window() {
+                window.show_appstream_sync_warning(&messages.join("\n"));
+            }
+        }
+
+        /// Opens a local file, a http(s) url, or an `appstream:` uri
+        async fn open_file(&self, file: &gio::File) {
+            if file.has_uri_scheme("appstream") {
+                match utils::appstream_uri_component_id(&file.uri()) {
+                    Some(id) => self.open_appstream_id(&id).await,
+                    None => warn!("Unable to parse appstream uri: {}", file.uri()),
+                }
+                return;
+            }
+
+            let is_download = file.has_uri_scheme("http")
+                || file.has_uri_scheme("https")
+                || file.has_uri_scheme("flatpak+https");
+            let file = if is_download {
+                match self.download_file(file).await {
+                    Ok(file) => file,
+                    Err(err) => {
+                        let title = i18n("Unable to Download File");
+                        self.show_error(&title, &err.message());
+                        return;
+                    }
+                }
             } else {
-                // Appstream data exists -> update it
-                // TODO: this ^
+                file.clone()
+            };
+
+            // Flatpakref files of already added remotes can be shown as regular app
+            if SkSideloadKind::determine_kind(&file) == SkSideloadKind::Ref {
+                if let Some(appstream) = self.resolve_ref_file(&file).await {
+                    if is_download {
+                        path::remove_download(&file.path().unwrap());
+                    }
+                    self.show_app(&appstream);
+                    return;
+                }
+            }
+
+            let window = self.create_sideload_window(&file);
+
+            // The downloaded file is only needed until the sideload is done
+            if is_download {
+                window.connect_destroy(move |_| path::remove_download(&file.path().unwrap()));
             }
         }
 
+        /// Shows the app with the given appstream component id
+        async fn open_appstream_id(&self, id: &str) {
+            self.worker.wait_ready().await;
+
+            // Components can be renamed, the old ids are available as `<provides>`
+            let mut packages = Vec::new();
+            for lookup in [
+                AppstreamLookup::Id(id.to_string()),
+                AppstreamLookup::Provides(id.to_string()),
+            ] {
+                match self.worker.lookup_appstream(lookup).await {
+                    Ok(result) => packages = result,
+                    Err(err) => error!("Unable to lookup appstream component: {}", err.message()),
+                }
+
+                if !packages.is_empty() {
+                    break;
+                }
+            }
+
+            // Prefer the package of the preferred installation
+            let preferred = self.worker.installations().preferred().info();
+            let appstream = packages
+                .iter()
+                .find(|a| a.package().remote().info().installation.as_ref() == Some(&preferred))
+                .or(packages.first());
+
+            if let Some(appstream) = appstream {
+                self.show_app(appstream);
+            } else {
+                let title = i18n("App Not Found");
+                let msg = i18n_f("No software source provides “{}”", &[id]);
+                self.show_error(&title, &msg);
+            }
+        }
+
+        /// Resolves a flatpakref file against the already added remotes.
+        /// Returns `None` if the ref isn't available from a known remote.
+        async fn resolve_ref_file(&self, file: &gio::File) -> Option<SkPackageAppstream> {
+            let bytes = file.load_bytes_future().await.ok()?.0;
+            let keyfile = KeyFile::new();
+            keyfile
+                .load_from_bytes(&bytes, glib::KeyFileFlags::NONE)
+                .ok()?;
+
+            let name = keyfile.string("Flatpak Ref", "Name").ok()?.to_string();
+            let url = keyfile.string("Flatpak Ref", "Url").ok()?.to_string();
+            let branch = keyfile.string("Flatpak Ref", "Branch").ok();
+
+            self.worker.wait_ready().await;
+            let lookup = AppstreamLookup::Id(name.clone());
+            let packages = self.worker.lookup_appstream(lookup).await.ok()?;
+
+            packages.into_iter().find(|appstream| {
+                let package = appstream.package();
+                let remote_url = package.remote().repository_url();
+
+                package.name() == name
+                    && branch
+                        .as_ref()
+                        .map_or(true, |b| package.branch() == b.as_str())
+                    && remote_url.trim_end_matches('/') == url.trim_end_matches('/')
+            })
+        }
+
+        /// Downloads a remote file into the cache directory, so that it can
+        /// get sideloaded
+        async fn download_file(&self, file: &gio::File) -> Result<gio::File, Error> {
+            // `flatpak+https` is used by websites to make browsers hand over the link
+            let url = file.uri().replacen("flatpak+https:", "https:", 1);
+
+            self.worker.wait_ready().await;
+            let task = self.worker.download_sideloadable(&url).await?;
+            task.await_result().await?;
+
+            task.result_download().ok_or(Error::UnsupportedSideloadType)
+        }
+
+        fn show_app(&self, appstream: &SkPackageAppstream) {
+            self.obj().activate();
+
+            if let Some(window) = self.app_window() {
+                window.show_app(appstream);
+            }
+        }
+
+        fn show_error(&self, title: &str, message: &str) {
+            self.obj().activate();
+
+            let dialog = adw::AlertDialog::new(Some(title), Some(message));
+            dialog.add_response("close", &i18n("_Close"));
+            dialog.present(self.app_window().as_ref());
+        }
+
         fn app_window(&self) -> Option<SkApplicationWindow> {
             if let Some(window) = self.window.get() {
                 window.upgrade()
@@ -295,6 +482,13 @@ impl SkApplication {
         // Start running gtk::Application
         app.run()
     }
+
+    pub fn settings(&self) -> gio::Settings {
+        self.imp()
+            .settings
+            .get_or_init(|| gio::Settings::new(config::APP_ID))
+            .clone()
+    }
 }
 
 impl Default for SkApplication {
@@ -0,0 +1,323 @@
+// Souk - collection.rs
+// Copyright (C) 2024  Felix Häcker <haeckerfelix@gnome.org>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::cell::OnceCell;
+use std::io::ErrorKind;
+
+use appstream::xmltree::{Element, XMLNode};
+use glib::{ParamSpec, Properties};
+use gtk::prelude::*;
+use gtk::subclass::prelude::*;
+use gtk::{gio, glib};
+use serde::Deserialize;
+
+use crate::main::appstream::SkPackageAppstream;
+use crate::main::error::Error;
+use crate::main::i18n::i18n;
+use crate::main::worker::SkWorker;
+use crate::main::SkApplication;
+use crate::shared::path;
+use crate::shared::task::AppstreamLookup;
+
+/// Collections which are shipped with Souk, used when there's no local override
+const BUNDLED_COLLECTIONS: &str = "/de/haeckerfelix/Souk/collections.xml";
+
+/// Maximum accepted size of a collections feed which gets downloaded
+const MAX_FEED_SIZE: u64 = 1024 * 1024;
+
+/// JSON feed which describes curated collections, eg.
+///
+/// ```json
+/// { "collections": [ { "id": "picks", "title": "Editor's Picks", "apps": [
+///     "de.haeckerfelix.Shortwave",
+///     { "id": "org.example.Tool", "remote": "company" }
+/// ] } ] }
+/// ```
+#[derive(Deserialize, Debug, Default)]
+struct CollectionsFeed {
+    #[serde(default)]
+    collections: Vec<CollectionEntry>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct CollectionEntry {
+    id: String,
+    title: String,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    apps: Vec<AppEntry>,
+}
+
+/// An app is referenced by its appstream component id, and can optionally be
+/// restricted to a specific remote
+#[derive(Deserialize, Debug, Clone)]
+#[serde(untagged)]
+enum AppEntry {
+    Id(String),
+    Remote { id: String, remote: Option<String> },
+}
+
+impl AppEntry {
+    fn id(&self) -> &str {
+        match self {
+            Self::Id(id) => id,
+            Self::Remote { id, .. } => id,
+        }
+    }
+
+    fn remote(&self) -> Option<&str> {
+        match self {
+            Self::Id(_) => None,
+            Self::Remote { remote, .. } => remote.as_deref(),
+        }
+    }
+}
+
+mod imp {
+    use super::*;
+
+    #[derive(Debug, Default, Properties)]
+    #[properties(wrapper_type = super::SkCollection)]
+    pub struct SkCollection {
+        #[property(get, set, construct_only)]
+        id: OnceCell<String>,
+        #[property(get, set, construct_only)]
+        title: OnceCell<String>,
+        #[property(get, set, construct_only)]
+        description: OnceCell<String>,
+        /// The `SkPackageAppstream` apps of this collection
+        #[property(get, set, construct_only)]
+        apps: OnceCell<gio::ListStore>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for SkCollection {
+        const NAME: &'static str = "SkCollection";
+        type Type = super::SkCollection;
+    }
+
+    impl ObjectImpl for SkCollection {
+        fn properties() -> &'static [ParamSpec] {
+            Self::derived_properties()
+        }
+
+        fn property(&self, id: usize, pspec: &ParamSpec) -> glib::Value {
+            Self::derived_property(self, id, pspec)
+        }
+
+        fn set_property(&self, id: usize, value: &glib::Value, pspec: &ParamSpec) {
+            Self::derived_set_property(self, id, value, pspec)
+        }
+    }
+}
+
+glib::wrapper! {
+    pub struct SkCollection(ObjectSubclass<imp::SkCollection>);
+}
+
+impl SkCollection {
+    pub fn new(id: &str, title: &str, description: &str, apps: &gio::ListStore) -> Self {
+        glib::Object::builder()
+            .property("id", id)
+            .property("title", title)
+            .property("description", description)
+            .property("apps", apps)
+            .build()
+    }
+
+    /// Loads the curated collections. The local override file replaces the
+    /// bundled collections, collections from the configured url get added on
+    /// top.
+    pub async fn load_entries(worker: &SkWorker) -> Vec<CollectionEntry> {
+        let mut entries = Self::local_entries();
+
+        match Self::remote_entries(worker).await {
+            Ok(remote_entries) => {
+                for entry in remote_entries {
+                    entries.retain(|e| e.id != entry.id);
+                    entries.push(entry);
+                }
+            }
+            Err(err) => warn!("Unable to load remote collections: {}", err.message()),
+        }
+
+        entries
+    }
+
+    /// Resolves the apps of the collections against the appstream data.
+    /// Collections without any available app are skipped.
+    pub async fn resolve_all(worker: &SkWorker, entries: &[CollectionEntry]) -> Vec<Self> {
+        let mut collections = Vec::new();
+        for entry in entries {
+            match Self::resolve(worker, entry).await {
+                Ok(collection) if collection.apps().n_items() != 0 => collections.push(collection),
+                Ok(_) => debug!("Skip collection \"{}\" without available apps", entry.id),
+                Err(err) => warn!(
+                    "Unable to resolve collection \"{}\": {}",
+                    entry.id,
+                    err.message()
+                ),
+            }
+        }
+
+        collections
+    }
+
+    /// Returns the collections of the local override file, or the bundled
+    /// ones if there's no (valid) override
+    fn local_entries() -> Vec<CollectionEntry> {
+        match std::fs::read(&*path::COLLECTIONS_FILE) {
+            Ok(bytes) => match serde_json::from_slice::<CollectionsFeed>(&bytes) {
+                Ok(feed) => return feed.collections,
+                Err(err) => warn!("Unable to parse {:?}: {}", *path::COLLECTIONS_FILE, err),
+            },
+            Err(err) if err.kind() == ErrorKind::NotFound => (),
+            Err(err) => warn!("Unable to read {:?}: {}", *path::COLLECTIONS_FILE, err),
+        }
+
+        let bytes =
+            match gio::resources_lookup_data(BUNDLED_COLLECTIONS, gio::ResourceLookupFlags::NONE) {
+                Ok(bytes) => bytes,
+                Err(err) => {
+                    warn!("Unable to find bundled collections: {err}");
+                    return Vec::new();
+                }
+            };
+
+        match Element::parse(&*bytes) {
+            Ok(root) => root
+                .children
+                .iter()
+                .filter_map(XMLNode::as_element)
+                .filter(|e| e.name == "collection")
+                .filter_map(Self::bundled_entry)
+                .collect(),
+            Err(err) => {
+                warn!("Unable to parse bundled collections: {err}");
+                Vec::new()
+            }
+        }
+    }
+
+    /// Reads a `<collection>` element of the bundled collections. The title
+    /// and description are marked for translation by `data/its/collections.its`
+    fn bundled_entry(element: &Element) -> Option<CollectionEntry> {
+        let text = |name: &str| {
+            element
+                .get_child(name)
+                .and_then(|e| e.get_text())
+                .map(|t| t.trim().to_string())
+                .unwrap_or_default()
+        };
+        let translated = |text: String| if text.is_empty() { text } else { i18n(&text) };
+
+        let Some(id) = element.attributes.get("id") else {
+            warn!("Skip bundled collection without id");
+            return None;
+        };
+
+        let apps = element
+            .children
+            .iter()
+            .filter_map(XMLNode::as_element)
+            .filter(|e| e.name == "app")
+            .filter_map(|e| {
+                let id = e.get_text()?.trim().to_string();
+                Some(match e.attributes.get("remote") {
+                    Some(remote) => AppEntry::Remote {
+                        id,
+                        remote: Some(remote.clone()),
+                    },
+                    None => AppEntry::Id(id),
+                })
+            })
+            .collect();
+
+        Some(CollectionEntry {
+            id: id.clone(),
+            title: translated(text("title")),
+            description: translated(text("description")),
+            apps,
+        })
+    }
+
+    /// Downloads the collections feed from the `collections-url` setting
+    async fn remote_entries(worker: &SkWorker) -> Result<Vec<CollectionEntry>, Error> {
+        let url = SkApplication::default()
+            .settings()
+            .string("collections-url");
+        if url.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let content_types = ["application/json", "text/plain"];
+        let task = worker.download(&url, MAX_FEED_SIZE, &content_types).await?;
+        task.await_result().await?;
+
+        let Some(file) = task.result_download() else {
+            return Ok(Vec::new());
+        };
+        let bytes = file.load_bytes_future().await;
+        path::remove_download(&file.path().unwrap());
+        let bytes = bytes?.0;
+
+        Ok(serde_json::from_slice::<CollectionsFeed>(&bytes)?.collections)
+    }
+
+    /// Looks up the apps of a collection. Apps which aren't provided by any
+    /// configured remote get skipped.
+    async fn resolve(worker: &SkWorker, entry: &CollectionEntry) -> Result<Self, Error> {
+        let ids = entry.apps.iter().map(|a| a.id().to_string()).collect();
+        let packages = worker.lookup_appstream(AppstreamLookup::Ids(ids)).await?;
+
+        let preferred = worker.installations().preferred().info();
+        let apps = gio::ListStore::new::<SkPackageAppstream>();
+
+        for app in &entry.apps {
+            let candidates: Vec<&SkPackageAppstream> = packages
+                .iter()
+                .filter(|a| a.component_id() == app.id())
+                .filter(|a| {
+                    app.remote()
+                        .map_or(true, |r| a.package().remote().name() == r)
+                })
+                .collect();
+
+            // Prefer the package of the preferred installation
+            let appstream = candidates
+                .iter()
+                .find(|a| a.package().remote().info().installation.as_ref() == Some(&preferred))
+                .or(candidates.first());
+
+            match appstream {
+                Some(appstream) => apps.append(*appstream),
+                None => debug!(
+                    "Skip unavailable app \"{}\" of collection \"{}\"",
+                    app.id(),
+                    entry.id
+                ),
+            }
+        }
+
+        Ok(Self::new(
+            &entry.id,
+            &entry.title,
+            &entry.description,
+            &apps,
+        ))
+    }
+}
@@ -0,0 +1,143 @@
+// Souk - content_rating.rs
+// Copyright (C) 2024  Felix Häcker <haeckerfelix@gnome.org>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Mapping of OARS content rating attributes to user facing titles and to the
+//! minimum age of the Common Sense Media (CSM) age ratings.
+//!
+//! See <https://hughsie.github.io/oars/> for the specification.
+
+use appstream::enums::ContentState;
+use appstream::ContentRating;
+
+use crate::main::i18n::i18n;
+
+/// Minimum ages for each attribute, in the order none, mild, moderate,
+/// intense. Matches the table used by libappstream.
+const CSM_AGES: &[(&str, [u32; 4])] = &[
+    ("violence-cartoon", [0, 3, 4, 6]),
+    ("violence-fantasy", [0, 3, 7, 8]),
+    ("violence-realistic", [0, 4, 9, 14]),
+    ("violence-bloodshed", [0, 9, 11, 18]),
+    ("violence-sexual", [0, 18, 18, 18]),
+    ("violence-desecration", [0, 3, 7, 13]),
+    ("violence-slavery", [0, 3, 13, 15]),
+    ("violence-worship", [0, 13, 15, 18]),
+    ("drugs-alcohol", [0, 11, 13, 16]),
+    ("drugs-narcotics", [0, 12, 14, 17]),
+    ("drugs-tobacco", [0, 10, 13, 13]),
+    ("sex-nudity", [0, 12, 14, 14]),
+    ("sex-themes", [0, 13, 14, 15]),
+    ("sex-homosexuality", [0, 10, 13, 18]),
+    ("sex-prostitution", [0, 12, 14, 18]),
+    ("sex-adultery", [0, 8, 10, 18]),
+    ("sex-appearance", [0, 10, 10, 15]),
+    ("language-profanity", [0, 8, 11, 14]),
+    ("language-humor", [0, 3, 8, 14]),
+    ("language-discrimination", [0, 9, 10, 11]),
+    ("money-advertising", [0, 7, 8, 10]),
+    ("money-gambling", [0, 10, 18, 18]),
+    ("money-purchasing", [0, 12, 14, 18]),
+    ("social-chat", [0, 4, 10, 13]),
+    ("social-audio", [0, 15, 15, 15]),
+    ("social-contacts", [0, 12, 12, 12]),
+    ("social-info", [0, 0, 13, 13]),
+    ("social-location", [0, 13, 13, 13]),
+];
+
+/// Returns the minimum age which is required for an attribute with the given
+/// intensity. Unknown attributes don't affect the age.
+pub fn attribute_age(id: &str, state: &ContentState) -> u32 {
+    let Some((_, ages)) = CSM_AGES.iter().find(|(attr, _)| *attr == id) else {
+        return 0;
+    };
+
+    ages[intensity_index(state)]
+}
+
+/// Returns the overall minimum age, which is the highest age of all rated
+/// attributes
+pub fn minimum_age(rating: &ContentRating) -> u32 {
+    rating
+        .attributes
+        .iter()
+        .map(|attr| attribute_age(&attr.id, &attr.value))
+        .max()
+        .unwrap_or(0)
+}
+
+/// Returns the age as short text, eg. "12+" or "All"
+pub fn age_text(age: u32) -> String {
+    if age == 0 {
+        i18n("All")
+    } else {
+        format!("{age}+")
+    }
+}
+
+/// Returns the position of the intensity in the `CSM_AGES` table, which also
+/// can be used to sort attributes by their intensity
+pub fn intensity_index(state: &ContentState) -> usize {
+    match state {
+        ContentState::None => 0,
+        ContentState::Mild => 1,
+        ContentState::Moderate => 2,
+        ContentState::Intense => 3,
+    }
+}
+
+pub fn intensity_title(state: &ContentState) -> String {
+    match state {
+        ContentState::None => i18n("None"),
+        ContentState::Mild => i18n("Mild"),
+        ContentState::Moderate => i18n("Moderate"),
+        ContentState::Intense => i18n("Intense"),
+    }
+}
+
+/// Returns a user facing title for an attribute, eg. "Fantasy Violence"
+pub fn attribute_title(id: &str) -> String {
+    match id {
+        "violence-cartoon" => i18n("Cartoon Violence"),
+        "violence-fantasy" => i18n("Fantasy Violence"),
+        "violence-realistic" => i18n("Realistic Violence"),
+        "violence-bloodshed" => i18n("Bloodshed"),
+        "violence-sexual" => i18n("Sexual Violence"),
+        "violence-desecration" => i18n("Desecration"),
+        "violence-slavery" => i18n("Slavery"),
+        "violence-worship" => i18n("Worship"),
+        "drugs-alcohol" => i18n("Alcohol"),
+        "drugs-narcotics" => i18n("Narcotics"),
+        "drugs-tobacco" => i18n("Tobacco"),
+        "sex-nudity" => i18n("Nudity"),
+        "sex-themes" => i18n("Sexual Themes"),
+        "sex-homosexuality" => i18n("Homosexuality"),
+        "sex-prostitution" => i18n("Prostitution"),
+        "sex-adultery" => i18n("Adultery"),
+        "sex-appearance" => i18n("Sexualized Characters"),
+        "language-profanity" => i18n("Profanity"),
+        "language-humor" => i18n("Inappropriate Humor"),
+        "language-discrimination" => i18n("Discrimination"),
+        "money-advertising" => i18n("Advertising"),
+        "money-gambling" => i18n("Gambling"),
+        "money-purchasing" => i18n("In-App Purchases"),
+        "social-chat" => i18n("Chat Between Users"),
+        "social-audio" => i18n("Audio and Video Chat"),
+        "social-contacts" => i18n("Contact Details"),
+        "social-info" => i18n("Identifying Information"),
+        "social-location" => i18n("Location Sharing"),
+        _ => id.to_string(),
+    }
+}
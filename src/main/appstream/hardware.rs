@@ -0,0 +1,87 @@
+// Souk - hardware.rs
+// Copyright (C) 2024  Felix Häcker <haeckerfelix@gnome.org>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Information about the local device, which gets compared against the
+//! hardware relations of appstream components.
+
+use std::fs;
+
+use gtk::gdk;
+use gtk::prelude::*;
+
+use crate::main::i18n::i18n;
+use crate::shared::appstream::DisplaySide;
+
+/// Returns the length of the given side of the largest connected monitor in
+/// logical pixels
+pub fn display_length(side: DisplaySide) -> Option<u32> {
+    let display = gdk::Display::default()?;
+    let monitors = display.monitors();
+
+    monitors
+        .iter::<gdk::Monitor>()
+        .filter_map(Result::ok)
+        .map(|monitor| {
+            let geometry = monitor.geometry();
+            let (width, height) = (geometry.width() as u32, geometry.height() as u32);
+            match side {
+                DisplaySide::Shortest => width.min(height),
+                DisplaySide::Longest => width.max(height),
+            }
+        })
+        .max()
+}
+
+/// Checks whether the input control is available. Returns `None` if that
+/// can't be determined, eg. for gamepads.
+pub fn has_control(control: &str) -> Option<bool> {
+    let seat = gdk::Display::default()?.default_seat()?;
+    let capabilities = seat.capabilities();
+
+    let capability = match control {
+        "keyboard" => gdk::SeatCapabilities::KEYBOARD,
+        "pointing" => gdk::SeatCapabilities::POINTER,
+        "touch" => gdk::SeatCapabilities::TOUCH,
+        "tablet" => gdk::SeatCapabilities::TABLET_STYLUS,
+        _ => return None,
+    };
+
+    Some(capabilities.contains(capability))
+}
+
+pub fn control_title(control: &str) -> String {
+    match control {
+        "keyboard" => i18n("Keyboard"),
+        "pointing" => i18n("Mouse or Touchpad"),
+        "touch" => i18n("Touchscreen"),
+        "tablet" => i18n("Graphics Tablet"),
+        "gamepad" => i18n("Gamepad"),
+        "console" => i18n("Text Console"),
+        "tv-remote" => i18n("TV Remote"),
+        "voice" => i18n("Voice Control"),
+        "vision" => i18n("Camera Control"),
+        _ => control.to_string(),
+    }
+}
+
+/// Returns the total physical memory in MiB
+pub fn total_memory() -> Option<u64> {
+    let meminfo = fs::read_to_string("/proc/meminfo").ok()?;
+    let line = meminfo.lines().find(|l| l.starts_with("MemTotal:"))?;
+    let kib: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+
+    Some(kib / 1024)
+}
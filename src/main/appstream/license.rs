@@ -0,0 +1,27 @@
+// Souk - license.rs
+// Copyright (C) 2024  Felix Häcker <haeckerfelix@gnome.org>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::main::i18n::i18n;
+
+/// Returns a user facing version of a SPDX license expression, eg.
+/// "LicenseRef-proprietary" gets displayed as "Proprietary"
+pub fn license_text(expression: &str) -> String {
+    if expression.starts_with("LicenseRef-proprietary") {
+        i18n("Proprietary")
+    } else {
+        expression.replace("LicenseRef-", "")
+    }
+}
@@ -0,0 +1,53 @@
+// Souk - license_kind.rs
+// Copyright (C) 2024  Felix Häcker <haeckerfelix@gnome.org>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::main::context::SkContextDetailLevel;
+use crate::main::i18n::i18n;
+use crate::shared::appstream::SkLicenseKind;
+
+impl SkLicenseKind {
+    pub fn title(&self) -> String {
+        match self {
+            Self::Free => i18n("Free Software"),
+            Self::Proprietary => i18n("Proprietary Software"),
+            Self::Unknown => i18n("Unknown License"),
+        }
+    }
+
+    pub fn description(&self) -> String {
+        match self {
+            Self::Free => i18n("Users are allowed to use, study, share and improve this software"),
+            Self::Proprietary => i18n("The source code is not public, so it cannot be independently checked for security and privacy issues"),
+            Self::Unknown => i18n("The developer has not specified a license which allows to use, study, share and improve this software"),
+        }
+    }
+
+    pub fn icon_name(&self) -> &'static str {
+        match self {
+            Self::Free => "emblem-ok-symbolic",
+            Self::Proprietary => "dialog-warning-symbolic",
+            Self::Unknown => "dialog-question-symbolic",
+        }
+    }
+
+    pub fn level(&self) -> SkContextDetailLevel {
+        match self {
+            Self::Free => SkContextDetailLevel::Good,
+            Self::Proprietary => SkContextDetailLevel::Warning,
+            Self::Unknown => SkContextDetailLevel::Neutral,
+        }
+    }
+}
@@ -14,7 +14,20 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
+mod collection;
+pub mod content_rating;
+pub mod hardware;
+pub mod license;
+mod license_kind;
 mod package_appstream;
+mod release;
+mod release_kind;
+mod release_urgency;
 pub mod utils;
 
+pub use crate::shared::appstream::SkLicenseKind;
+pub use collection::{CollectionEntry, SkCollection};
 pub use package_appstream::SkPackageAppstream;
+pub use release::SkRelease;
+pub use release_kind::SkReleaseKind;
+pub use release_urgency::SkReleaseUrgency;
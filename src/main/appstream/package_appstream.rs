@@ -17,7 +17,8 @@
 use std::cell::OnceCell;
 
 use appstream::builders::ComponentBuilder;
-use appstream::{AppId, Component, TranslatableString};
+use appstream::enums::{ImageKind, ProjectUrl};
+use appstream::{AppId, Component, MarkupTranslatableString, TranslatableString};
 use flatpak::prelude::*;
 use flatpak::{Installation, Ref};
 use glib::{ParamSpec, Properties};
@@ -25,9 +26,13 @@ use gtk::gdk::Paintable;
 use gtk::subclass::prelude::*;
 use gtk::{gdk, glib};
 
+use crate::main::appstream::{utils, SkRelease, SkReleaseKind, SkReleaseUrgency};
+use crate::main::context::SkContext;
 use crate::main::flatpak::package::{SkPackage, SkPackageKind, SkPackageSubrefKind};
+use crate::main::flatpak::utils as flatpak_utils;
 use crate::main::i18n::{i18n, i18n_f};
 use crate::main::SkApplication;
+use crate::shared::appstream::{ComponentInfo, ComponentRelations};
 use crate::shared::flatpak::info::PackageInfo;
 
 #[derive(Clone, Debug, glib::Boxed)]
@@ -50,6 +55,8 @@ mod imp {
         #[property(name = "version", get = Self::version, type = String)]
         #[property(name = "summary", get = Self::summary, type = String)]
         component: OnceCell<BoxedComponent>,
+        #[property(get, set, construct_only)]
+        relations: OnceCell<ComponentRelations>,
     }
 
     #[glib::object_subclass]
@@ -121,6 +128,15 @@ mod imp {
             }
         }
 
+        pub fn translated_markup(&self, value: &MarkupTranslatableString) -> String {
+            let locale = self.locale().unwrap_or("C".to_string());
+            value
+                .get_for_locale(&locale)
+                .or_else(|| value.get_default())
+                .cloned()
+                .unwrap_or_default()
+        }
+
         fn translated_value(&self, value: &TranslatableString) -> String {
             let locale = self.locale().unwrap_or("C".to_string());
             let fallback = "–".to_string();
@@ -150,14 +166,49 @@ glib::wrapper! {
 }
 
 impl SkPackageAppstream {
-    pub fn new(package: &SkPackage, icon: &Paintable, component: Component) -> Self {
+    pub fn new(
+        package: &SkPackage,
+        icon: &Paintable,
+        component: Component,
+        relations: &ComponentRelations,
+    ) -> Self {
         glib::Object::builder()
             .property("package", package)
             .property("icon", icon)
             .property("component", BoxedComponent(component))
+            .property("relations", relations)
             .build()
     }
 
+    /// Creates the appstream data of a package which got returned by an
+    /// appstream lookup
+    pub fn from_component_info(info: &ComponentInfo) -> Self {
+        let package = SkPackage::new(&info.package);
+        let icon = Self::icon_from_bytes(info.icon.as_ref());
+        let component = Self::component_from_json(Some(&info.appstream_component), &info.package);
+
+        Self::new(&package, &icon, component, &info.relations)
+    }
+
+    /// Deserializes the component, or returns a fallback component if that's
+    /// not possible
+    pub fn component_from_json(json: Option<&str>, package: &PackageInfo) -> Component {
+        json.and_then(|json| serde_json::from_str(json).ok())
+            .unwrap_or_else(|| Self::fallback_component(package))
+    }
+
+    /// Creates a texture from the icon data, or returns the fallback icon
+    pub fn icon_from_bytes(bytes: Option<&Vec<u8>>) -> Paintable {
+        let texture = bytes.and_then(|bytes| {
+            let bytes = glib::Bytes::from_owned(bytes.clone());
+            gdk::Texture::from_bytes(&bytes).ok()
+        });
+
+        texture
+            .map(|texture| texture.upcast())
+            .unwrap_or_else(Self::fallback_icon)
+    }
+
     pub fn fallback_component(package: &PackageInfo) -> Component {
         let ref_ = Ref::parse(&package.ref_).unwrap();
         let app_id = ref_.name().unwrap().to_string();
@@ -176,6 +227,125 @@ impl SkPackageAppstream {
         .upcast()
     }
 
+    /// Localized description as plain text
+    pub fn description(&self) -> String {
+        self.component()
+            .0
+            .description
+            .as_ref()
+            .map(|d| utils::markup_to_text(&self.imp().translated_markup(d)))
+            .unwrap_or_default()
+    }
+
+    /// Project links, eg. the homepage or the issue tracker
+    pub fn urls(&self) -> Vec<ProjectUrl> {
+        self.component().0.urls
+    }
+
+    /// The appstream component id, eg. `de.haeckerfelix.Souk`
+    pub fn component_id(&self) -> String {
+        self.component().0.id.0
+    }
+
+    /// Returns the image url of each screenshot, with the default screenshot
+    /// first. Source images are preferred over thumbnails.
+    pub fn screenshot_urls(&self) -> Vec<String> {
+        let mut screenshots = self.component().0.screenshots;
+        screenshots.sort_by_key(|s| !s.is_default);
+
+        screenshots
+            .iter()
+            .filter_map(|screenshot| {
+                let image = screenshot
+                    .images
+                    .iter()
+                    .find(|i| matches!(i.kind, ImageKind::Source))
+                    .or_else(|| screenshot.images.iter().max_by_key(|i| i.width))?;
+                Some(image.url.to_string())
+            })
+            .collect()
+    }
+
+    /// Returns all releases which are newer than the installed version of the
+    /// package, newest first. `self` is the appstream data of the update
+    /// candidate.
+    pub fn release_notes(&self, installed: &SkPackage) -> Vec<SkRelease> {
+        let version = flatpak_utils::installed_version(installed);
+        self.releases_since(version.as_deref())
+    }
+
+    /// Returns all releases which are newer than `version`, newest first. If
+    /// the version is unknown, only the latest release gets returned.
+    pub fn releases_since(&self, version: Option<&str>) -> Vec<SkRelease> {
+        let releases = self.releases().into_iter();
+
+        if let Some(version) = version {
+            releases
+                .take_while(|r| utils::compare_versions(&r.version(), version).is_gt())
+                .collect()
+        } else {
+            releases.take(1).collect()
+        }
+    }
+
+    /// Returns all releases, newest first
+    pub fn releases(&self) -> Vec<SkRelease> {
+        let mut releases = self.component().0.releases;
+        releases.sort_by(|r1, r2| utils::compare_versions(&r2.version, &r1.version));
+
+        releases
+            .iter()
+            .map(|release| {
+                let description = release
+                    .description
+                    .as_ref()
+                    .map(|d| utils::markup_to_text(&self.imp().translated_markup(d)))
+                    .unwrap_or_default();
+                let date = release
+                    .date
+                    .and_then(|date| glib::DateTime::from_unix_utc(date.timestamp()).ok());
+
+                SkRelease::new(
+                    &release.version,
+                    &description,
+                    date.as_ref(),
+                    SkReleaseKind::from(&release.kind),
+                    SkReleaseUrgency::from(&release.urgency),
+                )
+            })
+            .collect()
+    }
+
+    /// Returns the SPDX license expression of the project, eg.
+    /// "GPL-3.0-or-later"
+    pub fn project_license(&self) -> Option<String> {
+        self.component().0.project_license.map(|l| l.0)
+    }
+
+    /// Returns the SPDX license expression of the appstream metadata itself
+    pub fn metadata_license(&self) -> Option<String> {
+        self.component().0.metadata_license.map(|l| l.0)
+    }
+
+    pub fn license_context(&self) -> SkContext {
+        SkContext::license(
+            self.project_license().as_deref(),
+            self.metadata_license().as_deref(),
+            None,
+        )
+    }
+
+    /// Compares the hardware relations against the local device
+    pub fn hardware_context(&self) -> SkContext {
+        let arch = self.package().architecture();
+        SkContext::hardware(&self.relations(), Some(&arch))
+    }
+
+    /// Returns the OARS content rating as context, including the minimum age
+    pub fn content_rating_context(&self) -> SkContext {
+        SkContext::content_rating(self.component().0.content_rating.as_ref())
+    }
+
     /// Returns the version as user friendly text, eg. "Version 3.1" or "Unknown
     /// Version"
     pub fn version_text(&self, include_branch: bool) -> String {
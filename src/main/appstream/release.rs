@@ -0,0 +1,99 @@
+// Souk - release.rs
+// Copyright (C) 2024  Felix Häcker <haeckerfelix@gnome.org>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::cell::{Cell, OnceCell};
+
+use glib::{ParamSpec, Properties};
+use gtk::glib;
+use gtk::prelude::*;
+use gtk::subclass::prelude::*;
+
+use super::{SkReleaseKind, SkReleaseUrgency};
+use crate::main::i18n::i18n_f;
+
+mod imp {
+    use super::*;
+
+    #[derive(Debug, Default, Properties)]
+    #[properties(wrapper_type = super::SkRelease)]
+    pub struct SkRelease {
+        #[property(get, set, construct_only)]
+        version: OnceCell<String>,
+        /// Localized release description as plain text
+        #[property(get, set, construct_only)]
+        description: OnceCell<String>,
+        #[property(get, set, construct_only)]
+        date: OnceCell<Option<glib::DateTime>>,
+        #[property(get, set, construct_only, builder(SkReleaseKind::Stable))]
+        kind: Cell<SkReleaseKind>,
+        #[property(get, set, construct_only, builder(SkReleaseUrgency::Unknown))]
+        urgency: Cell<SkReleaseUrgency>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for SkRelease {
+        const NAME: &'static str = "SkRelease";
+        type Type = super::SkRelease;
+    }
+
+    impl ObjectImpl for SkRelease {
+        fn properties() -> &'static [ParamSpec] {
+            Self::derived_properties()
+        }
+
+        fn property(&self, id: usize, pspec: &ParamSpec) -> glib::Value {
+            Self::derived_property(self, id, pspec)
+        }
+
+        fn set_property(&self, id: usize, value: &glib::Value, pspec: &ParamSpec) {
+            Self::derived_set_property(self, id, value, pspec)
+        }
+    }
+}
+
+glib::wrapper! {
+    pub struct SkRelease(ObjectSubclass<imp::SkRelease>);
+}
+
+impl SkRelease {
+    pub fn new(
+        version: &str,
+        description: &str,
+        date: Option<&glib::DateTime>,
+        kind: SkReleaseKind,
+        urgency: SkReleaseUrgency,
+    ) -> Self {
+        glib::Object::builder()
+            .property("version", version)
+            .property("description", description)
+            .property("date", date)
+            .property("kind", kind)
+            .property("urgency", urgency)
+            .build()
+    }
+
+    /// Returns the version and the release date (in the locale format) as
+    /// user friendly text
+    pub fn title(&self) -> String {
+        let date = self.date().and_then(|date| date.format("%x").ok());
+
+        if let Some(date) = date {
+            i18n_f("Version {} – {}", &[&self.version(), &date])
+        } else {
+            i18n_f("Version {}", &[&self.version()])
+        }
+    }
+}
@@ -0,0 +1,48 @@
+// Souk - release_kind.rs
+// Copyright (C) 2024  Felix Häcker <haeckerfelix@gnome.org>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use appstream::enums::ReleaseKind;
+use glib::Enum;
+use gtk::glib;
+
+use crate::main::i18n::i18n;
+
+#[derive(Default, Copy, Debug, Clone, Eq, PartialEq, Enum)]
+#[repr(u32)]
+#[enum_type(name = "SkReleaseKind")]
+pub enum SkReleaseKind {
+    #[default]
+    Stable,
+    Development,
+}
+
+impl SkReleaseKind {
+    pub fn title(&self) -> String {
+        match self {
+            Self::Stable => i18n("Stable Release"),
+            Self::Development => i18n("Development Release"),
+        }
+    }
+}
+
+impl From<&ReleaseKind> for SkReleaseKind {
+    fn from(kind: &ReleaseKind) -> Self {
+        match kind {
+            ReleaseKind::Development => Self::Development,
+            _ => Self::Stable,
+        }
+    }
+}
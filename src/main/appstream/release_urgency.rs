@@ -0,0 +1,57 @@
+// Souk - release_urgency.rs
+// Copyright (C) 2024  Felix Häcker <haeckerfelix@gnome.org>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use appstream::enums::ReleaseUrgency;
+use glib::Enum;
+use gtk::glib;
+
+use crate::main::i18n::i18n;
+
+#[derive(Default, Copy, Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Enum)]
+#[repr(u32)]
+#[enum_type(name = "SkReleaseUrgency")]
+pub enum SkReleaseUrgency {
+    #[default]
+    Unknown,
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+impl SkReleaseUrgency {
+    pub fn title(&self) -> String {
+        match self {
+            Self::Unknown => String::new(),
+            Self::Low => i18n("Low Urgency"),
+            Self::Medium => i18n("Medium Urgency"),
+            Self::High => i18n("High Urgency"),
+            Self::Critical => i18n("Critical Update"),
+        }
+    }
+}
+
+impl From<&ReleaseUrgency> for SkReleaseUrgency {
+    fn from(urgency: &ReleaseUrgency) -> Self {
+        match urgency {
+            ReleaseUrgency::Low => Self::Low,
+            ReleaseUrgency::Medium => Self::Medium,
+            ReleaseUrgency::High => Self::High,
+            ReleaseUrgency::Critical => Self::Critical,
+            _ => Self::Unknown,
+        }
+    }
+}
@@ -14,10 +14,18 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
+use std::cmp::Ordering;
+use std::sync::LazyLock;
+
 use gio::Cancellable;
-use gtk::gio;
+use gtk::{gdk, gio, glib};
+use regex::Regex;
 use xb::prelude::*;
 
+use crate::main::error::Error;
+use crate::main::flatpak::installation::SkRemote;
+use crate::main::i18n::{i18n_f, ni18n_f};
+use crate::shared::appstream::{MediaCache, RemoteSyncStatus};
 use crate::shared::path;
 
 /// Check if a appstream xmlb silo exists at all (it doesn't have to be up to
@@ -40,3 +48,122 @@ pub fn check_appstream_silo_exists() -> bool {
 
     false
 }
+
+/// Returns a user facing description of the appstream sync state of a remote,
+/// eg. "Flathub catalogue is 12 days old: network error". Returns `None` if the
+/// last sync was successful.
+pub fn remote_sync_status_text(remote: &SkRemote) -> Option<String> {
+    let result = remote.sync_result()?;
+    let title = if remote.title().is_empty() {
+        remote.name()
+    } else {
+        remote.title()
+    };
+
+    let RemoteSyncStatus::Failed(error) = result.status else {
+        return None;
+    };
+
+    let text = if let Some(last_success) = result.last_success {
+        let now = glib::DateTime::now_utc().unwrap().to_unix();
+        let days = ((now - last_success) / 86400).max(0) as u32;
+
+        ni18n_f(
+            "{} catalogue is {} day old: {}",
+            "{} catalogue is {} days old: {}",
+            days,
+            &[&title, &days.to_string(), &error],
+        )
+    } else {
+        i18n_f("{} catalogue is not available: {}", &[&title, &error])
+    };
+
+    Some(text)
+}
+
+/// Returns the component id of an `appstream:` uri, eg. "org.gnome.Maps" for
+/// "appstream://org.gnome.Maps"
+pub fn appstream_uri_component_id(uri: &str) -> Option<String> {
+    let id = uri.strip_prefix("appstream:")?;
+    let id = id.split(['?', '#']).next().unwrap_or_default();
+    let id = id.trim_matches('/');
+
+    if id.is_empty() {
+        None
+    } else {
+        Some(id.to_string())
+    }
+}
+
+/// Loads a screenshot from the screenshot cache, or downloads it if it isn't
+/// cached yet
+pub async fn load_screenshot(url: &str) -> Result<gdk::Texture, Error> {
+    let path = MediaCache::screenshots().fetch_async(url).await?;
+    let file = gio::File::for_path(path);
+    Ok(gdk::Texture::from_file(&file)?)
+}
+
+/// Compares two version strings, similar to `as_vercmp`. Numeric parts get
+/// compared by value, everything else alphabetically.
+pub fn compare_versions(a: &str, b: &str) -> Ordering {
+    let tokenize = |version: &str| -> Vec<String> {
+        let mut tokens: Vec<String> = Vec::new();
+        let mut last_is_digit = None;
+
+        for c in version.chars() {
+            if !c.is_alphanumeric() {
+                last_is_digit = None;
+                continue;
+            }
+
+            if last_is_digit == Some(c.is_ascii_digit()) {
+                tokens.last_mut().unwrap().push(c);
+            } else {
+                tokens.push(c.to_string());
+            }
+            last_is_digit = Some(c.is_ascii_digit());
+        }
+
+        tokens
+    };
+
+    let a = tokenize(a);
+    let b = tokenize(b);
+
+    for (a, b) in a.iter().zip(b.iter()) {
+        let ordering = match (a.parse::<u64>(), b.parse::<u64>()) {
+            (Ok(a), Ok(b)) => a.cmp(&b),
+            // Numbers are newer than letters, eg. "1.0" > "1.rc1"
+            (Ok(_), Err(_)) => Ordering::Greater,
+            (Err(_), Ok(_)) => Ordering::Less,
+            (Err(_), Err(_)) => a.cmp(b),
+        };
+
+        if ordering.is_ne() {
+            return ordering;
+        }
+    }
+
+    a.len().cmp(&b.len())
+}
+
+/// Converts appstream description markup into plain text
+pub fn markup_to_text(markup: &str) -> String {
+    static TAG: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"<[^>]*>").unwrap());
+
+    let text = markup
+        .replace("<li>", "• ")
+        .replace("</li>", "\n")
+        .replace("</p>", "\n\n");
+    let text = TAG.replace_all(&text, "");
+
+    // Resolve XML entities
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+        .trim()
+        .to_string()
+}
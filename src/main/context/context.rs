@@ -16,15 +16,26 @@
 
 use std::cell::OnceCell;
 
+use appstream::ContentRating;
 use glib::{ParamSpec, Properties};
 use gtk::glib;
 use gtk::prelude::*;
 use gtk::subclass::prelude::*;
+use url::Url;
 
-use crate::main::context::{SkContextDetail, SkContextDetailGroup, SkContextDetailGroupModel};
-use crate::main::flatpak::permissions::types::{SkFilesystemPermission, SkServicePermission};
+use crate::main::appstream::{content_rating, hardware, license, SkLicenseKind};
+use crate::main::context::{
+    SkContextDetail, SkContextDetailGroup, SkContextDetailGroupModel, SkContextDetailKind,
+    SkContextDetailLevel,
+};
+use crate::main::flatpak::permissions::types::{
+    SkEnvironmentPermission, SkFilesystemPermission, SkServicePermission,
+};
 use crate::main::flatpak::permissions::{PermissionDetails, SkAppPermissions, SkPermissionSummary};
-use crate::main::i18n::i18n;
+use crate::main::i18n::{i18n, i18n_f};
+use crate::shared::appstream::license::license_kind;
+use crate::shared::appstream::{ComponentRelations, RelationCompare, RelationItem};
+use crate::shared::flatpak::{DiskUsage, PermissionChange};
 
 mod imp {
     use super::*;
@@ -80,9 +91,42 @@ impl SkContext {
         general_details.append(&mut permissions.subsystems().context_details());
         general_details.append(&mut permissions.devices().context_details());
         general_details.append(&mut permissions.sockets().context_details());
+        general_details.append(&mut permissions.features().context_details());
         summary |= permissions.subsystems().summary();
         summary |= permissions.devices().summary();
         summary |= permissions.sockets().summary();
+        summary |= permissions.features().summary();
+
+        for value in permissions.environment().snapshot() {
+            let value: SkEnvironmentPermission = value.downcast().unwrap();
+            general_details.append(&mut value.context_details());
+            summary |= value.summary();
+        }
+
+        if permissions.persistent().n_items() != 0 {
+            let paths: Vec<String> = permissions
+                .persistent()
+                .snapshot()
+                .iter()
+                .map(|p| {
+                    p.downcast_ref::<gtk::StringObject>()
+                        .unwrap()
+                        .string()
+                        .into()
+                })
+                .collect();
+
+            general_details.push(SkContextDetail::new(
+                SkContextDetailKind::Icon,
+                "folder-symbolic",
+                SkContextDetailLevel::Neutral,
+                &i18n("Persistent Folders"),
+                &i18n_f(
+                    "Keeps “{}” in its own data folder instead of the home folder",
+                    &[&paths.join(", ")],
+                ),
+            ));
+        }
 
         let description = i18n("The isolated environment does not protect against malicious applications. Applications can request additional permissions at runtime. However, these must be explicitly confirmed.");
         let group = SkContextDetailGroup::new(None, Some(&description));
@@ -107,12 +151,17 @@ impl SkContext {
 
         // Services
         let mut service_details = Vec::new();
+        let mut ignored_details = Vec::new();
         for value in permissions.services().snapshot() {
             let value: SkServicePermission = value.downcast().unwrap();
-            service_details.push(value.context_details()[0].clone());
-            summary |= value.summary();
+            if value.is_ignored() {
+                ignored_details.push(value.context_details()[0].clone());
+            } else {
+                service_details.push(value.context_details()[0].clone());
+                summary |= value.summary();
+            }
         }
-        if permissions.services().n_items() == 0 {
+        if service_details.is_empty() {
             service_details.push(SkServicePermission::no_access_context());
         }
 
@@ -121,6 +170,14 @@ impl SkContext {
         group.add_details(&service_details);
         groups.push(group);
 
+        if !ignored_details.is_empty() {
+            let title = i18n("Ignored Services");
+            let description = i18n("Commonly used services for desktop integration, which are not considered as additional permission");
+            let group = SkContextDetailGroup::new(Some(&title), Some(&description));
+            group.add_details(&ignored_details);
+            groups.push(group);
+        }
+
         // Summary
         let summary = summary.as_context_detail();
 
@@ -128,4 +185,574 @@ impl SkContext {
         model.add_groups(&groups);
         Self::new(&summary, &model)
     }
+
+    /// Differences between the permissions of the installed and the new
+    /// version of a package
+    pub fn permission_changes(changes: &[PermissionChange]) -> Self {
+        let mut groups = Vec::new();
+        let mut summary = SkPermissionSummary::empty();
+
+        let (extending, reducing): (Vec<&PermissionChange>, Vec<&PermissionChange>) =
+            changes.iter().partition(|c| c.is_extending());
+
+        if !extending.is_empty() {
+            let mut details = Vec::new();
+            for change in &extending {
+                details.append(&mut change.context_details());
+                summary |= change.summary();
+            }
+
+            let title = i18n("New Permissions");
+            let group = SkContextDetailGroup::new(Some(&title), None);
+            group.add_details(&details);
+            groups.push(group);
+        }
+
+        if !reducing.is_empty() {
+            let details: Vec<SkContextDetail> =
+                reducing.iter().flat_map(|c| c.context_details()).collect();
+
+            let title = i18n("Removed Permissions");
+            let group = SkContextDetailGroup::new(Some(&title), None);
+            group.add_details(&details);
+            groups.push(group);
+        }
+
+        // Summary
+        let (level, title, description) = if changes.is_empty() {
+            (
+                SkContextDetailLevel::Good,
+                i18n("No Permission Changes"),
+                i18n("The update requests the same permissions as the installed version"),
+            )
+        } else if extending.is_empty() {
+            (
+                SkContextDetailLevel::Good,
+                i18n("Reduced Permissions"),
+                i18n("The update requests fewer permissions than the installed version"),
+            )
+        } else if summary.is_empty() {
+            (
+                SkContextDetailLevel::Neutral,
+                i18n("Changed Permissions"),
+                i18n("The update requests different permissions than the installed version"),
+            )
+        } else {
+            (
+                SkContextDetailLevel::Warning,
+                i18n("Additional Permissions"),
+                summary.as_context_detail().description(),
+            )
+        };
+        let summary = SkContextDetail::new(
+            SkContextDetailKind::Icon,
+            "security-medium-symbolic",
+            level,
+            &title,
+            &description,
+        );
+
+        let model = SkContextDetailGroupModel::new();
+        model.add_groups(&groups);
+        Self::new(&summary, &model)
+    }
+
+    /// Storage which is used by an already installed package
+    pub fn installed_size(size: u64, name: &str, summary: &str) -> Self {
+        let description = i18n("Shared system packages are not included, since they are used by other applications as well.");
+        let group = SkContextDetailGroup::new(None, Some(&description));
+        group.add_details(&[SkContextDetail::new_neutral_size(size, name, summary)]);
+
+        let title = i18n_f("{} storage used", &[&glib::format_size(size)]);
+        let descr = i18n("Size of the installed package");
+        let summary = SkContextDetail::new(
+            SkContextDetailKind::Icon,
+            "drive-harddisk-system-symbolic",
+            SkContextDetailLevel::Neutral,
+            &title,
+            &descr,
+        );
+
+        let model = SkContextDetailGroupModel::new();
+        model.add_groups(&[group]);
+        Self::new(&summary, &model)
+    }
+
+    /// Storage which is used by an installed app, its exclusively used
+    /// runtimes / extensions and its sandbox data
+    pub fn storage(usage: &DiskUsage, name: &str, summary: &str) -> Self {
+        let mut groups = Vec::new();
+
+        // App
+        let group = SkContextDetailGroup::new(None, None);
+        group.add_details(&[SkContextDetail::new_neutral_size(
+            usage.deploy_size,
+            name,
+            summary,
+        )]);
+        groups.push(group);
+
+        // Exclusively used runtimes / extensions
+        if !usage.exclusive_refs.is_empty() {
+            let details: Vec<SkContextDetail> = usage
+                .exclusive_refs
+                .iter()
+                .map(|ref_usage| {
+                    // "runtime/org.gnome.Platform/x86_64/46" -> "org.gnome.Platform 46"
+                    let parts: Vec<&str> = ref_usage.ref_.split('/').collect();
+                    let title = match parts.as_slice() {
+                        [_, name, _, branch] => format!("{name} {branch}"),
+                        _ => ref_usage.ref_.clone(),
+                    };
+
+                    SkContextDetail::new_neutral_size(
+                        ref_usage.size,
+                        &title,
+                        &i18n("Not used by any other application"),
+                    )
+                })
+                .collect();
+
+            let title = i18n("System Packages");
+            let description = i18n("These components are only used by this application, and would no longer be needed after uninstalling it.");
+            let group = SkContextDetailGroup::new(Some(&title), Some(&description));
+            group.add_details(&details);
+            groups.push(group);
+        }
+
+        // Sandbox data
+        let title = i18n("User Data");
+        let description =
+            i18n("Data which got created by the application, stored in the home folder.");
+        let group = SkContextDetailGroup::new(Some(&title), Some(&description));
+        group.add_details(&[
+            SkContextDetail::new_neutral_size(
+                usage.cache_size,
+                &i18n("Cache"),
+                &i18n("Temporary files which can be recreated"),
+            ),
+            SkContextDetail::new_neutral_size(
+                usage.config_size,
+                &i18n("Configuration"),
+                &i18n("Settings and preferences"),
+            ),
+            SkContextDetail::new_neutral_size(
+                usage.data_size,
+                &i18n("Data"),
+                &i18n("Documents, databases and other persistent files"),
+            ),
+        ]);
+        groups.push(group);
+
+        // Summary
+        let title = i18n_f("{} storage used", &[&glib::format_size(usage.total_size())]);
+        let descr = if usage.user_data_size() == 0 {
+            i18n("Includes unused system packages")
+        } else {
+            i18n_f(
+                "Includes {} of user data",
+                &[&glib::format_size(usage.user_data_size())],
+            )
+        };
+        let summary = SkContextDetail::new(
+            SkContextDetailKind::Icon,
+            "drive-harddisk-system-symbolic",
+            SkContextDetailLevel::Neutral,
+            &title,
+            &descr,
+        );
+
+        let model = SkContextDetailGroupModel::new();
+        model.add_groups(&groups);
+        Self::new(&summary, &model)
+    }
+
+    pub fn content_rating(rating: Option<&ContentRating>) -> Self {
+        let model = SkContextDetailGroupModel::new();
+
+        let Some(rating) = rating else {
+            let summary = SkContextDetail::new(
+                SkContextDetailKind::Icon,
+                "dialog-question-symbolic",
+                SkContextDetailLevel::Neutral,
+                &i18n("Age rating unknown"),
+                &i18n("No information about the content of this app is available"),
+            );
+            return Self::new(&summary, &model);
+        };
+
+        // Rated attributes, most intense first
+        let mut attributes: Vec<_> = rating.attributes.iter().collect();
+        attributes
+            .sort_by_key(|attr| std::cmp::Reverse(content_rating::intensity_index(&attr.value)));
+
+        let mut details = Vec::new();
+        let mut notable = Vec::new();
+        for attr in attributes {
+            let title = content_rating::attribute_title(&attr.id);
+            let age = content_rating::attribute_age(&attr.id, &attr.value);
+            let level = match content_rating::intensity_index(&attr.value) {
+                0 => SkContextDetailLevel::Good,
+                1 => SkContextDetailLevel::Minor,
+                2 => SkContextDetailLevel::Moderate,
+                _ => SkContextDetailLevel::Warning,
+            };
+
+            let description = content_rating::intensity_title(&attr.value);
+
+            if age != 0 {
+                notable.push(title.clone());
+            }
+
+            details.push(SkContextDetail::new(
+                SkContextDetailKind::Text,
+                &content_rating::age_text(age),
+                level,
+                &title,
+                &description,
+            ));
+        }
+
+        if details.is_empty() {
+            details.push(SkContextDetail::new(
+                SkContextDetailKind::Icon,
+                "checkmark-symbolic",
+                SkContextDetailLevel::Good,
+                &i18n("No inappropriate content"),
+                &i18n("The developer declared that this app contains no objectionable content"),
+            ));
+        }
+
+        let description = i18n("The age rating is based on the information provided by the developer using the Open Age Ratings Service (OARS).");
+        let group = SkContextDetailGroup::new(None, Some(&description));
+        group.add_details(&details);
+        model.add_groups(&[group]);
+
+        // Summary
+        let age = content_rating::minimum_age(rating);
+        let (level, title) = if age == 0 {
+            (SkContextDetailLevel::Good, i18n("Suitable for all ages"))
+        } else {
+            let level = if age < 13 {
+                SkContextDetailLevel::Minor
+            } else if age < 18 {
+                SkContextDetailLevel::Moderate
+            } else {
+                SkContextDetailLevel::Warning
+            };
+            let title = i18n_f("Suitable for ages {}+", &[&age.to_string()]);
+            (level, title)
+        };
+
+        let description = if notable.is_empty() {
+            i18n("Contains no inappropriate content")
+        } else {
+            i18n_f("Contains {}", &[&notable.join(", ")])
+        };
+
+        let summary = SkContextDetail::new(
+            SkContextDetailKind::Text,
+            &content_rating::age_text(age),
+            level,
+            &title,
+            &description,
+        );
+        Self::new(&summary, &model)
+    }
+
+    pub fn license(
+        project_license: Option<&str>,
+        metadata_license: Option<&str>,
+        extra_data_source: Option<&Url>,
+    ) -> Self {
+        let kind = project_license.map(license_kind).unwrap_or_default();
+        let mut details = Vec::new();
+
+        // Project license
+        let description = project_license
+            .map(license::license_text)
+            .unwrap_or_else(|| i18n("No license specified"));
+        details.push(SkContextDetail::new(
+            SkContextDetailKind::Icon,
+            kind.icon_name(),
+            kind.level(),
+            &kind.title(),
+            &description,
+        ));
+
+        // Proprietary data which gets downloaded during the installation
+        if let Some(url) = extra_data_source {
+            let domain = url.domain().unwrap_or(url.as_str());
+            details.push(SkContextDetail::new(
+                SkContextDetailKind::Icon,
+                "folder-download-symbolic",
+                SkContextDetailLevel::Warning,
+                &i18n("Downloads Proprietary Data"),
+                &i18n_f(
+                    "Additional data gets downloaded from an external source ({}) which may not be free software",
+                    &[domain],
+                ),
+            ));
+        }
+
+        // Metadata license
+        if let Some(metadata_license) = metadata_license {
+            details.push(SkContextDetail::new(
+                SkContextDetailKind::Icon,
+                "text-x-generic-symbolic",
+                SkContextDetailLevel::Neutral,
+                &i18n("Metadata License"),
+                &license::license_text(metadata_license),
+            ));
+        }
+
+        let description = i18n("The license specifies the terms under which the software may be used, modified and distributed.");
+        let group = SkContextDetailGroup::new(None, Some(&description));
+        group.add_details(&details);
+
+        let model = SkContextDetailGroupModel::new();
+        model.add_groups(&[group]);
+
+        // Summary
+        let (level, description) =
+            if extra_data_source.is_some() && kind != SkLicenseKind::Proprietary {
+                (
+                    SkContextDetailLevel::Moderate,
+                    i18n("Downloads additional proprietary data during installation"),
+                )
+            } else {
+                (kind.level(), kind.description())
+            };
+
+        let summary = SkContextDetail::new(
+            SkContextDetailKind::Icon,
+            kind.icon_name(),
+            level,
+            &kind.title(),
+            &description,
+        );
+        Self::new(&summary, &model)
+    }
+
+    /// Compares the hardware relations of a component against the local
+    /// device. `arch` is the architecture of the ref, if it's known.
+    pub fn hardware(relations: &ComponentRelations, arch: Option<&str>) -> Self {
+        let mut details = Vec::new();
+
+        for (items, required) in [(&relations.requires, true), (&relations.recommends, false)] {
+            // It's enough if one of the listed controls is available
+            let any_control = items.iter().any(|item| match item {
+                RelationItem::Control(control) => hardware::has_control(control) == Some(true),
+                _ => false,
+            });
+
+            for item in items {
+                if let Some(detail) = Self::hardware_detail(item, required, any_control) {
+                    details.push(detail);
+                }
+            }
+        }
+
+        for item in &relations.supports {
+            if let RelationItem::Control(control) = item {
+                let level = if hardware::has_control(control) == Some(true) {
+                    SkContextDetailLevel::Good
+                } else {
+                    SkContextDetailLevel::Neutral
+                };
+
+                details.push(SkContextDetail::new(
+                    SkContextDetailKind::Icon,
+                    "input-keyboard-symbolic",
+                    level,
+                    &hardware::control_title(control),
+                    &i18n("Supported"),
+                ));
+            }
+        }
+
+        // Architecture
+        if let Some(arch) = arch {
+            let default_arch = flatpak::functions::default_arch()
+                .map(|a| a.to_string())
+                .unwrap_or_default();
+            let supported_arches = flatpak::functions::supported_arches();
+
+            if arch != default_arch {
+                let (level, title) = if supported_arches.iter().any(|a| a.as_str() == arch) {
+                    (
+                        SkContextDetailLevel::Moderate,
+                        i18n("Runs in Compatibility Mode"),
+                    )
+                } else {
+                    (SkContextDetailLevel::Bad, i18n("Unsupported Architecture"))
+                };
+
+                details.push(SkContextDetail::new(
+                    SkContextDetailKind::Icon,
+                    "computer-symbolic",
+                    level,
+                    &title,
+                    &i18n_f(
+                        "Built for {}, but this device uses {}",
+                        &[arch, &default_arch],
+                    ),
+                ));
+            }
+        }
+
+        let model = SkContextDetailGroupModel::new();
+        if !details.is_empty() {
+            let description = i18n("The hardware requirements are specified by the developer and compared with this device.");
+            let group = SkContextDetailGroup::new(None, Some(&description));
+            group.add_details(&details);
+            model.add_groups(&[group]);
+        }
+
+        // Summary, based on the most severe detail
+        let worst = details.iter().max_by_key(|d| d.level() as u32);
+        let summary = match worst {
+            None => SkContextDetail::new(
+                SkContextDetailKind::Icon,
+                "computer-symbolic",
+                SkContextDetailLevel::Neutral,
+                &i18n("No Hardware Requirements"),
+                &i18n("The developer has not specified any hardware requirements"),
+            ),
+            Some(worst) if worst.level() == SkContextDetailLevel::Bad => SkContextDetail::new(
+                SkContextDetailKind::Icon,
+                "dialog-error-symbolic",
+                SkContextDetailLevel::Bad,
+                &i18n("Not Supported on This Device"),
+                &worst.description(),
+            ),
+            Some(worst)
+                if worst.level() == SkContextDetailLevel::Warning
+                    || worst.level() == SkContextDetailLevel::Moderate =>
+            {
+                SkContextDetail::new(
+                    SkContextDetailKind::Icon,
+                    "dialog-warning-symbolic",
+                    worst.level(),
+                    &i18n("May Not Work Well on This Device"),
+                    &worst.description(),
+                )
+            }
+            Some(_) => SkContextDetail::new(
+                SkContextDetailKind::Icon,
+                "computer-symbolic",
+                SkContextDetailLevel::Good,
+                &i18n("Works on This Device"),
+                &i18n("This device meets the hardware requirements"),
+            ),
+        };
+
+        Self::new(&summary, &model)
+    }
+
+    fn hardware_detail(
+        item: &RelationItem,
+        required: bool,
+        any_control: bool,
+    ) -> Option<SkContextDetail> {
+        // Level for unmet requirements / recommendations
+        let unmet_level = if required {
+            SkContextDetailLevel::Bad
+        } else {
+            SkContextDetailLevel::Moderate
+        };
+        let level = |met: Option<bool>| match met {
+            Some(true) => SkContextDetailLevel::Good,
+            Some(false) => unmet_level,
+            None => SkContextDetailLevel::Neutral,
+        };
+
+        let detail = match item {
+            RelationItem::DisplayLength {
+                compare,
+                side,
+                length,
+            } => {
+                let met = hardware::display_length(*side).map(|l| compare.matches(l, *length));
+                let length = length.to_string();
+                let description = match compare {
+                    RelationCompare::Ge | RelationCompare::Gt => {
+                        i18n_f("Needs a screen of at least {} pixels", &[&length])
+                    }
+                    RelationCompare::Le | RelationCompare::Lt => {
+                        i18n_f("Designed for screens of up to {} pixels", &[&length])
+                    }
+                    RelationCompare::Eq | RelationCompare::Ne => {
+                        i18n_f("Designed for screens of {} pixels", &[&length])
+                    }
+                };
+
+                SkContextDetail::new(
+                    SkContextDetailKind::Icon,
+                    "video-display-symbolic",
+                    level(met),
+                    &i18n("Screen Size"),
+                    &description,
+                )
+            }
+            RelationItem::Control(control) => {
+                let mut met = hardware::has_control(control);
+                // Another control of the same relation is available
+                if met == Some(false) && any_control {
+                    met = None;
+                }
+
+                let description = if required {
+                    i18n("Required")
+                } else {
+                    i18n("Recommended")
+                };
+
+                SkContextDetail::new(
+                    SkContextDetailKind::Icon,
+                    "input-keyboard-symbolic",
+                    level(met),
+                    &hardware::control_title(control),
+                    &description,
+                )
+            }
+            RelationItem::Memory(memory) => {
+                let met = hardware::total_memory().map(|m| m >= *memory);
+                let size = glib::format_size(memory * 1024 * 1024);
+                let description = if required {
+                    i18n_f("Needs at least {} of memory", &[&size])
+                } else {
+                    i18n_f("Works best with at least {} of memory", &[&size])
+                };
+
+                SkContextDetail::new(
+                    SkContextDetailKind::Icon,
+                    "computer-symbolic",
+                    level(met),
+                    &i18n("Memory"),
+                    &description,
+                )
+            }
+            RelationItem::Internet(internet) => {
+                let description = match internet.as_str() {
+                    "always" => i18n("Needs a permanent internet connection"),
+                    "first-run" => {
+                        i18n("Needs an internet connection when started for the first time")
+                    }
+                    "offline-only" => i18n("Works without internet connection"),
+                    _ => return None,
+                };
+
+                SkContextDetail::new(
+                    SkContextDetailKind::Icon,
+                    "network-wireless-symbolic",
+                    SkContextDetailLevel::Neutral,
+                    &i18n("Internet Connection"),
+                    &description,
+                )
+            }
+        };
+
+        Some(detail)
+    }
 }
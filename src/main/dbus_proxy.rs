@@ -14,6 +14,10 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
+use std::collections::HashMap;
+
+use zbus::zvariant::OwnedValue;
+
 use crate::shared::config;
 
 #[zbus::proxy(interface = "de.haeckerfelix.Souk.Worker1")]
@@ -42,3 +46,21 @@ impl Default for WorkerProxy<'static> {
         async_std::task::block_on(fut).unwrap()
     }
 }
+
+/// Stores the permissions which got granted through portals
+#[zbus::proxy(
+    interface = "org.freedesktop.impl.portal.PermissionStore",
+    default_service = "org.freedesktop.impl.portal.PermissionStore",
+    default_path = "/org/freedesktop/impl/portal/PermissionStore"
+)]
+trait PermissionStore {
+    fn list(&self, table: &str) -> zbus::Result<Vec<String>>;
+
+    fn lookup(
+        &self,
+        table: &str,
+        id: &str,
+    ) -> zbus::Result<(HashMap<String, Vec<String>>, OwnedValue)>;
+
+    fn delete_permission(&self, table: &str, id: &str, app: &str) -> zbus::Result<()>;
+}
@@ -0,0 +1,93 @@
+// Souk - addon.rs
+// Copyright (C) 2024  Felix Häcker <haeckerfelix@gnome.org>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::cell::OnceCell;
+
+use glib::{ParamSpec, Properties};
+use gtk::glib;
+use gtk::prelude::*;
+use gtk::subclass::prelude::*;
+
+use crate::main::appstream::SkPackageAppstream;
+use crate::main::flatpak::package::{SkPackage, SkPackageImpl};
+use crate::shared::appstream::AddonInfo;
+
+mod imp {
+    use super::*;
+
+    #[derive(Debug, Default, Properties)]
+    #[properties(wrapper_type = super::SkAddon)]
+    pub struct SkAddon {
+        #[property(name = "appstream", get)]
+        appstream: OnceCell<SkPackageAppstream>,
+        #[property(get, set, construct_only)]
+        #[property(name = "extension-point", get, type = Option<String>, member = extension_point)]
+        #[property(name = "is-installed", get, type = bool, member = is_installed)]
+        data: OnceCell<AddonInfo>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for SkAddon {
+        const NAME: &'static str = "SkAddon";
+        type Type = super::SkAddon;
+        type ParentType = SkPackage;
+    }
+
+    impl ObjectImpl for SkAddon {
+        fn properties() -> &'static [ParamSpec] {
+            Self::derived_properties()
+        }
+
+        fn property(&self, id: usize, pspec: &ParamSpec) -> glib::Value {
+            Self::derived_property(self, id, pspec)
+        }
+
+        fn set_property(&self, id: usize, value: &glib::Value, pspec: &ParamSpec) {
+            Self::derived_set_property(self, id, value, pspec)
+        }
+
+        fn constructed(&self) {
+            self.parent_constructed();
+            let data = self.obj().data();
+
+            // Set appstream
+            let icon = SkPackageAppstream::icon_from_bytes(data.icon.as_ref());
+            let component = SkPackageAppstream::component_from_json(
+                data.appstream_component.as_deref(),
+                &data.package,
+            );
+
+            let package: SkPackage = self.obj().clone().upcast();
+            let appstream = SkPackageAppstream::new(&package, &icon, component, &data.relations);
+            self.appstream.set(appstream).unwrap();
+        }
+    }
+
+    impl SkPackageImpl for SkAddon {}
+}
+
+glib::wrapper! {
+    pub struct SkAddon(ObjectSubclass<imp::SkAddon>) @extends SkPackage;
+}
+
+impl SkAddon {
+    pub fn new(data: &AddonInfo) -> Self {
+        glib::Object::builder()
+            .property("info", &data.package)
+            .property("data", data)
+            .build()
+    }
+}
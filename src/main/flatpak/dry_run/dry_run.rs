@@ -31,6 +31,7 @@ use crate::main::flatpak::package::SkPackageExt;
 use crate::main::i18n::{i18n, i18n_f};
 use crate::shared::flatpak::dry_run::DryRun;
 use crate::shared::flatpak::info::RemoteInfo;
+use crate::shared::flatpak::PolicyViolation;
 
 mod imp {
     use super::*;
@@ -213,4 +214,14 @@ impl SkDryRun {
     pub fn installed_size_context(&self) -> SkContext {
         self.imp().size_context(true)
     }
+
+    /// Size of the sandbox data which gets removed by an uninstall task
+    pub fn removed_data_size(&self) -> Option<u64> {
+        self.data().removed_data_size
+    }
+
+    /// Permission policy rules which discourage the installation
+    pub fn policy_warnings(&self) -> Vec<PolicyViolation> {
+        self.data().policy_warnings
+    }
 }
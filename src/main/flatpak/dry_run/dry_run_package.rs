@@ -16,14 +16,13 @@
 
 use std::cell::OnceCell;
 
-use glib::{Bytes, KeyFile, KeyFileFlags, ParamSpec, Properties};
-use gtk::gdk::Paintable;
+use glib::{KeyFile, KeyFileFlags, ParamSpec, Properties};
+use gtk::glib;
 use gtk::prelude::*;
 use gtk::subclass::prelude::*;
-use gtk::{gdk, glib};
 use url::Url;
 
-use crate::main::appstream::SkPackageAppstream;
+use crate::main::appstream::{SkPackageAppstream, SkRelease};
 use crate::main::context::{SkContext, SkContextDetail, SkContextDetailKind, SkContextDetailLevel};
 use crate::main::flatpak::package::{SkPackage, SkPackageExt, SkPackageImpl};
 use crate::main::flatpak::permissions::SkAppPermissions;
@@ -73,27 +72,15 @@ mod imp {
             let data = self.obj().data();
 
             // Set appstream
-            let icon: Paintable = if let Some(icon) = data.icon.as_ref() {
-                let bytes = Bytes::from_owned(icon.clone());
-                if let Ok(texture) = gdk::Texture::from_bytes(&bytes) {
-                    texture.upcast()
-                } else {
-                    SkPackageAppstream::fallback_icon().upcast()
-                }
-            } else {
-                SkPackageAppstream::fallback_icon().upcast()
-            };
-
+            let icon = SkPackageAppstream::icon_from_bytes(data.icon.as_ref());
             let package_info = self.obj().info();
-            let component = if let Some(json) = data.appstream_component.as_ref() {
-                serde_json::from_str(json)
-                    .unwrap_or_else(|_| SkPackageAppstream::fallback_component(&package_info))
-            } else {
-                SkPackageAppstream::fallback_component(&package_info)
-            };
+            let component = SkPackageAppstream::component_from_json(
+                data.appstream_component.as_deref(),
+                &package_info,
+            );
 
             let package: SkPackage = self.obj().clone().upcast();
-            let appstream = SkPackageAppstream::new(&package, &icon, component);
+            let appstream = SkPackageAppstream::new(&package, &icon, component, &data.relations);
             self.appstream.set(appstream).unwrap();
         }
     }
@@ -209,11 +196,38 @@ impl SkDryRunPackage {
         None
     }
 
-    // TODO: Include old permissions as well
+    /// Releases between the installed and the new version, if the package
+    /// gets updated
+    pub fn release_notes(&self) -> Vec<SkRelease> {
+        if self.operation_kind() != SkFlatpakOperationKind::Update {
+            return Vec::new();
+        }
+
+        let old_version = self.data().old_version;
+        self.appstream().releases_since(old_version.as_deref())
+    }
+
+    pub fn license_context(&self) -> SkContext {
+        let appstream = self.appstream();
+        SkContext::license(
+            appstream.project_license().as_deref(),
+            appstream.metadata_license().as_deref(),
+            self.extra_data_source().as_ref(),
+        )
+    }
+
     pub fn permissions_context(&self) -> SkContext {
         SkContext::permissions(&self.permissions())
     }
 
+    /// Added, removed and changed permissions compared to the installed
+    /// version, if the package gets updated
+    pub fn permission_changes_context(&self) -> Option<SkContext> {
+        self.data()
+            .permission_changes
+            .map(|changes| SkContext::permission_changes(&changes))
+    }
+
     // TODO: Rework context info so it makes use of new SkDryRun objects etc.
     pub fn permissions(&self) -> SkAppPermissions {
         // TODO: Make this a gobject property of SkDryRun
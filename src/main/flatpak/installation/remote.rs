@@ -24,6 +24,7 @@ use gtk::{gio, glib};
 
 use super::SkInstallation;
 use crate::main::SkApplication;
+use crate::shared::appstream::RemoteSyncResult;
 use crate::shared::flatpak::info::RemoteInfo;
 
 mod imp {
@@ -119,4 +120,17 @@ impl SkRemote {
     pub fn new(info: &RemoteInfo) -> Self {
         glib::Object::builder().property("info", info).build()
     }
+
+    /// Returns the result of the last appstream sync for this remote
+    pub fn sync_result(&self) -> Option<RemoteSyncResult> {
+        let info = self.info();
+
+        RemoteSyncResult::load().into_values().find(|result| {
+            if info.repository_url.is_empty() {
+                result.remote.name == info.name
+            } else {
+                result.remote.repository_url == info.repository_url
+            }
+        })
+    }
 }
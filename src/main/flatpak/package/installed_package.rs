@@ -0,0 +1,233 @@
+// Souk - installed_package.rs
+// Copyright (C) 2024  Felix Häcker <haeckerfelix@gnome.org>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::cell::OnceCell;
+use std::path::PathBuf;
+
+use appstream::TranslatableString;
+use flatpak::prelude::*;
+use flatpak::{Installation, InstalledRef};
+use glib::{KeyFile, ParamSpec, Properties};
+use gtk::gdk::Paintable;
+use gtk::prelude::*;
+use gtk::subclass::prelude::*;
+use gtk::{gdk, gio, glib};
+
+use super::{SkPackage, SkPackageExt, SkPackageKind, SkPackageSubrefKind};
+use crate::main::appstream::SkPackageAppstream;
+use crate::main::flatpak::installation::SkInstallation;
+use crate::main::worker::SkWorker;
+use crate::shared::appstream::ComponentRelations;
+use crate::shared::task::AppstreamLookup;
+
+mod imp {
+    use super::*;
+
+    #[derive(Debug, Default, Properties)]
+    #[properties(wrapper_type = super::SkInstalledPackage)]
+    pub struct SkInstalledPackage {
+        #[property(get, set, construct_only)]
+        appstream: OnceCell<SkPackageAppstream>,
+        /// Installed size in bytes
+        #[property(get, set, construct_only)]
+        installed_size: OnceCell<u64>,
+        /// Date when the currently deployed commit got installed
+        #[property(get, set, construct_only)]
+        install_date: OnceCell<Option<glib::DateTime>>,
+        /// Ref of the runtime which is used by an app, eg.
+        /// `runtime/org.gnome.Platform/x86_64/46`. Empty for runtimes.
+        #[property(get, set, construct_only)]
+        runtime: OnceCell<String>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for SkInstalledPackage {
+        const NAME: &'static str = "SkInstalledPackage";
+        type Type = super::SkInstalledPackage;
+    }
+
+    impl ObjectImpl for SkInstalledPackage {
+        fn properties() -> &'static [ParamSpec] {
+            Self::derived_properties()
+        }
+
+        fn property(&self, id: usize, pspec: &ParamSpec) -> glib::Value {
+            Self::derived_property(self, id, pspec)
+        }
+
+        fn set_property(&self, id: usize, value: &glib::Value, pspec: &ParamSpec) {
+            Self::derived_set_property(self, id, value, pspec)
+        }
+    }
+}
+
+glib::wrapper! {
+    pub struct SkInstalledPackage(ObjectSubclass<imp::SkInstalledPackage>);
+}
+
+impl SkInstalledPackage {
+    pub fn new(
+        appstream: &SkPackageAppstream,
+        installed_size: u64,
+        install_date: Option<&glib::DateTime>,
+        runtime: &str,
+    ) -> Self {
+        glib::Object::builder()
+            .property("appstream", appstream)
+            .property("installed-size", installed_size)
+            .property("install-date", install_date)
+            .property("runtime", runtime)
+            .build()
+    }
+
+    /// Loads all installed packages of an installation, together with their
+    /// appstream data. Packages without appstream data get a fallback, based
+    /// on the appdata of the installed ref.
+    pub async fn load(installation: &SkInstallation, worker: &SkWorker) -> Vec<Self> {
+        let packages: Vec<SkPackage> = installation
+            .packages()
+            .iter::<SkPackage>()
+            .flatten()
+            .collect();
+
+        let ids = packages
+            .iter()
+            .filter(|p| p.subref_kind() == SkPackageSubrefKind::None)
+            .map(|p| p.name())
+            .collect();
+
+        let appstream = match worker.lookup_appstream(AppstreamLookup::Ids(ids)).await {
+            Ok(appstream) => appstream,
+            Err(err) => {
+                warn!("Unable to lookup installed packages: {}", err.message());
+                Vec::new()
+            }
+        };
+
+        let f_inst = Installation::from(&installation.info());
+        let f_refs = match f_inst.list_installed_refs(gio::Cancellable::NONE) {
+            Ok(f_refs) => f_refs,
+            Err(err) => {
+                warn!("Unable to list installed refs: {}", err.message());
+                return Vec::new();
+            }
+        };
+
+        let mut installed = Vec::new();
+        for package in &packages {
+            let Some(f_ref) = f_refs
+                .iter()
+                .find(|f_ref| f_ref.format_ref().as_deref() == Some(package.info().ref_.as_str()))
+            else {
+                continue;
+            };
+
+            let appstream = appstream
+                .iter()
+                .find(|a| {
+                    a.package().info().ref_ == package.info().ref_
+                        && a.package().remote().name() == package.remote().name()
+                })
+                .cloned()
+                .unwrap_or_else(|| Self::fallback_appstream(package, f_ref));
+
+            installed.push(Self::new(
+                &appstream,
+                f_ref.installed_size(),
+                Self::deploy_date(f_ref).as_ref(),
+                &Self::runtime_ref(package, f_ref).unwrap_or_default(),
+            ));
+        }
+
+        installed
+    }
+
+    pub fn package(&self) -> SkPackage {
+        self.appstream().package()
+    }
+
+    /// Whether the name or the id of the package contains the search text
+    pub fn matches(&self, text: &str) -> bool {
+        let text = text.to_lowercase();
+        self.appstream().name().to_lowercase().contains(&text)
+            || self.package().name().to_lowercase().contains(&text)
+    }
+
+    /// Appstream data which is built from the appdata of the installed ref,
+    /// and from the exported icon
+    fn fallback_appstream(package: &SkPackage, f_ref: &InstalledRef) -> SkPackageAppstream {
+        let mut component = SkPackageAppstream::fallback_component(&package.info());
+        if let Some(name) = f_ref.appdata_name() {
+            component.name = TranslatableString::with_default(&name);
+        }
+        if let Some(summary) = f_ref.appdata_summary() {
+            component.summary = Some(TranslatableString::with_default(&summary));
+        }
+
+        let icon =
+            Self::exported_icon(package, f_ref).unwrap_or_else(SkPackageAppstream::fallback_icon);
+        SkPackageAppstream::new(package, &icon, component, &ComponentRelations::default())
+    }
+
+    fn exported_icon(package: &SkPackage, f_ref: &InstalledRef) -> Option<Paintable> {
+        if package.kind() != SkPackageKind::App {
+            return None;
+        }
+
+        let mut icons = PathBuf::from(f_ref.deploy_dir()?.as_str());
+        icons.push("export/share/icons/hicolor");
+
+        ["128x128/apps", "scalable/apps", "64x64/apps"]
+            .iter()
+            .flat_map(|dir| {
+                ["png", "svg"].map(|ext| icons.join(dir).join(format!("{}.{ext}", package.name())))
+            })
+            .find_map(|path| gdk::Texture::from_filename(path).ok())
+            .map(|texture| texture.upcast())
+    }
+
+    /// The deploy directory gets created when a commit gets deployed, so its
+    /// modification time is the date of the installation / last update
+    fn deploy_date(f_ref: &InstalledRef) -> Option<glib::DateTime> {
+        let deploy_dir = f_ref.deploy_dir()?;
+        let modified = std::fs::metadata(deploy_dir.as_str())
+            .ok()?
+            .modified()
+            .ok()?;
+        let secs = modified
+            .duration_since(std::time::UNIX_EPOCH)
+            .ok()?
+            .as_secs();
+
+        glib::DateTime::from_unix_local(secs as i64).ok()
+    }
+
+    /// Reads the runtime of an app from its metadata
+    fn runtime_ref(package: &SkPackage, f_ref: &InstalledRef) -> Option<String> {
+        if package.kind() != SkPackageKind::App {
+            return None;
+        }
+
+        let metadata = f_ref.load_metadata(gio::Cancellable::NONE).ok()?;
+        let keyfile = KeyFile::new();
+        keyfile
+            .load_from_bytes(&metadata, glib::KeyFileFlags::NONE)
+            .ok()?;
+
+        let runtime = keyfile.string("Application", "runtime").ok()?;
+        Some(format!("runtime/{runtime}"))
+    }
+}
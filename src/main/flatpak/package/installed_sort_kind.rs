@@ -0,0 +1,38 @@
+// Souk - installed_sort_kind.rs
+// Copyright (C) 2024  Felix Häcker <haeckerfelix@gnome.org>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use glib::Enum;
+use gtk::glib;
+
+#[derive(Default, Copy, Debug, Clone, Eq, PartialEq, Enum)]
+#[repr(u32)]
+#[enum_type(name = "SkInstalledSortKind")]
+pub enum SkInstalledSortKind {
+    #[default]
+    Name,
+    Size,
+    InstallDate,
+}
+
+impl From<&str> for SkInstalledSortKind {
+    fn from(nick: &str) -> Self {
+        match nick {
+            "size" => Self::Size,
+            "install-date" => Self::InstallDate,
+            _ => Self::Name,
+        }
+    }
+}
@@ -14,12 +14,16 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
+mod installed_package;
+mod installed_sort_kind;
 #[allow(clippy::module_inception)]
 mod package;
 mod package_kind;
 mod package_model;
 mod package_subref_kind;
 
+pub use installed_package::SkInstalledPackage;
+pub use installed_sort_kind::SkInstalledSortKind;
 pub use package::{SkPackage, SkPackageExt, SkPackageImpl};
 pub use package_kind::SkPackageKind;
 pub use package_model::SkPackageModel;
@@ -14,7 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
-use std::cell::OnceCell;
+use std::cell::{OnceCell, RefCell};
 
 use flatpak::prelude::*;
 use flatpak::Ref;
@@ -23,9 +23,11 @@ use gtk::glib;
 use gtk::subclass::prelude::*;
 
 use super::{SkPackageKind, SkPackageSubrefKind};
+use crate::main::error::Error;
 use crate::main::flatpak::installation::SkRemote;
 use crate::main::SkApplication;
 use crate::shared::flatpak::info::PackageInfo;
+use crate::shared::flatpak::DiskUsage;
 
 mod imp {
     use super::*;
@@ -42,6 +44,10 @@ mod imp {
         #[property(name = "branch", get = Self::branch, type = String)]
         #[property(get, set, construct_only)]
         info: OnceCell<PackageInfo>,
+        /// Only available for installed packages after calling
+        /// `load_disk_usage()`
+        #[property(get, set, nullable)]
+        disk_usage: RefCell<Option<DiskUsage>>,
 
         flatpak_ref: OnceCell<Ref>,
     }
@@ -130,6 +136,16 @@ impl SkPackage {
     pub fn new(info: &PackageInfo) -> Self {
         glib::Object::builder().property("info", info).build()
     }
+
+    /// Calculates the disk usage of the installed package, and stores it in
+    /// the `disk-usage` property
+    pub async fn load_disk_usage(&self) -> Result<DiskUsage, Error> {
+        let worker = SkApplication::default().worker();
+        let disk_usage = worker.disk_usage(self).await?;
+
+        self.set_disk_usage(Some(disk_usage.clone()));
+        Ok(disk_usage)
+    }
 }
 
 pub trait SkPackageExt: 'static {
@@ -146,6 +162,8 @@ pub trait SkPackageExt: 'static {
     fn branch(&self) -> String;
 
     fn remote(&self) -> SkRemote;
+
+    fn disk_usage(&self) -> Option<DiskUsage>;
 }
 
 impl<O: IsA<SkPackage>> SkPackageExt for O {
@@ -176,6 +194,10 @@ impl<O: IsA<SkPackage>> SkPackageExt for O {
     fn info(&self) -> PackageInfo {
         self.upcast_ref().info()
     }
+
+    fn disk_usage(&self) -> Option<DiskUsage> {
+        self.upcast_ref().disk_usage()
+    }
 }
 
 pub trait SkPackageImpl: ObjectImpl {}
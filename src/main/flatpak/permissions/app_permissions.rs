@@ -15,27 +15,17 @@
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
 use std::cell::OnceCell;
-use std::sync::LazyLock;
 
 use gio::ListStore;
 use glib::{KeyFile, ParamSpec, Properties};
 use gtk::prelude::*;
 use gtk::subclass::prelude::*;
-use gtk::{gio, glib};
+use gtk::{gio, glib, StringList};
 
 use super::types::*;
-
-static SERVICE_WHITELIST: LazyLock<Vec<&'static str>> = LazyLock::new(|| {
-    vec![
-        "org.kde.StatusNotifier",
-        "org.mpris.MediaPlayer",
-        "org.freedesktop.Notifications",
-        "com.canonical.AppMenu.Registrar",
-        "com.canonical.indicator.application",
-        "com.canonical.Unity.LauncherEntry",
-        "org.a11y.Bus",
-    ]
-});
+use super::{PermissionDetails, SkPermissionSummary};
+use crate::main::context::SkContextDetail;
+use crate::shared::flatpak::permissions::PermissionSet;
 
 mod imp {
     use super::*;
@@ -53,6 +43,12 @@ mod imp {
         sockets: OnceCell<SkSocketPermission>,
         #[property(get, set, construct_only)]
         subsystems: OnceCell<SkSubsystemPermission>,
+        #[property(get, set, construct_only)]
+        features: OnceCell<SkFeaturePermission>,
+        #[property(get, set, construct_only)]
+        environment: OnceCell<ListStore>,
+        #[property(get, set, construct_only)]
+        persistent: OnceCell<StringList>,
     }
 
     #[glib::object_subclass]
@@ -74,16 +70,6 @@ mod imp {
             Self::derived_set_property(self, id, value, pspec)
         }
     }
-
-    impl SkAppPermissions {
-        pub fn is_whitelisted(list: Vec<&str>, value: &str) -> bool {
-            let res = list.iter().any(|i| value.starts_with(i));
-            if res {
-                debug!("Ignoring whitelisted permission entry: {}", value);
-            }
-            res
-        }
-    }
 }
 
 glib::wrapper! {
@@ -91,12 +77,16 @@ glib::wrapper! {
 }
 
 impl SkAppPermissions {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         filesystems: &ListStore,
         services: &ListStore,
         devices: &SkDevicePermission,
         sockets: &SkSocketPermission,
         subsystems: &SkSubsystemPermission,
+        features: &SkFeaturePermission,
+        environment: &ListStore,
+        persistent: &StringList,
     ) -> Self {
         glib::Object::builder()
             .property("filesystems", filesystems)
@@ -104,69 +94,119 @@ impl SkAppPermissions {
             .property("devices", devices)
             .property("sockets", sockets)
             .property("subsystems", subsystems)
+            .property("features", features)
+            .property("environment", environment)
+            .property("persistent", persistent)
             .build()
     }
 
     pub fn from_metadata(keyfile: &KeyFile) -> Self {
+        let permissions = PermissionSet::from_metadata(keyfile);
+
         let filesystems = ListStore::new::<SkFilesystemPermission>();
-        if let Ok(filesystem_list) = keyfile.string_list("Context", "filesystems") {
-            for filesystem in filesystem_list {
-                let value = SkFilesystemPermission::from_flatpak(filesystem.as_str());
-                filesystems.append(&value);
-            }
+        for filesystem in &permissions.filesystems {
+            filesystems.append(&SkFilesystemPermission::new(
+                &filesystem.kind,
+                &filesystem.path,
+            ));
         }
 
         let services = ListStore::new::<SkServicePermission>();
-        if let Ok(session_list) = keyfile.keys("Session Bus Policy") {
-            for service in session_list {
-                if imp::SkAppPermissions::is_whitelisted(
-                    SERVICE_WHITELIST.to_vec(),
-                    service.as_str(),
-                ) {
-                    continue;
-                }
-                let value = SkServicePermission::new(service.as_str(), false);
-                services.append(&value);
-            }
+        for service in &permissions.services {
+            services.append(&SkServicePermission::new(
+                &service.name,
+                service.is_system,
+                service.policy,
+            ));
         }
-        if let Ok(system_list) = keyfile.keys("System Bus Policy") {
-            for service in system_list {
-                if imp::SkAppPermissions::is_whitelisted(
-                    SERVICE_WHITELIST.to_vec(),
-                    service.as_str(),
-                ) {
-                    continue;
-                }
-                let value = SkServicePermission::new(service.as_str(), true);
-                services.append(&value);
-            }
+
+        let environment = ListStore::new::<SkEnvironmentPermission>();
+        for (name, value) in &permissions.environment {
+            environment.append(&SkEnvironmentPermission::new(name, value));
         }
 
-        let mut devices = SkDevicePermission::NONE;
-        if let Ok(device_list) = keyfile.string_list("Context", "devices") {
-            for device in device_list {
-                devices |= device.as_str().into();
-                devices.remove(SkDevicePermission::NONE);
-            }
+        let persistent = StringList::new(&[]);
+        for path in &permissions.persistent {
+            persistent.append(path);
         }
 
-        let mut sockets = SkSocketPermission::NONE;
-        if let Ok(socket_list) = keyfile.string_list("Context", "sockets") {
-            for socket in socket_list {
-                sockets |= socket.as_str().into();
-                sockets.remove(SkSocketPermission::NONE);
-            }
+        Self::new(
+            &filesystems,
+            &services,
+            &permissions.devices,
+            &permissions.sockets,
+            &permissions.subsystems,
+            &permissions.features,
+            &environment,
+            &persistent,
+        )
+    }
+
+    /// Combined summary of all permissions
+    pub fn summary(&self) -> SkPermissionSummary {
+        let mut summary = self.subsystems().summary()
+            | self.devices().summary()
+            | self.sockets().summary()
+            | self.features().summary();
+
+        for value in self.filesystems().snapshot() {
+            summary |= value
+                .downcast_ref::<SkFilesystemPermission>()
+                .unwrap()
+                .summary();
+        }
+        for value in self.services().snapshot() {
+            summary |= value
+                .downcast_ref::<SkServicePermission>()
+                .unwrap()
+                .summary();
+        }
+        for value in self.environment().snapshot() {
+            summary |= value
+                .downcast_ref::<SkEnvironmentPermission>()
+                .unwrap()
+                .summary();
         }
 
-        let mut subsystems = SkSubsystemPermission::NONE;
-        if let Ok(subsystem_list) = keyfile.string_list("Context", "shared") {
-            for subsystem in subsystem_list {
-                subsystems |= subsystem.as_str().into();
-                subsystems.remove(SkSubsystemPermission::NONE);
-            }
+        summary
+    }
+
+    /// Context details of all permissions
+    pub fn context_details(&self) -> Vec<SkContextDetail> {
+        let mut details = self.subsystems().context_details();
+        details.append(&mut self.devices().context_details());
+        details.append(&mut self.sockets().context_details());
+        details.append(&mut self.features().context_details());
+
+        for value in self.filesystems().snapshot() {
+            let value = value.downcast_ref::<SkFilesystemPermission>().unwrap();
+            details.append(&mut value.context_details());
+        }
+        for value in self.services().snapshot() {
+            let value = value.downcast_ref::<SkServicePermission>().unwrap();
+            details.append(&mut value.context_details());
+        }
+        for value in self.environment().snapshot() {
+            let value = value.downcast_ref::<SkEnvironmentPermission>().unwrap();
+            details.append(&mut value.context_details());
         }
 
-        Self::new(&filesystems, &services, &devices, &sockets, &subsystems)
+        details
+    }
+
+    /// Whether a `[Context]` entry, e.g. `sockets=x11`, is granted
+    pub fn contains_entry(&self, key: &str, value: &str) -> bool {
+        match key {
+            "shared" => self.subsystems().contains(value.into()),
+            "sockets" => self.sockets().contains(value.into()),
+            "devices" => self.devices().contains(value.into()),
+            "features" => self.features().contains(value.into()),
+            "filesystems" => self.filesystems().snapshot().iter().any(|f| {
+                let f: &SkFilesystemPermission = f.downcast_ref().unwrap();
+                f.path() == value
+            }),
+            _ => false,
+        }
     }
 
     /// Compares with a different `SkAppPermissions` object, and returns the
@@ -175,6 +215,7 @@ impl SkAppPermissions {
         let devices = other.devices().difference(self.devices());
         let sockets = other.sockets().difference(self.sockets());
         let subsystems = other.subsystems().difference(self.subsystems());
+        let features = other.features().difference(self.features());
 
         let filesystems = ListStore::new::<SkFilesystemPermission>();
         for filesystem in other.filesystems().snapshot() {
@@ -190,14 +231,51 @@ impl SkAppPermissions {
         let services = ListStore::new::<SkServicePermission>();
         for service in other.services().snapshot() {
             let service: SkServicePermission = service.downcast().unwrap();
+            if service.is_ignored() {
+                continue;
+            }
+
             if !self.services().snapshot().iter().any(|a| {
                 let a: &SkServicePermission = a.downcast_ref().unwrap();
-                a.name() == service.name() && a.is_system() == service.is_system()
+                a.name() == service.name()
+                    && a.is_system() == service.is_system()
+                    && a.policy() >= service.policy()
             }) {
                 services.append(&service);
             }
         }
 
-        Self::new(&filesystems, &services, &devices, &sockets, &subsystems)
+        let environment = ListStore::new::<SkEnvironmentPermission>();
+        for variable in other.environment().snapshot() {
+            let variable: SkEnvironmentPermission = variable.downcast().unwrap();
+            if !self.environment().snapshot().iter().any(|a| {
+                let a: &SkEnvironmentPermission = a.downcast_ref().unwrap();
+                a.name() == variable.name() && a.value() == variable.value()
+            }) {
+                environment.append(&variable);
+            }
+        }
+
+        let persistent = StringList::new(&[]);
+        for path in other.persistent().snapshot() {
+            let path: gtk::StringObject = path.downcast().unwrap();
+            if !self.persistent().snapshot().iter().any(|a| {
+                let a: &gtk::StringObject = a.downcast_ref().unwrap();
+                a.string() == path.string()
+            }) {
+                persistent.append(&path.string());
+            }
+        }
+
+        Self::new(
+            &filesystems,
+            &services,
+            &devices,
+            &sockets,
+            &subsystems,
+            &features,
+            &environment,
+            &persistent,
+        )
     }
 }
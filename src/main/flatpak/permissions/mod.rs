@@ -17,9 +17,18 @@
 pub mod types;
 
 mod app_permissions;
+mod permission_change;
 mod permission_details;
+mod permission_overrides;
+mod permission_risk;
 mod permission_summary;
+mod policy_description;
+mod portal_permission;
 
+pub use crate::shared::flatpak::permissions::SkPermissionSummary;
 pub use app_permissions::SkAppPermissions;
 pub use permission_details::PermissionDetails;
-pub use permission_summary::SkPermissionSummary;
+pub use permission_overrides::PermissionOverrides;
+pub use permission_risk::PermissionRisk;
+pub use policy_description::describe_policy_violation;
+pub use portal_permission::PortalPermission;
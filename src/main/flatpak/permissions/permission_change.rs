@@ -0,0 +1,155 @@
+// Souk - permission_change.rs
+// Copyright (C) 2024  Felix Häcker <haeckerfelix@gnome.org>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::types::*;
+use super::{PermissionDetails, SkPermissionSummary};
+use crate::main::context::{SkContextDetail, SkContextDetailKind, SkContextDetailLevel};
+use crate::main::i18n::{i18n, i18n_f};
+use crate::shared::flatpak::{PermissionChange, PermissionChangeKind};
+
+/// Returns the value which grants additional access, if the change extends
+/// the permissions
+fn granted_value(change: &PermissionChange) -> Option<String> {
+    if !change.is_extending() {
+        return None;
+    }
+
+    match change.kind {
+        // A negated entry got removed, e.g. `!x11`
+        PermissionChangeKind::Removed => change
+            .old_value
+            .as_ref()
+            .map(|v| v.trim_start_matches('!').to_string()),
+        _ => change.new_value.clone(),
+    }
+}
+
+/// Returns the semantic permission details of a granted value, using the
+/// same permission types as `SkAppPermissions`
+fn granted_details(
+    change: &PermissionChange,
+    value: &str,
+) -> (SkPermissionSummary, Vec<SkContextDetail>) {
+    fn flags<T: PermissionDetails>(value: T) -> (SkPermissionSummary, Vec<SkContextDetail>) {
+        // Flag details also describe missing permissions, e.g. "No Network
+        // Access", which doesn't make sense for a single entry
+        let details = value
+            .context_details()
+            .into_iter()
+            .filter(|d| d.level() != SkContextDetailLevel::Good)
+            .collect();
+        (value.summary(), details)
+    }
+
+    match (change.group.as_str(), change.key.as_str()) {
+        ("Context", "filesystems") => {
+            let value = SkFilesystemPermission::from_flatpak(value);
+            (value.summary(), value.context_details())
+        }
+        ("Context", "sockets") => flags(SkSocketPermission::from(value)),
+        ("Context", "devices") => flags(SkDevicePermission::from(value)),
+        ("Context", "shared") => flags(SkSubsystemPermission::from(value)),
+        ("Context", "features") => flags(SkFeaturePermission::from(value)),
+        ("Session Bus Policy", name) | ("System Bus Policy", name) => {
+            let is_system = change.group == "System Bus Policy";
+            let value = SkServicePermission::new(name, is_system, value.into());
+            (value.summary(), value.context_details())
+        }
+        ("Environment", name) => {
+            let value = SkEnvironmentPermission::new(name, value);
+            (value.summary(), value.context_details())
+        }
+        _ => (SkPermissionSummary::empty(), Vec::new()),
+    }
+}
+
+/// Generic title, used when there's no more specific detail available
+fn title(change: &PermissionChange, value: &str) -> String {
+    let value = value.trim_start_matches('!');
+
+    match (change.group.as_str(), change.key.as_str()) {
+        ("Context", "filesystems") => i18n_f("Filesystem Access to “{}”", &[value]),
+        ("Context", "sockets") => i18n_f("Access to “{}” Socket", &[value]),
+        ("Context", "devices") => i18n_f("Access to “{}” Devices", &[value]),
+        ("Context", "shared") => i18n_f("Access to “{}” Subsystem", &[value]),
+        ("Context", "features") => i18n_f("Uses “{}” Feature", &[value]),
+        ("Context", "persistent") => i18n_f("Persistent Folder “{}”", &[value]),
+        ("Session Bus Policy", name) => i18n_f("Access to “{}” Service", &[name]),
+        ("System Bus Policy", name) => i18n_f("Access to System Service “{}”", &[name]),
+        ("Environment", name) => i18n_f("Sets “{}” Variable", &[name]),
+        (_, key) => i18n_f("“{}” Permission", &[key]),
+    }
+}
+
+impl PermissionDetails for PermissionChange {
+    fn summary(&self) -> SkPermissionSummary {
+        match granted_value(self) {
+            Some(value) => granted_details(self, &value).0,
+            None => SkPermissionSummary::empty(),
+        }
+    }
+
+    fn context_details(&self) -> Vec<SkContextDetail> {
+        let kind = SkContextDetailKind::Icon;
+        let old_value = self.old_value.clone().unwrap_or_default();
+        let new_value = self.new_value.clone().unwrap_or_default();
+
+        if let Some(value) = granted_value(self) {
+            let (_, details) = granted_details(self, &value);
+            if !details.is_empty() {
+                return details;
+            }
+
+            let description = match self.kind {
+                PermissionChangeKind::Upgraded | PermissionChangeKind::Changed => {
+                    i18n_f("Changed from “{}” to “{}”", &[&old_value, &new_value])
+                }
+                _ => i18n("Newly requested permission"),
+            };
+
+            return vec![SkContextDetail::new(
+                kind,
+                "list-add-symbolic",
+                SkContextDetailLevel::Neutral,
+                &title(self, &value),
+                &description,
+            )];
+        }
+
+        let (title, description) = match self.kind {
+            PermissionChangeKind::Downgraded => (
+                title(self, &old_value),
+                i18n_f("Reduced from “{}” to “{}”", &[&old_value, &new_value]),
+            ),
+            PermissionChangeKind::Added => (
+                title(self, &new_value),
+                i18n("Explicitly revoked permission"),
+            ),
+            _ => (
+                title(self, &old_value),
+                i18n("No longer requested permission"),
+            ),
+        };
+
+        vec![SkContextDetail::new(
+            kind,
+            "list-remove-symbolic",
+            SkContextDetailLevel::Good,
+            &title,
+            &description,
+        )]
+    }
+}
@@ -0,0 +1,127 @@
+// Souk - permission_overrides.rs
+// Copyright (C) 2024  Felix Häcker <haeckerfelix@gnome.org>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use glib::{KeyFile, KeyFileFlags};
+use gtk::glib;
+
+/// `[Context]` keys which contain a list of permissions. Overrides get
+/// appended to these lists, since later entries take precedence.
+const CONTEXT_LIST_KEYS: [&str; 6] = [
+    "shared",
+    "sockets",
+    "devices",
+    "features",
+    "filesystems",
+    "persistent",
+];
+
+/// A Flatpak overrides keyfile, e.g.
+/// `~/.local/share/flatpak/overrides/<app-id>`. It has the same format as
+/// the `metadata` file of an app.
+#[derive(Debug, Clone)]
+pub struct PermissionOverrides {
+    keyfile: KeyFile,
+}
+
+impl PermissionOverrides {
+    pub fn from_data(data: &str) -> Self {
+        let keyfile = KeyFile::new();
+        if let Err(err) = keyfile.load_from_data(data, KeyFileFlags::KEEP_COMMENTS) {
+            warn!("Unable to parse permission overrides: {}", err.message());
+        }
+
+        Self { keyfile }
+    }
+
+    pub fn to_data(&self) -> String {
+        self.keyfile.to_data().to_string()
+    }
+
+    /// Applies the overrides on top of the metadata, in the given order
+    pub fn apply(metadata: &KeyFile, overrides: &[Self]) -> KeyFile {
+        let keyfile = KeyFile::new();
+        keyfile
+            .load_from_data(&metadata.to_data(), KeyFileFlags::NONE)
+            .unwrap();
+
+        for layer in overrides {
+            let layer = &layer.keyfile;
+            for group in layer.groups() {
+                let group = group.as_str();
+                let Ok(keys) = layer.keys(group) else {
+                    continue;
+                };
+
+                for key in keys {
+                    let key = key.as_str();
+                    if group == "Context" && CONTEXT_LIST_KEYS.contains(&key) {
+                        let mut list = Self::string_list(&keyfile, group, key);
+                        list.extend(Self::string_list(layer, group, key));
+
+                        let list: Vec<&str> = list.iter().map(String::as_str).collect();
+                        keyfile.set_string_list(group, key, &list);
+                    } else if let Ok(value) = layer.string(group, key) {
+                        keyfile.set_string(group, key, &value);
+                    }
+                }
+            }
+        }
+
+        keyfile
+    }
+
+    /// Grants (`Some(true)`) or revokes (`Some(false)`) a `[Context]` list
+    /// entry, e.g. `sockets=x11`. `None` removes the override again, so
+    /// that the value of the metadata applies.
+    pub fn set_context_entry(&self, key: &str, value: &str, allow: Option<bool>) {
+        let identity = Self::identity(value);
+        let mut list: Vec<String> = Self::string_list(&self.keyfile, "Context", key)
+            .into_iter()
+            .filter(|entry| Self::identity(entry) != identity)
+            .collect();
+
+        match allow {
+            Some(true) => list.push(value.to_string()),
+            Some(false) => list.push(format!("!{identity}")),
+            None => (),
+        }
+
+        if list.is_empty() {
+            let _ = self.keyfile.remove_key("Context", key);
+        } else {
+            let list: Vec<&str> = list.iter().map(String::as_str).collect();
+            self.keyfile.set_string_list("Context", key, &list);
+        }
+    }
+
+    fn string_list(keyfile: &KeyFile, group: &str, key: &str) -> Vec<String> {
+        keyfile
+            .string_list(group, key)
+            .map(|list| list.iter().map(|e| e.to_string()).collect())
+            .unwrap_or_default()
+    }
+
+    /// The entry without negation and access suffix, e.g. `home` for
+    /// `!home` or `home:ro`
+    fn identity(entry: &str) -> &str {
+        let entry = entry.trim_start_matches('!');
+        entry
+            .strip_suffix(":ro")
+            .or_else(|| entry.strip_suffix(":rw"))
+            .or_else(|| entry.strip_suffix(":create"))
+            .unwrap_or(entry)
+    }
+}
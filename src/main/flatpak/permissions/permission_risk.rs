@@ -0,0 +1,100 @@
+// Souk - permission_risk.rs
+// Copyright (C) 2024  Felix Häcker <haeckerfelix@gnome.org>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::cmp::Reverse;
+
+use super::{SkAppPermissions, SkPermissionSummary};
+use crate::main::context::{SkContextDetail, SkContextDetailLevel};
+
+/// Additional weight of the summary flags, since a single permission (e.g.
+/// `--talk-name=org.freedesktop.Flatpak`) can outweigh many minor ones
+const SUMMARY_WEIGHTS: [(SkPermissionSummary, u32); 9] = [
+    (SkPermissionSummary::SANDBOX_ESCAPE, 50),
+    (SkPermissionSummary::FULL_SYSTEM_BUS_ACCESS, 25),
+    (SkPermissionSummary::FULL_SESSION_BUS_ACCESS, 20),
+    (SkPermissionSummary::READWRITE_DATA, 15),
+    (SkPermissionSummary::FULL_DEVICE_ACCESS, 10),
+    (SkPermissionSummary::DEBUGGING_ACCESS, 10),
+    (SkPermissionSummary::UNKNOWN, 10),
+    (SkPermissionSummary::READ_DATA, 5),
+    (SkPermissionSummary::NETWORK_ACCESS, 2),
+];
+
+/// Numeric risk of the permissions of an app. The same permissions always
+/// result in the same score, which makes it possible to compare apps.
+#[derive(Debug, Clone)]
+pub struct PermissionRisk {
+    pub score: u32,
+    /// Permissions which contributed to the score with their weight, highest
+    /// first
+    pub contributions: Vec<(u32, SkContextDetail)>,
+}
+
+impl PermissionRisk {
+    pub fn new(permissions: &SkAppPermissions) -> Self {
+        let mut contributions: Vec<(u32, SkContextDetail)> = permissions
+            .context_details()
+            .into_iter()
+            .map(|detail| (Self::level_weight(detail.level()), detail))
+            .filter(|(weight, _)| *weight != 0)
+            .collect();
+        contributions.sort_by_key(|(weight, detail)| (Reverse(*weight), detail.title()));
+
+        let summary = permissions.summary();
+        let summary_score: u32 = SUMMARY_WEIGHTS
+            .iter()
+            .filter(|(flag, _)| summary.contains(*flag))
+            .map(|(_, weight)| weight)
+            .sum();
+
+        let score = summary_score + contributions.iter().map(|(w, _)| w).sum::<u32>();
+
+        Self {
+            score,
+            contributions,
+        }
+    }
+
+    /// The permissions with the highest weight
+    pub fn top_contributions(&self, count: usize) -> Vec<SkContextDetail> {
+        self.contributions
+            .iter()
+            .take(count)
+            .map(|(_, detail)| detail.clone())
+            .collect()
+    }
+
+    /// Overall level of the score, e.g. to color it
+    pub fn level(&self) -> SkContextDetailLevel {
+        match self.score {
+            0 => SkContextDetailLevel::Good,
+            1..=9 => SkContextDetailLevel::Minor,
+            10..=29 => SkContextDetailLevel::Moderate,
+            30..=59 => SkContextDetailLevel::Warning,
+            _ => SkContextDetailLevel::Bad,
+        }
+    }
+
+    fn level_weight(level: SkContextDetailLevel) -> u32 {
+        match level {
+            SkContextDetailLevel::Neutral | SkContextDetailLevel::Good => 0,
+            SkContextDetailLevel::Minor => 1,
+            SkContextDetailLevel::Moderate => 3,
+            SkContextDetailLevel::Warning => 6,
+            SkContextDetailLevel::Bad => 10,
+        }
+    }
+}
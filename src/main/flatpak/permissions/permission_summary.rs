@@ -16,31 +16,9 @@
 
 use std::fmt::Write;
 
-use gtk::glib;
-
 use crate::main::context::{SkContextDetail, SkContextDetailKind, SkContextDetailLevel};
 use crate::main::i18n::i18n;
-
-#[glib::flags(name = "SkPermissionSummary")]
-pub enum SkPermissionSummary {
-    #[flags_value(name = "full-device-access")]
-    FULL_DEVICE_ACCESS = 1 << 1,
-    #[flags_value(name = "full-session-bus-access")]
-    FULL_SESSION_BUS_ACCESS = 1 << 2,
-    #[flags_value(name = "full-system-bus-access")]
-    FULL_SYSTEM_BUS_ACCESS = 1 << 3,
-    #[flags_value(name = "read-data")]
-    READ_DATA = 1 << 4,
-    #[flags_value(name = "readwrite-data")]
-    READWRITE_DATA = 1 << 5,
-    #[flags_value(name = "network-access")]
-    NETWORK_ACCESS = 1 << 6,
-    #[flags_value(name = "sandbox-escape")]
-    SANDBOX_ESCAPE = 1 << 7,
-
-    #[flags_value(name = "unknown")]
-    UNKNOWN = 1 << 8,
-}
+use crate::shared::flatpak::permissions::SkPermissionSummary;
 
 impl SkPermissionSummary {
     pub fn as_context_detail(&self) -> SkContextDetail {
@@ -76,6 +54,12 @@ impl SkPermissionSummary {
             ));
         }
 
+        if self.contains(Self::DEBUGGING_ACCESS) {
+            descriptions.push(i18n(
+                "Can inspect and modify other processes running in the same sandbox.",
+            ));
+        }
+
         if self.contains(Self::UNKNOWN) {
             descriptions.push(i18n("Has an unknown permission."));
         }
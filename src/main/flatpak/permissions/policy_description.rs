@@ -0,0 +1,44 @@
+// Souk - policy_description.rs
+// Copyright (C) 2024  Felix Häcker <haeckerfelix@gnome.org>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use gtk::glib;
+
+use crate::main::i18n::{i18n, i18n_f};
+use crate::shared::flatpak::PolicyViolation;
+
+/// User facing description of a matched
+/// [PermissionPolicy](crate::shared::flatpak::PermissionPolicy) rule
+pub fn describe_policy_violation(violation: &PolicyViolation) -> String {
+    let rule = glib::markup_escape_text(&violation.rule);
+    let message = match violation.rule.as_str() {
+        "allowed-remotes" => Some(i18n("Packages from this remote are not allowed")),
+        "allow-proprietary" => Some(i18n("Proprietary software is not allowed")),
+        name if name.starts_with("blocked-licenses=") => {
+            Some(i18n("Software with this license is not allowed"))
+        }
+        _ => violation.message.clone(),
+    };
+    let reason = match message {
+        Some(message) => format!("{} (<tt>{rule}</tt>)", glib::markup_escape_text(&message)),
+        None => format!("<tt>{rule}</tt>"),
+    };
+
+    if violation.blocking {
+        i18n_f("Not permitted by your administrator: {}", &[&reason])
+    } else {
+        i18n_f("Discouraged by your administrator: {}", &[&reason])
+    }
+}
@@ -0,0 +1,364 @@
+// Souk - portal_permission.rs
+// Copyright (C) 2024  Felix Häcker <haeckerfelix@gnome.org>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::sync::OnceLock;
+
+use super::{PermissionDetails, SkPermissionSummary};
+use crate::main::context::{SkContextDetail, SkContextDetailKind, SkContextDetailLevel};
+use crate::main::dbus_proxy::PermissionStoreProxy;
+use crate::main::error::Error;
+use crate::main::i18n::{i18n, i18n_f};
+
+/// Permission store tables of the portals, which contain per-app entries
+const TABLES: &[&str] = &[
+    "devices",
+    "location",
+    "background",
+    "notifications",
+    "screenshot",
+    "wallpaper",
+];
+
+/// Proxy of the permission store, shared between all load / revoke calls
+static PERMISSION_STORE: OnceLock<PermissionStoreProxy<'static>> = OnceLock::new();
+
+async fn permission_store() -> Result<PermissionStoreProxy<'static>, Error> {
+    if let Some(store) = PERMISSION_STORE.get() {
+        return Ok(store.clone());
+    }
+
+    let connection = zbus::Connection::session().await?;
+    let store = PermissionStoreProxy::new(&connection).await?;
+    Ok(PERMISSION_STORE.get_or_init(|| store).clone())
+}
+
+/// A permission which got granted to an app through a portal, and is saved in
+/// the permission store
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PortalPermission {
+    pub table: String,
+    pub id: String,
+    pub value: Vec<String>,
+}
+
+impl PortalPermission {
+    /// Loads all granted portal permissions of an app
+    pub async fn load(app_id: &str) -> Result<Vec<Self>, Error> {
+        Self::load_from(&permission_store().await?, app_id).await
+    }
+
+    async fn load_from(store: &PermissionStoreProxy<'_>, app_id: &str) -> Result<Vec<Self>, Error> {
+        let mut permissions = Vec::new();
+        for table in TABLES {
+            // Tables only exist once the corresponding portal got used
+            let ids = match store.list(table).await {
+                Ok(ids) => ids,
+                Err(err) => {
+                    debug!("Unable to list permission store table {table}: {err}");
+                    continue;
+                }
+            };
+
+            for id in ids {
+                let (apps, _) = match store.lookup(table, &id).await {
+                    Ok(entry) => entry,
+                    Err(err) => {
+                        debug!("Unable to lookup permission store entry {table}/{id}: {err}");
+                        continue;
+                    }
+                };
+
+                if let Some(value) = apps.get(app_id) {
+                    let permission = Self {
+                        table: table.to_string(),
+                        id,
+                        value: value.clone(),
+                    };
+
+                    if permission.is_granted() {
+                        permissions.push(permission);
+                    }
+                }
+            }
+        }
+
+        Ok(permissions)
+    }
+
+    /// Removes the entry from the permission store, the app has to ask again
+    /// the next time it wants to use the portal
+    pub async fn revoke(&self, app_id: &str) -> Result<(), Error> {
+        self.revoke_from(&permission_store().await?, app_id).await
+    }
+
+    async fn revoke_from(
+        &self,
+        store: &PermissionStoreProxy<'_>,
+        app_id: &str,
+    ) -> Result<(), Error> {
+        store
+            .delete_permission(&self.table, &self.id, app_id)
+            .await?;
+        Ok(())
+    }
+
+    fn is_granted(&self) -> bool {
+        match self.value.first().map(String::as_str) {
+            // The location portal saves the accuracy instead of yes / no, "ask"
+            // means that the app has to ask again every time
+            Some("NONE") | Some("no") | Some("ask") | None => false,
+            Some(_) => true,
+        }
+    }
+}
+
+impl PermissionDetails for PortalPermission {
+    fn summary(&self) -> SkPermissionSummary {
+        SkPermissionSummary::empty()
+    }
+
+    fn context_details(&self) -> Vec<SkContextDetail> {
+        let kind = SkContextDetailKind::Icon;
+
+        let (icon_name, level, title, description) = match (self.table.as_str(), self.id.as_str()) {
+            ("devices", "camera") => (
+                "camera-web-symbolic",
+                SkContextDetailLevel::Warning,
+                i18n("Camera"),
+                i18n("Can use the camera without asking"),
+            ),
+            ("devices", "microphone") => (
+                "audio-input-microphone-symbolic",
+                SkContextDetailLevel::Warning,
+                i18n("Microphone"),
+                i18n("Can record audio without asking"),
+            ),
+            ("devices", "speakers") => (
+                "audio-speakers-symbolic",
+                SkContextDetailLevel::Minor,
+                i18n("Speakers"),
+                i18n("Can play audio without asking"),
+            ),
+            ("location", _) => (
+                "find-location-symbolic",
+                SkContextDetailLevel::Warning,
+                i18n("Location"),
+                i18n_f(
+                    "Can determine the current location with “{}” accuracy",
+                    &[&self.value[0].to_lowercase()],
+                ),
+            ),
+            ("background", _) => (
+                "preferences-system-time-symbolic",
+                SkContextDetailLevel::Minor,
+                i18n("Run in Background"),
+                i18n("Can keep running after all windows got closed"),
+            ),
+            ("notifications", _) => (
+                "preferences-system-notifications-symbolic",
+                SkContextDetailLevel::Minor,
+                i18n("Notifications"),
+                i18n("Can send notifications"),
+            ),
+            ("screenshot", _) => (
+                "camera-photo-symbolic",
+                SkContextDetailLevel::Warning,
+                i18n("Screenshots"),
+                i18n("Can take screenshots without asking"),
+            ),
+            ("wallpaper", _) => (
+                "preferences-desktop-wallpaper-symbolic",
+                SkContextDetailLevel::Minor,
+                i18n("Background Image"),
+                i18n("Can change the background image without asking"),
+            ),
+            (table, id) => (
+                "dialog-question-symbolic",
+                SkContextDetailLevel::Moderate,
+                i18n_f("Portal Permission “{}”", &[&format!("{table}/{id}")]),
+                i18n_f("Granted value: {}", &[&self.value.join(", ")]),
+            ),
+        };
+
+        vec![SkContextDetail::new(
+            kind,
+            icon_name,
+            level,
+            &title,
+            &description,
+        )]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::os::unix::net::UnixStream;
+
+    use zbus::zvariant::{OwnedValue, Value};
+
+    use super::*;
+
+    /// Serves a fixed set of `table -> id -> app -> value` entries
+    struct MockPermissionStore {
+        tables: HashMap<String, HashMap<String, HashMap<String, Vec<String>>>>,
+    }
+
+    #[zbus::interface(name = "org.freedesktop.impl.portal.PermissionStore")]
+    impl MockPermissionStore {
+        fn list(&self, table: &str) -> zbus::fdo::Result<Vec<String>> {
+            self.tables
+                .get(table)
+                .map(|ids| ids.keys().cloned().collect())
+                .ok_or_else(|| zbus::fdo::Error::Failed(format!("No entry for {table}")))
+        }
+
+        fn lookup(
+            &self,
+            table: &str,
+            id: &str,
+        ) -> zbus::fdo::Result<(HashMap<String, Vec<String>>, OwnedValue)> {
+            let apps = self
+                .tables
+                .get(table)
+                .and_then(|ids| ids.get(id))
+                .cloned()
+                .ok_or_else(|| zbus::fdo::Error::Failed(format!("No entry for {id}")))?;
+            Ok((apps, Value::from(0u32).try_to_owned().unwrap()))
+        }
+
+        fn delete_permission(&mut self, table: &str, id: &str, app: &str) -> zbus::fdo::Result<()> {
+            self.tables
+                .get_mut(table)
+                .and_then(|ids| ids.get_mut(id))
+                .and_then(|apps| apps.remove(app))
+                .map(|_| ())
+                .ok_or_else(|| zbus::fdo::Error::Failed(format!("No entry for {app}")))
+        }
+    }
+
+    fn entry(values: &[(&str, &[&str])]) -> HashMap<String, Vec<String>> {
+        values
+            .iter()
+            .map(|(app, value)| {
+                let value = value.iter().map(|v| v.to_string()).collect();
+                (app.to_string(), value)
+            })
+            .collect()
+    }
+
+    /// Connects a proxy to a mock permission store over a peer-to-peer
+    /// connection, so no session bus is needed
+    async fn mock_store() -> (PermissionStoreProxy<'static>, zbus::Connection) {
+        let mut devices = HashMap::new();
+        devices.insert(
+            "camera".to_string(),
+            entry(&[
+                ("org.example.App", &["yes"]),
+                ("org.example.Other", &["yes"]),
+            ]),
+        );
+        devices.insert(
+            "microphone".to_string(),
+            entry(&[("org.example.App", &["ask"])]),
+        );
+        devices.insert(
+            "speakers".to_string(),
+            entry(&[("org.example.App", &["no"])]),
+        );
+
+        let mut location = HashMap::new();
+        location.insert(
+            "location".to_string(),
+            entry(&[("org.example.App", &["EXACT", "0"])]),
+        );
+
+        let mut tables = HashMap::new();
+        tables.insert("devices".to_string(), devices);
+        tables.insert("location".to_string(), location);
+
+        let (server_stream, client_stream) = UnixStream::pair().unwrap();
+        let server = async_std::task::spawn(async move {
+            zbus::connection::Builder::unix_stream(server_stream)
+                .server(zbus::Guid::generate())
+                .unwrap()
+                .p2p()
+                .serve_at(
+                    "/org/freedesktop/impl/portal/PermissionStore",
+                    MockPermissionStore { tables },
+                )
+                .unwrap()
+                .build()
+                .await
+                .unwrap()
+        });
+
+        let client = zbus::connection::Builder::unix_stream(client_stream)
+            .p2p()
+            .build()
+            .await
+            .unwrap();
+        let server = server.await;
+
+        let store = PermissionStoreProxy::new(&client).await.unwrap();
+        (store, server)
+    }
+
+    #[test]
+    fn load_granted_permissions() {
+        async_std::task::block_on(async {
+            let (store, _server) = mock_store().await;
+
+            let mut permissions = PortalPermission::load_from(&store, "org.example.App")
+                .await
+                .unwrap();
+            permissions.sort_by(|a, b| a.id.cmp(&b.id));
+
+            // "ask" and "no" don't grant anything, missing tables get skipped
+            let ids: Vec<_> = permissions
+                .iter()
+                .map(|p| (p.table.as_str(), p.id.as_str()))
+                .collect();
+            assert_eq!(ids, [("devices", "camera"), ("location", "location")]);
+            assert_eq!(permissions[1].value, ["EXACT", "0"]);
+        });
+    }
+
+    #[test]
+    fn revoke_permission() {
+        async_std::task::block_on(async {
+            let (store, _server) = mock_store().await;
+
+            let permissions = PortalPermission::load_from(&store, "org.example.App")
+                .await
+                .unwrap();
+            let camera = permissions.iter().find(|p| p.id == "camera").unwrap();
+            camera.revoke_from(&store, "org.example.App").await.unwrap();
+
+            let permissions = PortalPermission::load_from(&store, "org.example.App")
+                .await
+                .unwrap();
+            assert!(permissions.iter().all(|p| p.id != "camera"));
+
+            // Other apps keep their entry
+            let other = PortalPermission::load_from(&store, "org.example.Other")
+                .await
+                .unwrap();
+            assert_eq!(other.len(), 1);
+            assert_eq!(other[0].id, "camera");
+        });
+    }
+}
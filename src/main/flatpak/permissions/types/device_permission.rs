@@ -14,45 +14,14 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
-use gtk::glib;
-
 use crate::main::context::{SkContextDetail, SkContextDetailKind, SkContextDetailLevel};
 use crate::main::flatpak::permissions::{PermissionDetails, SkPermissionSummary};
 use crate::main::i18n::i18n;
-
-#[glib::flags(name = "SkDevicePermission")]
-pub enum SkDevicePermission {
-    #[flags_value(name = "none")]
-    NONE = 1 << 0,
-    #[flags_value(name = "unknown")]
-    UNKNOWN = 1 << 1,
-    #[flags_value(name = "dri")]
-    DRI = 1 << 2,
-    #[flags_value(name = "kvm")]
-    KVM = 1 << 3,
-    #[flags_value(name = "shm")]
-    SHM = 1 << 4,
-    #[flags_value(name = "all")]
-    ALL = 1 << 5,
-}
+use crate::shared::flatpak::permissions::SkDevicePermission;
 
 impl PermissionDetails for SkDevicePermission {
     fn summary(&self) -> SkPermissionSummary {
-        let mut summary = SkPermissionSummary::empty();
-
-        if self.contains(Self::ALL) {
-            summary |= SkPermissionSummary::FULL_DEVICE_ACCESS;
-        }
-
-        if self.contains(Self::SHM) {
-            summary |= SkPermissionSummary::READ_DATA;
-        }
-
-        if self.contains(Self::UNKNOWN) {
-            summary |= SkPermissionSummary::UNKNOWN;
-        }
-
-        summary
+        self.permission_summary()
     }
 
     fn context_details(&self) -> Vec<SkContextDetail> {
@@ -137,15 +106,3 @@ impl PermissionDetails for SkDevicePermission {
         details
     }
 }
-
-impl From<&str> for SkDevicePermission {
-    fn from(value: &str) -> Self {
-        match value {
-            "dri" => Self::DRI,
-            "kvm" => Self::KVM,
-            "shm" => Self::SHM,
-            "all" => Self::ALL,
-            _ => Self::UNKNOWN,
-        }
-    }
-}
@@ -0,0 +1,122 @@
+// Souk - environment_permission.rs
+// Copyright (C) 2024  Felix Häcker <haeckerfelix@gnome.org>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::cell::OnceCell;
+
+use glib::{ParamSpec, Properties};
+use gtk::glib;
+use gtk::prelude::*;
+use gtk::subclass::prelude::*;
+
+use crate::main::context::{SkContextDetail, SkContextDetailKind, SkContextDetailLevel};
+use crate::main::flatpak::permissions::{PermissionDetails, SkPermissionSummary};
+use crate::main::i18n::{i18n, i18n_f};
+
+mod imp {
+    use super::*;
+
+    #[derive(Debug, Default, Properties)]
+    #[properties(wrapper_type = super::SkEnvironmentPermission)]
+    pub struct SkEnvironmentPermission {
+        #[property(get, set, construct_only)]
+        name: OnceCell<String>,
+        #[property(get, set, construct_only)]
+        value: OnceCell<String>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for SkEnvironmentPermission {
+        const NAME: &'static str = "SkEnvironmentPermission";
+        type Type = super::SkEnvironmentPermission;
+    }
+
+    impl ObjectImpl for SkEnvironmentPermission {
+        fn properties() -> &'static [ParamSpec] {
+            Self::derived_properties()
+        }
+
+        fn property(&self, id: usize, pspec: &ParamSpec) -> glib::Value {
+            Self::derived_property(self, id, pspec)
+        }
+
+        fn set_property(&self, id: usize, value: &glib::Value, pspec: &ParamSpec) {
+            Self::derived_set_property(self, id, value, pspec)
+        }
+    }
+}
+
+glib::wrapper! {
+    pub struct SkEnvironmentPermission(ObjectSubclass<imp::SkEnvironmentPermission>);
+}
+
+impl SkEnvironmentPermission {
+    pub fn new(name: &str, value: &str) -> Self {
+        glib::Object::builder()
+            .property("name", name)
+            .property("value", value)
+            .build()
+    }
+}
+
+impl PermissionDetails for SkEnvironmentPermission {
+    fn summary(&self) -> SkPermissionSummary {
+        SkPermissionSummary::for_environment(&self.name())
+    }
+
+    fn context_details(&self) -> Vec<SkContextDetail> {
+        let kind = SkContextDetailKind::Icon;
+        let icon_name = "utilities-terminal-symbolic";
+        let name = self.name();
+
+        // Most variables only configure the application itself, only the
+        // ones which change what gets loaded / contacted are worth mentioning
+        let (level, description) = match name.as_str() {
+            "LD_PRELOAD" | "LD_LIBRARY_PATH" | "LD_AUDIT" => (
+                SkContextDetailLevel::Warning,
+                i18n_f("Loads additional libraries from “{}”", &[&self.value()]),
+            ),
+            "PATH" | "PYTHONPATH" | "GTK_PATH" | "GIO_EXTRA_MODULES" | "GST_PLUGIN_PATH" => (
+                SkContextDetailLevel::Moderate,
+                i18n_f(
+                    "Searches for executables or modules in “{}”",
+                    &[&self.value()],
+                ),
+            ),
+            "DBUS_SESSION_BUS_ADDRESS" | "DBUS_SYSTEM_BUS_ADDRESS" => (
+                SkContextDetailLevel::Bad,
+                i18n_f(
+                    "Connects to the message bus at “{}”, which can bypass the service filtering",
+                    &[&self.value()],
+                ),
+            ),
+            "GTK_USE_PORTAL" if self.value() == "0" => (
+                SkContextDetailLevel::Moderate,
+                i18n("Does not use portals to ask for permission, e.g. when opening files"),
+            ),
+            _ => return Vec::new(),
+        };
+
+        let title = i18n_f("Sets “{}” Variable", &[&name]);
+
+        vec![SkContextDetail::new(
+            kind,
+            icon_name,
+            level,
+            &title,
+            &description,
+        )]
+    }
+}
@@ -0,0 +1,95 @@
+// Souk - feature_permission.rs
+// Copyright (C) 2024  Felix Häcker <haeckerfelix@gnome.org>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::main::context::{SkContextDetail, SkContextDetailKind, SkContextDetailLevel};
+use crate::main::flatpak::permissions::{PermissionDetails, SkPermissionSummary};
+use crate::main::i18n::i18n;
+use crate::shared::flatpak::permissions::SkFeaturePermission;
+
+impl PermissionDetails for SkFeaturePermission {
+    fn summary(&self) -> SkPermissionSummary {
+        self.permission_summary()
+    }
+
+    fn context_details(&self) -> Vec<SkContextDetail> {
+        let mut details = Vec::new();
+        let kind = SkContextDetailKind::Icon;
+
+        if self.contains(Self::DEVEL) {
+            let icon_name = "utilities-terminal-symbolic";
+            let level = SkContextDetailLevel::Bad;
+            let title = i18n("Development Features");
+            let description = i18n("Can inspect and modify other processes in the sandbox using debugging syscalls like ptrace");
+
+            details.push(SkContextDetail::new(
+                kind,
+                icon_name,
+                level,
+                &title,
+                &description,
+            ));
+        }
+
+        if self.contains(Self::BLUETOOTH) {
+            let icon_name = "bluetooth-symbolic";
+            let level = SkContextDetailLevel::Warning;
+            let title = i18n("Direct Bluetooth Access");
+            let description = i18n(
+                "Can communicate with bluetooth devices without going through a system service",
+            );
+
+            details.push(SkContextDetail::new(
+                kind,
+                icon_name,
+                level,
+                &title,
+                &description,
+            ));
+        }
+
+        if self.contains(Self::CANBUS) {
+            let icon_name = "network-wired-symbolic";
+            let level = SkContextDetailLevel::Warning;
+            let title = i18n("Access to CAN Bus");
+            let description = i18n("Can communicate with devices connected to a CAN bus, e.g. in vehicles or industrial machines");
+
+            details.push(SkContextDetail::new(
+                kind,
+                icon_name,
+                level,
+                &title,
+                &description,
+            ));
+        }
+
+        if self.contains(Self::UNKNOWN) {
+            let icon_name = "dialog-question-symbolic";
+            let level = SkContextDetailLevel::Bad;
+            let title = i18n("Unknown Sandbox Feature");
+            let description = i18n("Uses an unknown sandbox feature");
+
+            details.push(SkContextDetail::new(
+                kind,
+                icon_name,
+                level,
+                &title,
+                &description,
+            ));
+        }
+
+        details
+    }
+}
@@ -21,10 +21,10 @@ use gtk::glib;
 use gtk::prelude::*;
 use gtk::subclass::prelude::*;
 
-use super::SkFilesystemPermissionKind;
 use crate::main::context::{SkContextDetail, SkContextDetailKind, SkContextDetailLevel};
 use crate::main::flatpak::permissions::{PermissionDetails, SkPermissionSummary};
 use crate::main::i18n::{i18n, i18n_f};
+use crate::shared::flatpak::permissions::{FilesystemDetails, SkFilesystemPermissionKind};
 
 mod imp {
     use super::*;
@@ -77,21 +77,7 @@ impl SkFilesystemPermission {
     }
 
     pub fn from_flatpak(value: &str) -> Self {
-        let path: &str;
-        let kind = if value.ends_with(":rw") {
-            path = value.trim_end_matches(":rw");
-            SkFilesystemPermissionKind::ReadWrite
-        } else if value.ends_with(":create") {
-            path = value.trim_end_matches(":create");
-            SkFilesystemPermissionKind::Create
-        } else if value.ends_with(":ro") {
-            path = value.trim_end_matches(":ro");
-            SkFilesystemPermissionKind::ReadOnly
-        } else {
-            path = value;
-            SkFilesystemPermissionKind::ReadWrite
-        };
-
+        let (path, kind) = SkFilesystemPermissionKind::from_flatpak(value);
         Self::new(&kind, path)
     }
 
@@ -109,125 +95,20 @@ impl SkFilesystemPermission {
 
 impl PermissionDetails for SkFilesystemPermission {
     fn summary(&self) -> SkPermissionSummary {
-        let mut summary = SkPermissionSummary::empty();
-
-        let s = if self.kind() == SkFilesystemPermissionKind::ReadOnly {
-            SkPermissionSummary::READ_DATA
-        } else {
-            SkPermissionSummary::READWRITE_DATA
-        };
-        summary |= s;
-
-        if self.kind() != SkFilesystemPermissionKind::ReadOnly
-            && self.path().contains("flatpak/overrides")
-        {
-            summary |= SkPermissionSummary::SANDBOX_ESCAPE;
-        }
-
-        summary
+        SkPermissionSummary::for_filesystem(&self.path(), self.kind())
     }
 
     fn context_details(&self) -> Vec<SkContextDetail> {
-        let path = self.path();
-
-        vec![Details::new(self.kind().can_write(), &path).into()]
+        let creates = self.kind().creates();
+        vec![FilesystemDetails::with_details(
+            &self.path(),
+            self.kind(),
+            |details| details.context_detail(creates),
+        )]
     }
 }
 
-enum Details<'a> {
-    Home {
-        can_write: bool,
-        subdir: Option<&'a str>,
-    },
-    Host(bool),
-    HostOs(bool),
-    HostEtc(bool),
-    Desktop {
-        can_write: bool,
-        subdir: Option<&'a str>,
-    },
-    Documents {
-        can_write: bool,
-        subdir: Option<&'a str>,
-    },
-    Download {
-        can_write: bool,
-        subdir: Option<&'a str>,
-    },
-    Music {
-        can_write: bool,
-        subdir: Option<&'a str>,
-    },
-    Pictures {
-        can_write: bool,
-        subdir: Option<&'a str>,
-    },
-    Public {
-        can_write: bool,
-        subdir: Option<&'a str>,
-    },
-    Videos {
-        can_write: bool,
-        subdir: Option<&'a str>,
-    },
-    Templates {
-        can_write: bool,
-        subdir: Option<&'a str>,
-    },
-    Config {
-        can_write: bool,
-        subdir: Option<&'a str>,
-    },
-    Cache {
-        can_write: bool,
-        subdir: Option<&'a str>,
-    },
-    Data {
-        can_write: bool,
-        subdir: Option<&'a str>,
-    },
-    Runtime {
-        can_write: bool,
-        name: &'a str,
-    },
-    Path {
-        can_write: bool,
-        path: &'a str,
-    },
-}
-
-impl<'a> Details<'a> {
-    fn new(can_write: bool, path: &'a str) -> Self {
-        let (permission, subdir) = match path.split_once('/') {
-            Some(("", path)) => (path, None),
-            Some((path, subdir)) => (path, Some(subdir)),
-            None => (path, None),
-        };
-
-        match permission {
-            "home" | "~" => Self::Home { can_write, subdir },
-            "host" => Self::Host(can_write),
-            "host-os" => Self::HostOs(can_write),
-            "host-etc" => Self::HostEtc(can_write),
-            "xdg-desktop" => Self::Desktop { can_write, subdir },
-            "xdg-documents" => Self::Documents { can_write, subdir },
-            "xdg-download" => Self::Download { can_write, subdir },
-            "xdg-music" => Self::Music { can_write, subdir },
-            "xdg-pictures" => Self::Pictures { can_write, subdir },
-            "xdg-public-share" => Self::Public { can_write, subdir },
-            "xdg-videos" => Self::Videos { can_write, subdir },
-            "xdg-templates" => Self::Templates { can_write, subdir },
-            "xdg-config" => Self::Config { can_write, subdir },
-            "xdg-cache" => Self::Cache { can_write, subdir },
-            "xdg-data" => Self::Data { can_write, subdir },
-            "xdg-run" => Self::Runtime {
-                can_write,
-                name: subdir.unwrap_or("*"),
-            },
-            _ => Self::Path { can_write, path },
-        }
-    }
-
+impl FilesystemDetails<'_> {
     const fn icon_name(&self) -> &'static str {
         match self {
             Self::Home { .. } => "user-home-symbolic",
@@ -244,6 +125,10 @@ impl<'a> Details<'a> {
             Self::Cache { .. } => "folder-symbolic",
             Self::Data { .. } => "folder-symbolic",
             Self::Runtime { .. } => "system-run-symbolic",
+            Self::Pipewire => "camera-web-symbolic",
+            Self::SshKeys(_) | Self::GnupgKeys(_) => "dialog-password-symbolic",
+            Self::RemovableMedia { .. } => "drive-removable-media-symbolic",
+            Self::Traversal { .. } => "dialog-warning-symbolic",
             Self::Path { .. } => "folder-symbolic",
         }
     }
@@ -254,6 +139,14 @@ impl<'a> Details<'a> {
                 name: "app/com.discordapp.Discord",
                 ..
             } => SkContextDetailLevel::Moderate,
+            Self::Pipewire
+            | Self::RemovableMedia {
+                can_write: true, ..
+            } => SkContextDetailLevel::Warning,
+            Self::RemovableMedia { .. } => SkContextDetailLevel::Moderate,
+            Self::SshKeys(_) | Self::GnupgKeys(_) | Self::Traversal { .. } => {
+                SkContextDetailLevel::Bad
+            }
             Self::Home { .. }
             | Self::Host(_)
             | Self::HostOs(_)
@@ -534,6 +427,38 @@ impl<'a> Details<'a> {
                 i18n_f("“{}” Runtime Folder Read-Only Access", &[name]),
                 i18n_f("Can read runtime under “{}”", &[name])
             ),
+            Self::Pipewire => (
+                i18n("Screen, Camera and Audio Access"),
+                i18n("Can capture the screen, cameras and audio devices through PipeWire without asking")
+            ),
+            Self::SshKeys(false) => (
+                i18n("SSH Keys Read-Only Access"),
+                i18n("Can read your SSH keys, which allow logging into other computers")
+            ),
+            Self::SshKeys(true) => (
+                i18n("SSH Keys Read/Write Access"),
+                i18n("Can read and modify your SSH keys and configuration, which allows running commands outside of the sandbox")
+            ),
+            Self::GnupgKeys(false) => (
+                i18n("GnuPG Keys Read-Only Access"),
+                i18n("Can read your GnuPG keys, which are used for signing and encryption")
+            ),
+            Self::GnupgKeys(true) => (
+                i18n("GnuPG Keys Read/Write Access"),
+                i18n("Can read and modify your GnuPG keys and trusted identities")
+            ),
+            Self::RemovableMedia { can_write: true, path } => (
+                i18n("Removable Devices Read/Write Access"),
+                i18n_f("Can read and write data on removable devices and additional drives under “{}”", &[path])
+            ),
+            Self::RemovableMedia { can_write: false, path } => (
+                i18n("Removable Devices Read-Only Access"),
+                i18n_f("Can read data on removable devices and additional drives under “{}”", &[path])
+            ),
+            Self::Traversal { path, .. } => (
+                i18n("Access Outside of the Specified Folder"),
+                i18n_f("“{}” points outside of its folder, and can give access to arbitrary locations", &[path])
+            ),
             Self::Path { can_write: true, path } if path.contains("/flatpak/overrides")  => (
                 i18n("Explicit Access to Flatpak System Folder"),
                 i18n("Can set arbitrary permissions, or change the permissions of other applications")
@@ -548,15 +473,17 @@ impl<'a> Details<'a> {
             ),
         }
     }
-}
 
-impl<'a> From<Details<'a>> for SkContextDetail {
-    fn from(value: Details) -> Self {
-        let (title, description) = value.describe();
-        Self::new(
+    fn context_detail(&self, creates: bool) -> SkContextDetail {
+        let (title, mut description) = self.describe();
+        if creates {
+            description = i18n_f("{}, and creates it if it doesn't exist", &[&description]);
+        }
+
+        SkContextDetail::new(
             SkContextDetailKind::Icon,
-            value.icon_name(),
-            value.level(),
+            self.icon_name(),
+            self.level(),
             &title,
             &description,
         )
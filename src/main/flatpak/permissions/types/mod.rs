@@ -15,15 +15,17 @@
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
 mod device_permission;
+mod environment_permission;
+mod feature_permission;
 mod filesystem_permission;
-mod filesystem_permission_kind;
 mod service_permission;
 mod socket_permission;
 mod subsystem_permission;
 
-pub use device_permission::SkDevicePermission;
+pub use crate::shared::flatpak::permissions::{
+    SkDevicePermission, SkFeaturePermission, SkFilesystemPermissionKind, SkServicePolicy,
+    SkSocketPermission, SkSubsystemPermission,
+};
+pub use environment_permission::SkEnvironmentPermission;
 pub use filesystem_permission::SkFilesystemPermission;
-pub use filesystem_permission_kind::SkFilesystemPermissionKind;
 pub use service_permission::SkServicePermission;
-pub use socket_permission::SkSocketPermission;
-pub use subsystem_permission::SkSubsystemPermission;
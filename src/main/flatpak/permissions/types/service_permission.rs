@@ -15,29 +15,17 @@
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
 use std::cell::OnceCell;
-use std::sync::LazyLock;
 
 use glib::{ParamSpec, Properties};
 use gtk::glib;
 use gtk::prelude::*;
 use gtk::subclass::prelude::*;
 
+use super::SkServicePolicy;
 use crate::main::context::{SkContextDetail, SkContextDetailKind, SkContextDetailLevel};
 use crate::main::flatpak::permissions::{PermissionDetails, SkPermissionSummary};
 use crate::main::i18n::{i18n, i18n_f};
-
-static SENSITIVE_SERVICES: LazyLock<Vec<&'static str>> = LazyLock::new(|| {
-    vec![
-        "org.gnome.SessionManager",
-        "org.freedesktop.PackageKit",
-        "org.freedesktop.NetworkManager",
-        "org.freedesktop.UDisks2",
-        "ca.desrt.dconf",
-        "org.gnome.SettingsDaemon",
-        "org.freedesktop.secrets",
-        "org.freedesktop.Flatpak",
-    ]
-});
+use crate::shared::flatpak::SERVICE_CONFIG;
 
 mod imp {
     use super::*;
@@ -49,6 +37,8 @@ mod imp {
         name: OnceCell<String>,
         #[property(get, set, construct_only)]
         is_system: OnceCell<bool>,
+        #[property(get, set, construct_only, builder(SkServicePolicy::Talk))]
+        policy: OnceCell<SkServicePolicy>,
     }
 
     #[glib::object_subclass]
@@ -77,13 +67,29 @@ glib::wrapper! {
 }
 
 impl SkServicePermission {
-    pub fn new(name: &str, is_system: bool) -> Self {
+    pub fn new(name: &str, is_system: bool, policy: SkServicePolicy) -> Self {
         glib::Object::builder()
             .property("name", name)
             .property("is-system", is_system)
+            .property("policy", policy)
             .build()
     }
 
+    fn is_sensitive(&self) -> bool {
+        SERVICE_CONFIG.is_sensitive(&self.name())
+    }
+
+    /// Allows executing commands outside of the sandbox, or changing
+    /// permissions
+    pub fn is_dangerous(&self) -> bool {
+        SERVICE_CONFIG.is_dangerous(&self.name())
+    }
+
+    /// Commonly used service which doesn't count as additional permission
+    pub fn is_ignored(&self) -> bool {
+        SERVICE_CONFIG.is_ignored(&self.name())
+    }
+
     pub fn no_access_context() -> SkContextDetail {
         let kind = SkContextDetailKind::Icon;
         let icon_name = "system-run-symbolic".to_string();
@@ -98,20 +104,7 @@ impl SkServicePermission {
 
 impl PermissionDetails for SkServicePermission {
     fn summary(&self) -> SkPermissionSummary {
-        let mut summary = SkPermissionSummary::empty();
-
-        if SENSITIVE_SERVICES
-            .iter()
-            .any(|i| self.name().starts_with(i))
-        {
-            summary |= SkPermissionSummary::READWRITE_DATA;
-        }
-
-        if self.name().starts_with("org.freedesktop.Flatpak") {
-            summary |= SkPermissionSummary::SANDBOX_ESCAPE;
-        }
-
-        summary
+        SkPermissionSummary::for_service(&self.name(), self.policy())
     }
 
     fn context_details(&self) -> Vec<SkContextDetail> {
@@ -191,13 +184,57 @@ impl PermissionDetails for SkServicePermission {
             description = i18n("Can read and modify system / application settings");
         }
 
-        if SENSITIVE_SERVICES
-            .iter()
-            .any(|i| self.name().starts_with(i))
+        if self.name().starts_with("org.freedesktop.systemd1") {
+            title = i18n("Access to Service Manager");
+            description = i18n("Can start and stop services outside of the sandbox");
+        }
+
+        if self
+            .name()
+            .starts_with("org.freedesktop.impl.portal.PermissionStore")
         {
+            title = i18n("Access to Permission Store");
+            description = i18n("Can read and change the permissions of all apps");
+        }
+
+        if self.is_sensitive() || self.is_dangerous() {
             level = SkContextDetailLevel::Bad;
         }
 
+        match self.policy() {
+            SkServicePolicy::See | SkServicePolicy::None => {
+                level = SkContextDetailLevel::Neutral;
+                title = if !self.is_system() {
+                    i18n_f("Can See “{}” Service", &[&self.name()])
+                } else {
+                    i18n_f("Can See System Service “{}”", &[&self.name()])
+                };
+                description = i18n("Can only check whether the service is running");
+            }
+            SkServicePolicy::Own => {
+                // Owning a name allows impersonating the service, and
+                // receiving messages which are meant for it
+                level = if self.is_sensitive() || self.is_dangerous() || self.is_system() {
+                    SkContextDetailLevel::Bad
+                } else {
+                    SkContextDetailLevel::Warning
+                };
+                title = if !self.is_system() {
+                    i18n_f("Provides “{}” Service", &[&self.name()])
+                } else {
+                    i18n_f("Provides System Service “{}”", &[&self.name()])
+                };
+                description =
+                    i18n("Can act as this service, and receives messages which are meant for it");
+            }
+            SkServicePolicy::Talk => (),
+        }
+
+        if self.is_ignored() {
+            level = SkContextDetailLevel::Neutral;
+            description = i18n("Commonly used for desktop integration, therefore ignored");
+        }
+
         vec![SkContextDetail::new(
             kind,
             &icon_name,
@@ -14,67 +14,14 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
-use gtk::glib;
-
 use crate::main::context::{SkContextDetail, SkContextDetailKind, SkContextDetailLevel};
 use crate::main::flatpak::permissions::{PermissionDetails, SkPermissionSummary};
 use crate::main::i18n::i18n;
-
-#[glib::flags(name = "SkSocketPermission")]
-pub enum SkSocketPermission {
-    #[flags_value(name = "none")]
-    NONE = 1 << 0,
-    #[flags_value(name = "unknown")]
-    UNKNOWN = 1 << 1,
-    #[flags_value(name = "x11")]
-    X11 = 1 << 2,
-    #[flags_value(name = "wayland")]
-    WAYLAND = 1 << 3,
-    #[flags_value(name = "fallback-x11")]
-    FALLBACK_X11 = 1 << 4,
-    #[flags_value(name = "pulseaudio")]
-    PULSEAUDIO = 1 << 5,
-    #[flags_value(name = "system-bus")]
-    SYSTEM_BUS = 1 << 6,
-    #[flags_value(name = "session-bus")]
-    SESSION_BUS = 1 << 7,
-    #[flags_value(name = "ssh-auth")]
-    SSH_AUTH = 1 << 8,
-    #[flags_value(name = "pcsc")]
-    PCSC = 1 << 9,
-    #[flags_value(name = "cups")]
-    CUPS = 1 << 10,
-}
+use crate::shared::flatpak::permissions::SkSocketPermission;
 
 impl PermissionDetails for SkSocketPermission {
     fn summary(&self) -> SkPermissionSummary {
-        let mut summary = SkPermissionSummary::empty();
-
-        if self.contains(Self::X11) && !self.contains(Self::FALLBACK_X11) {
-            summary |= SkPermissionSummary::READ_DATA;
-        }
-
-        if self.contains(Self::SESSION_BUS) {
-            summary |= SkPermissionSummary::FULL_SESSION_BUS_ACCESS;
-        }
-
-        if self.contains(Self::SYSTEM_BUS) {
-            summary |= SkPermissionSummary::FULL_SYSTEM_BUS_ACCESS;
-        }
-
-        if self.contains(Self::SSH_AUTH) {
-            summary |= SkPermissionSummary::READ_DATA;
-        }
-
-        if self.contains(Self::PCSC) {
-            summary |= SkPermissionSummary::READ_DATA;
-        }
-
-        if self.contains(Self::UNKNOWN) {
-            summary |= SkPermissionSummary::UNKNOWN;
-        }
-
-        summary
+        self.permission_summary()
     }
 
     fn context_details(&self) -> Vec<SkContextDetail> {
@@ -185,20 +132,3 @@ impl PermissionDetails for SkSocketPermission {
         details
     }
 }
-
-impl From<&str> for SkSocketPermission {
-    fn from(value: &str) -> Self {
-        match value {
-            "x11" => Self::X11,
-            "wayland" => Self::WAYLAND,
-            "fallback-x11" => Self::FALLBACK_X11,
-            "pulseaudio" => Self::PULSEAUDIO,
-            "system-bus" => Self::SYSTEM_BUS,
-            "session-bus" => Self::SESSION_BUS,
-            "ssh-auth" => Self::SSH_AUTH,
-            "pcsc" => Self::PCSC,
-            "cups" => Self::CUPS,
-            _ => Self::UNKNOWN,
-        }
-    }
-}
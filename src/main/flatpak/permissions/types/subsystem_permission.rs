@@ -14,37 +14,14 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
-use gtk::glib;
-
 use crate::main::context::{SkContextDetail, SkContextDetailKind, SkContextDetailLevel};
 use crate::main::flatpak::permissions::{PermissionDetails, SkPermissionSummary};
 use crate::main::i18n::i18n;
-
-#[glib::flags(name = "SkSubsystemPermission")]
-pub enum SkSubsystemPermission {
-    #[flags_value(name = "none")]
-    NONE = 1 << 0,
-    #[flags_value(name = "unknown")]
-    UNKNOWN = 1 << 1,
-    #[flags_value(name = "network")]
-    NETWORK = 1 << 2,
-    #[flags_value(name = "ipc")]
-    IPC = 1 << 3,
-}
+use crate::shared::flatpak::permissions::SkSubsystemPermission;
 
 impl PermissionDetails for SkSubsystemPermission {
     fn summary(&self) -> SkPermissionSummary {
-        let mut summary = SkPermissionSummary::empty();
-
-        if self.contains(Self::NETWORK) {
-            summary |= SkPermissionSummary::NETWORK_ACCESS;
-        }
-
-        if self.contains(Self::UNKNOWN) {
-            summary |= SkPermissionSummary::UNKNOWN;
-        }
-
-        summary
+        self.permission_summary()
     }
 
     fn context_details(&self) -> Vec<SkContextDetail> {
@@ -96,13 +73,3 @@ impl PermissionDetails for SkSubsystemPermission {
         details
     }
 }
-
-impl From<&str> for SkSubsystemPermission {
-    fn from(value: &str) -> Self {
-        match value {
-            "network" => Self::NETWORK,
-            "ipc" => Self::IPC,
-            _ => Self::UNKNOWN,
-        }
-    }
-}
@@ -31,13 +31,48 @@ pub enum SkSideloadKind {
 
 impl SkSideloadKind {
     pub fn determine_kind(file: &File) -> SkSideloadKind {
-        let file = file.path().unwrap();
+        // Use the basename instead of the path, since remote files (eg. http
+        // urls) don't have a local path
+        let Some(basename) = file.basename() else {
+            return SkSideloadKind::None;
+        };
 
-        match file.extension().unwrap_or_default().to_str().unwrap() {
+        match basename.extension().unwrap_or_default().to_str().unwrap() {
             "flatpakref" => SkSideloadKind::Ref,
             "flatpakrepo" => SkSideloadKind::Repo,
             "flatpak" => SkSideloadKind::Bundle,
             _ => SkSideloadKind::None,
         }
     }
+
+    /// Maximum accepted size when downloading a file of this kind
+    pub fn max_download_size(&self) -> u64 {
+        match self {
+            // Ref and repo files are small keyfiles, only the included GPG key
+            // takes up some space
+            SkSideloadKind::Ref | SkSideloadKind::Repo => 1024 * 1024,
+            SkSideloadKind::Bundle => 8 * 1024 * 1024 * 1024,
+            SkSideloadKind::None => 0,
+        }
+    }
+
+    /// Content types which are accepted when downloading a file of this kind.
+    /// Lots of web servers don't know the Flatpak mimetypes, so generic types
+    /// get accepted as well.
+    pub fn content_types(&self) -> Vec<&'static str> {
+        match self {
+            SkSideloadKind::Ref => vec![
+                "application/vnd.flatpak.ref",
+                "application/octet-stream",
+                "text/plain",
+            ],
+            SkSideloadKind::Repo => vec![
+                "application/vnd.flatpak.repo",
+                "application/octet-stream",
+                "text/plain",
+            ],
+            SkSideloadKind::Bundle => vec!["application/vnd.flatpak", "application/octet-stream"],
+            SkSideloadKind::None => Vec::new(),
+        }
+    }
 }
@@ -14,6 +14,13 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
+use flatpak::prelude::*;
+use flatpak::{Installation, InstalledRef, Ref};
+use glib::KeyFile;
+use gtk::{gio, glib};
+
+use crate::main::flatpak::package::{SkPackage, SkPackageExt};
+
 pub fn normalize_string(string: &str) -> String {
     string
         .chars()
@@ -26,3 +33,50 @@ pub fn normalize_string(string: &str) -> String {
         })
         .collect()
 }
+
+/// Returns the installed ref of the package, if the package is installed
+pub fn installed_ref(package: &SkPackage) -> Option<InstalledRef> {
+    let installation = package.remote().installation()?;
+    let f_inst = Installation::from(&installation.info());
+    let f_ref = Ref::parse(&package.info().ref_).ok()?;
+
+    f_inst
+        .installed_ref(
+            f_ref.kind(),
+            &f_ref.name()?,
+            f_ref.arch().as_deref(),
+            f_ref.branch().as_deref(),
+            gio::Cancellable::NONE,
+        )
+        .ok()
+}
+
+pub fn is_installed(package: &SkPackage) -> bool {
+    installed_ref(package).is_some()
+}
+
+/// Returns the installed size in bytes, if the package is installed
+pub fn installed_size(package: &SkPackage) -> Option<u64> {
+    Some(installed_ref(package)?.installed_size())
+}
+
+/// Returns the metadata of the installed package, if the package is installed
+pub fn installed_metadata(package: &SkPackage) -> Option<KeyFile> {
+    let bytes = installed_ref(package)?
+        .load_metadata(gio::Cancellable::NONE)
+        .ok()?;
+
+    let keyfile = KeyFile::new();
+    keyfile
+        .load_from_bytes(&bytes, glib::KeyFileFlags::NONE)
+        .ok()?;
+    Some(keyfile)
+}
+
+/// Returns the appstream version of the installed package, if the package is
+/// installed
+pub fn installed_version(package: &SkPackage) -> Option<String> {
+    installed_ref(package)?
+        .appdata_version()
+        .map(|v| v.to_string())
+}
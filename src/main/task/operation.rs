@@ -25,6 +25,7 @@ use crate::main::flatpak::installation::SkRemote;
 use crate::main::flatpak::package::SkPackage;
 use crate::main::task::{SkOperationKind, SkOperationStatus};
 use crate::shared::appstream::AppstreamOperationKind;
+use crate::shared::flatpak::backup::BackupOperationKind;
 use crate::shared::flatpak::FlatpakOperationKind;
 use crate::shared::task::response::OperationActivity;
 
@@ -86,6 +87,12 @@ impl SkOperation {
             SkOperationKind::from(activity.flatpak_operation.clone())
         } else if activity.appstream_operation != AppstreamOperationKind::None {
             SkOperationKind::from(activity.appstream_operation.clone())
+        } else if activity.backup_operation != BackupOperationKind::None {
+            SkOperationKind::from(activity.backup_operation.clone())
+        } else if activity.download_url.is_some() {
+            SkOperationKind::Download
+        } else if activity.data_removal.is_some() {
+            SkOperationKind::RemoveData
         } else {
             warn!("Unable to determine operation kind");
             SkOperationKind::None
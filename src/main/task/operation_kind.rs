@@ -18,6 +18,7 @@ use glib::Enum;
 use gtk::glib;
 
 use crate::shared::appstream::AppstreamOperationKind;
+use crate::shared::flatpak::backup::BackupOperationKind;
 use crate::shared::flatpak::FlatpakOperationKind;
 
 #[derive(Copy, Debug, Clone, Eq, PartialEq, Enum)]
@@ -30,6 +31,11 @@ pub enum SkOperationKind {
     FlatpakUpdate,
     AppstreamSync,
     AppstreamCompile,
+    Download,
+    /// Removal of the sandbox data of an app
+    RemoveData,
+    BackupExport,
+    BackupRestore,
     #[default]
     None,
 }
@@ -55,3 +61,13 @@ impl From<AppstreamOperationKind> for SkOperationKind {
         }
     }
 }
+
+impl From<BackupOperationKind> for SkOperationKind {
+    fn from(kind: BackupOperationKind) -> Self {
+        match kind {
+            BackupOperationKind::Export => Self::BackupExport,
+            BackupOperationKind::Restore => Self::BackupRestore,
+            BackupOperationKind::None => Self::None,
+        }
+    }
+}
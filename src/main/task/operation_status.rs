@@ -33,7 +33,10 @@ pub enum SkOperationStatus {
     Updating,
     Uninstalling,
     Processing,
+    Downloading,
     Done,
+    Skipped,
+    Failed,
     #[default]
     None,
 }
@@ -53,7 +56,10 @@ impl From<OperationStatus> for SkOperationStatus {
             OperationStatus::Updating => Self::Updating,
             OperationStatus::Uninstalling => Self::Uninstalling,
             OperationStatus::Processing => Self::Processing,
+            OperationStatus::Downloading => Self::Downloading,
             OperationStatus::Done => Self::Done,
+            OperationStatus::Skipped => Self::Skipped,
+            OperationStatus::Failed => Self::Failed,
             OperationStatus::None => Self::None,
         }
     }
@@ -68,7 +74,10 @@ impl fmt::Display for SkOperationStatus {
             Self::Updating => i18n("Updating…"),
             Self::Uninstalling => i18n("Uninstalling…"),
             Self::Processing => i18n("Processing…"),
+            Self::Downloading => i18n("Downloading…"),
             Self::Done => String::new(),
+            Self::Skipped => i18n("Skipped"),
+            Self::Failed => i18n("Failed"),
             Self::None => i18n("Unknown"),
         };
 
@@ -20,13 +20,15 @@ use std::sync::LazyLock;
 use async_std::channel::{unbounded, Receiver, Sender};
 use glib::subclass::Signal;
 use glib::{ParamSpec, Properties};
-use gtk::glib;
 use gtk::prelude::*;
 use gtk::subclass::prelude::*;
+use gtk::{gio, glib};
 
 use crate::main::error::Error;
 use crate::main::flatpak::dry_run::SkDryRun;
 use crate::main::task::{SkOperation, SkOperationModel, SkTaskKind, SkTaskStatus};
+use crate::shared::appstream::{AddonInfo, ComponentInfo, RemoteSyncResult};
+use crate::shared::flatpak::DiskUsage;
 use crate::shared::task::response::{OperationActivity, OperationStatus, TaskResult};
 use crate::shared::task::Task;
 use crate::shared::WorkerError;
@@ -59,6 +61,13 @@ mod imp {
 
         // Possible result values
         pub result_dry_run: OnceCell<SkDryRun>,
+        pub result_appstream_sync: OnceCell<Vec<RemoteSyncResult>>,
+        pub result_appstream_lookup: OnceCell<Vec<ComponentInfo>>,
+        pub result_addons: OnceCell<Vec<AddonInfo>>,
+        pub result_download: OnceCell<String>,
+        pub result_disk_usage: OnceCell<DiskUsage>,
+        pub result_data_dry_run: OnceCell<u64>,
+        pub result_overrides: OnceCell<String>,
         pub result_error: OnceCell<WorkerError>,
     }
 
@@ -138,7 +147,7 @@ impl SkTask {
 
         // Find currently active ongoing operation of the task
         for oa in activity {
-            if oa.status != OperationStatus::Done
+            if !oa.status.is_completed()
                 && oa.status != OperationStatus::Pending
                 && oa.status != OperationStatus::None
             {
@@ -187,6 +196,80 @@ impl SkTask {
 
                 SkTaskStatus::Done
             }
+            TaskResult::DoneAppstreamSync(sync_results) => {
+                imp.result_appstream_sync
+                    .set(*sync_results.clone())
+                    .unwrap();
+
+                imp.progress.set(1.0);
+                self.notify_progress();
+                self.emit_by_name::<()>("done", &[]);
+                imp.finished_sender.get().unwrap().try_send(()).unwrap();
+
+                SkTaskStatus::Done
+            }
+            TaskResult::DoneAppstreamLookup(components) => {
+                imp.result_appstream_lookup
+                    .set(*components.clone())
+                    .unwrap();
+
+                imp.progress.set(1.0);
+                self.notify_progress();
+                self.emit_by_name::<()>("done", &[]);
+                imp.finished_sender.get().unwrap().try_send(()).unwrap();
+
+                SkTaskStatus::Done
+            }
+            TaskResult::DoneAddons(addons) => {
+                imp.result_addons.set(*addons.clone()).unwrap();
+
+                imp.progress.set(1.0);
+                self.notify_progress();
+                self.emit_by_name::<()>("done", &[]);
+                imp.finished_sender.get().unwrap().try_send(()).unwrap();
+
+                SkTaskStatus::Done
+            }
+            TaskResult::DoneDownload(path) => {
+                imp.result_download.set(path.clone()).unwrap();
+
+                imp.progress.set(1.0);
+                self.notify_progress();
+                self.emit_by_name::<()>("done", &[]);
+                imp.finished_sender.get().unwrap().try_send(()).unwrap();
+
+                SkTaskStatus::Done
+            }
+            TaskResult::DoneDiskUsage(disk_usage) => {
+                imp.result_disk_usage.set(*disk_usage.clone()).unwrap();
+
+                imp.progress.set(1.0);
+                self.notify_progress();
+                self.emit_by_name::<()>("done", &[]);
+                imp.finished_sender.get().unwrap().try_send(()).unwrap();
+
+                SkTaskStatus::Done
+            }
+            TaskResult::DoneDataDryRun(size) => {
+                imp.result_data_dry_run.set(*size).unwrap();
+
+                imp.progress.set(1.0);
+                self.notify_progress();
+                self.emit_by_name::<()>("done", &[]);
+                imp.finished_sender.get().unwrap().try_send(()).unwrap();
+
+                SkTaskStatus::Done
+            }
+            TaskResult::DoneOverrides(overrides) => {
+                imp.result_overrides.set(overrides.clone()).unwrap();
+
+                imp.progress.set(1.0);
+                self.notify_progress();
+                self.emit_by_name::<()>("done", &[]);
+                imp.finished_sender.get().unwrap().try_send(()).unwrap();
+
+                SkTaskStatus::Done
+            }
             TaskResult::Error(worker_error) => {
                 imp.result_error.set(*worker_error.clone()).unwrap();
 
@@ -228,6 +311,39 @@ impl SkTask {
         self.imp().result_dry_run.get().cloned()
     }
 
+    /// Per-remote sync results of an appstream update task
+    pub fn result_appstream_sync(&self) -> Option<Vec<RemoteSyncResult>> {
+        self.imp().result_appstream_sync.get().cloned()
+    }
+
+    /// Matching components of an appstream lookup task
+    pub fn result_appstream_lookup(&self) -> Option<Vec<ComponentInfo>> {
+        self.imp().result_appstream_lookup.get().cloned()
+    }
+
+    /// Available addons of an addon discovery task
+    pub fn result_addons(&self) -> Option<Vec<AddonInfo>> {
+        self.imp().result_addons.get().cloned()
+    }
+
+    /// The local file of a download task
+    pub fn result_download(&self) -> Option<gio::File> {
+        self.imp().result_download.get().map(gio::File::for_path)
+    }
+
+    pub fn result_disk_usage(&self) -> Option<DiskUsage> {
+        self.imp().result_disk_usage.get().cloned()
+    }
+
+    /// The size in bytes which would be freed by a data task
+    pub fn result_data_dry_run(&self) -> Option<u64> {
+        self.imp().result_data_dry_run.get().copied()
+    }
+
+    pub fn result_overrides(&self) -> Option<String> {
+        self.imp().result_overrides.get().cloned()
+    }
+
     pub fn result_error(&self) -> Option<WorkerError> {
         self.imp().result_error.get().cloned()
     }
@@ -17,7 +17,10 @@
 use glib::Enum;
 use gtk::glib;
 
-use crate::shared::task::{AppstreamTaskKind, FlatpakTaskKind, Task, TaskKind};
+use crate::shared::task::{
+    AppstreamTaskKind, BackupTaskKind, DataTaskKind, FlatpakTaskKind, OverrideTaskKind, Task,
+    TaskKind,
+};
 
 #[derive(Copy, Debug, Clone, Eq, PartialEq, Enum)]
 #[repr(u32)]
@@ -30,6 +33,8 @@ pub enum SkTaskKind {
     FlatpakInstall,
     /// A Flatpak package (with all related refs) gets uninstalled
     FlatpakUninstall,
+    /// Addons of a Flatpak package get installed / uninstalled
+    FlatpakChangeAddons,
     /// One single Flatpak package gets updated (with all related refs)
     FlatpakUpdate,
     /// A whole Flatpak installation gets updated
@@ -38,6 +43,28 @@ pub enum SkTaskKind {
     AppstreamEnsure,
     /// Updates entire appstream data
     AppstreamUpdate,
+    /// Looks up appstream components
+    AppstreamLookup,
+    /// Discovers addons of an app
+    AppstreamAddons,
+    /// Downloads a file
+    Download,
+    /// Calculates the disk usage of an app
+    DataDiskUsage,
+    /// Clears the cache of an app
+    DataClearCache,
+    /// Resets the data of an app
+    DataResetData,
+    /// Exports the data of apps into a backup archive
+    BackupExport,
+    /// Installs the apps of a backup archive and restores their data
+    BackupImport,
+    /// Loads the permission overrides of an app
+    OverrideLoad,
+    /// Writes the permission overrides of an app
+    OverrideSave,
+    /// Removes the permission overrides of an app
+    OverrideReset,
     #[default]
     None,
 }
@@ -52,6 +79,14 @@ impl SkTaskKind {
             }
         } else if let TaskKind::Appstream(appstream_task) = &data.kind {
             return appstream_task.kind.clone().into();
+        } else if let TaskKind::Download(_) = &data.kind {
+            return Self::Download;
+        } else if let TaskKind::Data(data_task) = &data.kind {
+            return data_task.kind.clone().into();
+        } else if let TaskKind::Backup(backup_task) = &data.kind {
+            return backup_task.kind.clone().into();
+        } else if let TaskKind::Override(override_task) = &data.kind {
+            return override_task.kind.clone().into();
         }
 
         error!("Unable to determine task kind from data: {:#?}", data);
@@ -65,6 +100,7 @@ impl From<FlatpakTaskKind> for SkTaskKind {
             FlatpakTaskKind::Install => Self::FlatpakInstall,
             FlatpakTaskKind::InstallRefFile => Self::FlatpakInstall,
             FlatpakTaskKind::InstallBundleFile => Self::FlatpakInstall,
+            FlatpakTaskKind::ChangeAddons => Self::FlatpakChangeAddons,
             FlatpakTaskKind::Update => Self::FlatpakUpdate,
             FlatpakTaskKind::UpdateInstallation => Self::FlatpakUpdateInstallation,
             FlatpakTaskKind::Uninstall => Self::FlatpakUninstall,
@@ -78,8 +114,42 @@ impl From<AppstreamTaskKind> for SkTaskKind {
         match kind {
             AppstreamTaskKind::Ensure => Self::AppstreamEnsure,
             AppstreamTaskKind::Update => Self::AppstreamUpdate,
+            AppstreamTaskKind::Lookup(_) => Self::AppstreamLookup,
+            AppstreamTaskKind::Addons(_) => Self::AppstreamAddons,
             AppstreamTaskKind::Dependency => Self::None,
             AppstreamTaskKind::None => Self::None,
         }
     }
 }
+
+impl From<DataTaskKind> for SkTaskKind {
+    fn from(kind: DataTaskKind) -> Self {
+        match kind {
+            DataTaskKind::DiskUsage => Self::DataDiskUsage,
+            DataTaskKind::ClearCache => Self::DataClearCache,
+            DataTaskKind::ResetData(_) => Self::DataResetData,
+            DataTaskKind::None => Self::None,
+        }
+    }
+}
+
+impl From<BackupTaskKind> for SkTaskKind {
+    fn from(kind: BackupTaskKind) -> Self {
+        match kind {
+            BackupTaskKind::Export { .. } => Self::BackupExport,
+            BackupTaskKind::Import { .. } => Self::BackupImport,
+            BackupTaskKind::None => Self::None,
+        }
+    }
+}
+
+impl From<OverrideTaskKind> for SkTaskKind {
+    fn from(kind: OverrideTaskKind) -> Self {
+        match kind {
+            OverrideTaskKind::Load => Self::OverrideLoad,
+            OverrideTaskKind::Save(_) => Self::OverrideSave,
+            OverrideTaskKind::Reset => Self::OverrideReset,
+            OverrideTaskKind::None => Self::None,
+        }
+    }
+}
@@ -21,7 +21,7 @@ use adw::subclass::prelude::*;
 use glib::{subclass, ParamSpec, Properties};
 use gtk::{glib, CompositeTemplate};
 
-use crate::main::context::{SkContextDetail, SkContextDetailKind, SkContextDetailLevel};
+use crate::main::context::{SkContextDetail, SkContextDetailKind};
 use crate::main::ui::utils;
 
 mod imp {
@@ -93,14 +93,7 @@ mod imp {
                 self.text_label.remove_css_class("size");
             }
 
-            let css = match detail.level() {
-                SkContextDetailLevel::Neutral => "color-neutral",
-                SkContextDetailLevel::Good => "color-green",
-                SkContextDetailLevel::Minor => "color-blue",
-                SkContextDetailLevel::Moderate => "color-orange",
-                SkContextDetailLevel::Warning => "color-yellow",
-                SkContextDetailLevel::Bad => "color-red",
-            };
+            let css = utils::level_css_color(detail.level());
             self.icon_image.add_css_class(css);
             self.text_label.add_css_class(css);
 
@@ -17,13 +17,13 @@
 use adw::prelude::*;
 use adw::subclass::prelude::*;
 use glib::{clone, subclass};
-use gtk::{gdk, gio, glib, CompositeTemplate};
+use gtk::{gio, glib, CompositeTemplate};
 
-use crate::main::app::SkApplication;
+use crate::main::appstream::{SkCollection, SkPackageAppstream};
 use crate::main::i18n::{i18n, i18n_f};
 use crate::main::task::SkTask;
 use crate::main::ui::main::SkSidebar;
-use crate::main::ui::page::SkInstalledPage;
+use crate::main::ui::page::{SkAppPage, SkDiscoverPage, SkInstalledPage};
 use crate::main::ui::SkProgressBar;
 use crate::shared::config;
 
@@ -48,11 +48,15 @@ mod imp {
         #[template_child]
         pub split_view: TemplateChild<adw::NavigationSplitView>,
 
+        #[template_child]
+        pub navigation_view: TemplateChild<adw::NavigationView>,
+        #[template_child]
+        pub discover_page: TemplateChild<SkDiscoverPage>,
         #[template_child]
         pub installed_page: TemplateChild<SkInstalledPage>,
 
         #[template_child]
-        pub status_page: TemplateChild<adw::StatusPage>,
+        pub sync_banner: TemplateChild<adw::Banner>,
     }
 
     #[glib::object_subclass]
@@ -80,19 +84,6 @@ mod imp {
                 self.obj().add_css_class("devel");
             }
 
-            // DND support for sideloading
-            let drop_target =
-                gtk::DropTarget::new(gdk::FileList::static_type(), gdk::DragAction::COPY);
-            drop_target.connect_drop(move |_, data, _, _| {
-                if let Ok(filelist) = data.get::<gdk::FileList>() {
-                    let app = SkApplication::default();
-                    app.open(&filelist.files(), "");
-                    return true;
-                }
-                false
-            });
-            self.status_page.add_controller(drop_target);
-
             // Initial view
             self.initial_status_page.set_icon_name(Some(config::APP_ID));
             let title = i18n_f("Welcome to {}", &[config::NAME]);
@@ -155,6 +146,31 @@ impl SkApplicationWindow {
             }),
         );
     }
+
+    /// Opens a page with the details of an app
+    pub fn show_app(&self, appstream: &SkPackageAppstream) {
+        let page = SkAppPage::new(appstream);
+        self.imp().navigation_view.push(&page);
+        self.present();
+    }
+
+    /// Shows the curated collections on the discover page
+    pub fn show_collections(&self, collections: &[SkCollection]) {
+        self.imp().discover_page.set_collections(collections);
+    }
+
+    /// Displays a banner with information about remotes whose appstream data
+    /// couldn't get updated
+    pub fn show_appstream_sync_warning(&self, message: &str) {
+        let imp = self.imp();
+        imp.sync_banner.set_title(message);
+        imp.sync_banner.set_revealed(true);
+    }
+
+    #[template_callback]
+    fn sync_banner_dismissed(&self) {
+        self.imp().sync_banner.set_revealed(false);
+    }
 }
 
 impl Default for SkApplicationWindow {
@@ -0,0 +1,1180 @@
+// Souk - app_page.rs
+// Copyright (C) 2024  Felix Häcker <haeckerfelix@gnome.org>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::cell::{Cell, OnceCell, RefCell};
+
+use adw::prelude::*;
+use adw::subclass::prelude::*;
+use glib::{clone, subclass, ParamSpec, Properties};
+use gtk::{gio, glib, CompositeTemplate};
+
+use appstream::enums::ProjectUrl;
+
+use crate::main::appstream::{utils, SkPackageAppstream, SkRelease};
+use crate::main::context::SkContext;
+use crate::main::error::Error;
+use crate::main::flatpak::addon::SkAddon;
+use crate::main::flatpak::dry_run::SkDryRun;
+use crate::main::flatpak::installation::SkInstallation;
+use crate::main::flatpak::package::{SkPackageExt, SkPackageKind};
+use crate::main::flatpak::permissions::{
+    describe_policy_violation, PermissionDetails, PermissionOverrides, PortalPermission,
+    SkAppPermissions,
+};
+use crate::main::flatpak::utils as flatpak_utils;
+use crate::main::flatpak::SkFlatpakOperationKind;
+use crate::main::i18n::{i18n, i18n_f};
+use crate::main::task::SkTask;
+use crate::main::ui::badge::SkBadge;
+use crate::main::ui::context::{SkContextBox, SkContextDetailRow};
+use crate::main::ui::utils as ui_utils;
+use crate::main::ui::SkProgressBar;
+use crate::main::SkApplication;
+use crate::shared::task::DataRemoval;
+use crate::shared::WorkerError;
+
+mod imp {
+    use super::*;
+
+    #[derive(Debug, Default, Properties, CompositeTemplate)]
+    #[properties(wrapper_type = super::SkAppPage)]
+    #[template(resource = "/de/haeckerfelix/Souk/gtk/page/app_page.ui")]
+    pub struct SkAppPage {
+        #[property(get, set, construct_only)]
+        appstream: OnceCell<SkPackageAppstream>,
+
+        #[template_child]
+        icon_image: TemplateChild<gtk::Image>,
+        #[template_child]
+        name_label: TemplateChild<gtk::Label>,
+        #[template_child]
+        developer_label: TemplateChild<gtk::Label>,
+        #[template_child]
+        version_label: TemplateChild<gtk::Label>,
+        #[template_child]
+        branch_badge: TemplateChild<SkBadge>,
+        #[template_child]
+        repository_badge: TemplateChild<SkBadge>,
+        #[template_child]
+        install_button: TemplateChild<gtk::Button>,
+        #[template_child]
+        update_button: TemplateChild<gtk::Button>,
+        #[template_child]
+        launch_button: TemplateChild<gtk::Button>,
+        #[template_child]
+        uninstall_button: TemplateChild<gtk::Button>,
+        #[template_child]
+        progress_bar: TemplateChild<SkProgressBar>,
+        #[template_child]
+        screenshots_box: TemplateChild<gtk::Box>,
+        #[template_child]
+        screenshots_carousel: TemplateChild<adw::Carousel>,
+        #[template_child]
+        summary_label: TemplateChild<gtk::Label>,
+        #[template_child]
+        description_label: TemplateChild<gtk::Label>,
+        #[template_child]
+        context_listbox: TemplateChild<gtk::ListBox>,
+        #[template_child]
+        addons_group: TemplateChild<adw::PreferencesGroup>,
+        #[template_child]
+        addons_listbox: TemplateChild<gtk::ListBox>,
+        #[template_child]
+        release_notes_group: TemplateChild<adw::PreferencesGroup>,
+        #[template_child]
+        release_notes_listbox: TemplateChild<gtk::ListBox>,
+        #[template_child]
+        storage_group: TemplateChild<adw::PreferencesGroup>,
+        #[template_child]
+        clear_cache_button: TemplateChild<gtk::Button>,
+        #[template_child]
+        reset_data_button: TemplateChild<gtk::Button>,
+        #[template_child]
+        overrides_group: TemplateChild<adw::PreferencesGroup>,
+        #[template_child]
+        network_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        home_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        host_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        x11_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        audio_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        devices_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        reset_overrides_button: TemplateChild<gtk::Button>,
+        #[template_child]
+        portal_group: TemplateChild<adw::PreferencesGroup>,
+        #[template_child]
+        links_group: TemplateChild<adw::PreferencesGroup>,
+        #[template_child]
+        links_listbox: TemplateChild<gtk::ListBox>,
+
+        contexts: OnceCell<gio::ListStore>,
+        /// Number of contexts at the beginning of `contexts`, which depend on
+        /// the installed state and get replaced after each task
+        state_contexts: Cell<u32>,
+        dry_run: RefCell<Option<SkDryRun>>,
+        /// Reason why the permission policy doesn't permit the installation
+        policy_blocked: RefCell<Option<String>>,
+        task: RefCell<Option<SkTask>>,
+
+        /// Overrides of the current user for this app, which get changed by
+        /// the permission switches
+        user_overrides: RefCell<Option<PermissionOverrides>>,
+        /// Permissions of the app without `user_overrides`
+        base_permissions: RefCell<Option<SkAppPermissions>>,
+        /// Set while the switches get updated, to avoid writing overrides
+        updating_overrides: Cell<bool>,
+        /// Rows of the permissions which got granted through portals
+        portal_rows: RefCell<Vec<SkContextDetailRow>>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for SkAppPage {
+        const NAME: &'static str = "SkAppPage";
+        type ParentType = adw::NavigationPage;
+        type Type = super::SkAppPage;
+
+        fn class_init(klass: &mut Self::Class) {
+            Self::bind_template(klass);
+            Self::Type::bind_template_callbacks(klass);
+        }
+
+        fn instance_init(obj: &subclass::InitializingObject<Self>) {
+            obj.init_template();
+        }
+    }
+
+    impl ObjectImpl for SkAppPage {
+        fn properties() -> &'static [ParamSpec] {
+            Self::derived_properties()
+        }
+
+        fn property(&self, id: usize, pspec: &ParamSpec) -> glib::Value {
+            Self::derived_property(self, id, pspec)
+        }
+
+        fn set_property(&self, id: usize, value: &glib::Value, pspec: &ParamSpec) {
+            Self::derived_set_property(self, id, value, pspec)
+        }
+
+        fn constructed(&self) {
+            self.parent_constructed();
+
+            let appstream = self.obj().appstream();
+            let package = appstream.package();
+
+            self.obj().set_title(&appstream.name());
+
+            self.icon_image.set_paintable(Some(&appstream.icon()));
+            self.name_label.set_text(&appstream.name());
+            self.developer_label.set_text(&appstream.developer_name());
+            self.version_label.set_text(&appstream.version_text(false));
+            self.summary_label.set_text(&appstream.summary());
+
+            let description = appstream.description();
+            self.description_label.set_text(&description);
+            self.description_label.set_visible(!description.is_empty());
+
+            self.branch_badge.set_value(package.branch());
+            self.repository_badge.set_value(package.remote().name());
+
+            // Releases since the installed version, or the latest release
+            let releases = gio::ListStore::new::<SkRelease>();
+            releases.extend_from_slice(&appstream.release_notes(&package));
+            self.release_notes_group
+                .set_visible(releases.n_items() != 0);
+            self.release_notes_listbox
+                .bind_model(Some(&releases), |release| {
+                    let release: &SkRelease = release.downcast_ref().unwrap();
+                    ui_utils::release_row(release).upcast()
+                });
+
+            // Context information
+            let contexts = gio::ListStore::new::<SkContext>();
+            contexts.append(&appstream.license_context());
+            contexts.append(&appstream.hardware_context());
+            if package.kind() == SkPackageKind::App {
+                contexts.append(&appstream.content_rating_context());
+            }
+            self.context_listbox.set_visible(contexts.n_items() != 0);
+            self.contexts.set(contexts.clone()).unwrap();
+
+            self.context_listbox.bind_model(
+                Some(&contexts),
+                clone!(
+                    #[weak(rename_to = this)]
+                    self,
+                    #[upgrade_or_panic]
+                    move |context| {
+                        let context: &SkContext = context.downcast_ref().unwrap();
+                        let row = SkContextDetailRow::new(&context.summary(), true);
+                        row.set_activatable(true);
+                        row.set_subtitle_lines(2);
+
+                        row.connect_activated(clone!(
+                            #[weak]
+                            this,
+                            #[weak]
+                            context,
+                            move |_| this.show_context(&context)
+                        ));
+
+                        row.upcast()
+                    }
+                ),
+            );
+
+            for (row, key, value) in self.override_rows() {
+                row.connect_active_notify(clone!(
+                    #[weak(rename_to = this)]
+                    self,
+                    move |row| {
+                        if this.updating_overrides.get() {
+                            return;
+                        }
+
+                        let active = row.is_active();
+                        let fut = clone!(
+                            #[weak]
+                            this,
+                            async move {
+                                this.change_override(key, value, active).await;
+                            }
+                        );
+                        crate::main::spawn_future_local(fut);
+                    }
+                ));
+            }
+
+            self.setup_links();
+            self.update_state();
+
+            let fut = clone!(
+                #[weak(rename_to = this)]
+                self,
+                async move {
+                    this.load_dry_run().await;
+                }
+            );
+            crate::main::spawn_future_local(fut);
+
+            let fut = clone!(
+                #[weak(rename_to = this)]
+                self,
+                async move {
+                    this.load_screenshots().await;
+                }
+            );
+            crate::main::spawn_future_local(fut);
+
+            let fut = clone!(
+                #[weak(rename_to = this)]
+                self,
+                async move {
+                    this.load_addons().await;
+                }
+            );
+            crate::main::spawn_future_local(fut);
+        }
+    }
+
+    impl WidgetImpl for SkAppPage {}
+
+    impl NavigationPageImpl for SkAppPage {}
+
+    impl SkAppPage {
+        /// Updates the visible actions, depending on the installed state of
+        /// the package and whether a task is running
+        fn update_state(&self) {
+            let package = self.obj().appstream().package();
+            let installed = flatpak_utils::is_installed(&package);
+            let busy = self.task.borrow().is_some();
+
+            let dry_run = self.dry_run.borrow();
+            let update_available = dry_run
+                .as_ref()
+                .is_some_and(|d| d.package().operation_kind() == SkFlatpakOperationKind::Update);
+            let blocked_reason = self.policy_blocked.borrow().clone();
+
+            self.install_button.set_visible(!installed && !busy);
+            self.install_button.set_sensitive(blocked_reason.is_none());
+            self.install_button
+                .set_tooltip_markup(blocked_reason.as_deref());
+            self.update_button
+                .set_visible(installed && update_available && !busy);
+            self.launch_button
+                .set_visible(installed && package.kind() == SkPackageKind::App && !busy);
+            self.uninstall_button.set_visible(installed && !busy);
+            self.progress_bar.set_visible(busy);
+
+            self.storage_group
+                .set_visible(installed && package.kind() == SkPackageKind::App);
+            self.clear_cache_button.set_sensitive(!busy);
+            self.reset_data_button.set_sensitive(!busy);
+
+            self.overrides_group.set_visible(
+                installed
+                    && package.kind() == SkPackageKind::App
+                    && self.user_overrides.borrow().is_some(),
+            );
+            self.overrides_group.set_sensitive(!busy);
+
+            self.portal_group.set_visible(
+                installed
+                    && package.kind() == SkPackageKind::App
+                    && !self.portal_rows.borrow().is_empty(),
+            );
+        }
+
+        /// Simulates the installation, to retrieve the size / permission
+        /// information and to check if an update is available
+        async fn load_dry_run(&self) {
+            let worker = SkApplication::default().worker();
+            let appstream = self.obj().appstream();
+            let package = appstream.package();
+            let installed = flatpak_utils::is_installed(&package);
+
+            let dry_run = match worker.install_flatpak(&package, &[], false, true).await {
+                Ok(task) => task.await_result().await.map(|_| task.result_dry_run()),
+                Err(err) => Err(err),
+            };
+
+            self.policy_blocked.replace(None);
+            let dry_run = match dry_run {
+                Ok(dry_run) => dry_run,
+                Err(Error::Worker(WorkerError::PolicyBlocked(violation))) => {
+                    let reason = describe_policy_violation(&violation);
+                    self.policy_blocked.replace(Some(reason));
+                    None
+                }
+                Err(err) => {
+                    warn!("Unable to run dry run: {}", err.message());
+                    None
+                }
+            };
+
+            let permissions = if installed && package.kind() == SkPackageKind::App {
+                self.load_portal_permissions().await;
+                self.load_permissions().await
+            } else {
+                self.user_overrides.replace(None);
+                None
+            };
+
+            let mut contexts = Vec::new();
+            match &dry_run {
+                Some(dry_run)
+                    if !installed
+                        || dry_run.package().operation_kind() == SkFlatpakOperationKind::Update =>
+                {
+                    contexts.push(dry_run.download_size_context());
+                    contexts.push(dry_run.installed_size_context());
+                    if package.kind() == SkPackageKind::App {
+                        contexts.push(dry_run.package().permissions_context());
+                        if let Some(context) = dry_run.package().permission_changes_context() {
+                            contexts.push(context);
+                        }
+                    }
+                }
+                _ => {
+                    let summary = appstream.version_text(true);
+                    match package.load_disk_usage().await {
+                        Ok(usage) => {
+                            contexts.push(SkContext::storage(&usage, &appstream.name(), &summary));
+                        }
+                        Err(err) => {
+                            warn!("Unable to calculate disk usage: {}", err.message());
+                            if let Some(size) = flatpak_utils::installed_size(&package) {
+                                let context =
+                                    SkContext::installed_size(size, &appstream.name(), &summary);
+                                contexts.push(context);
+                            }
+                        }
+                    }
+
+                    if let Some(permissions) = &permissions {
+                        contexts.push(SkContext::permissions(permissions));
+                    }
+                }
+            }
+
+            let store = self.contexts.get().unwrap();
+            store.splice(0, self.state_contexts.get(), &contexts);
+            self.state_contexts.set(contexts.len() as u32);
+            self.context_listbox.set_visible(store.n_items() != 0);
+
+            self.dry_run.replace(dry_run);
+            self.update_state();
+        }
+
+        /// Switch rows of the permissions group, with the `[Context]` entry
+        /// which they control
+        fn override_rows(&self) -> [(adw::SwitchRow, &'static str, &'static str); 6] {
+            [
+                (self.network_row.get(), "shared", "network"),
+                (self.home_row.get(), "filesystems", "home"),
+                (self.host_row.get(), "filesystems", "host"),
+                (self.x11_row.get(), "sockets", "x11"),
+                (self.audio_row.get(), "sockets", "pulseaudio"),
+                (self.devices_row.get(), "devices", "all"),
+            ]
+        }
+
+        fn user_installation() -> Option<SkInstallation> {
+            let installations = SkApplication::default().worker().installations();
+            installations
+                .snapshot()
+                .into_iter()
+                .filter_map(|i| i.downcast::<SkInstallation>().ok())
+                .find(|i| i.is_user())
+        }
+
+        /// Loads the overrides of the installed app, and returns its
+        /// effective permissions. The overrides get applied in the same order
+        /// as Flatpak does: System before user, global before per-app. System
+        /// overrides only apply to apps of a system-wide installation.
+        ///
+        /// The switches change the per-app overrides of the current user,
+        /// global and system-wide overrides are taken into account.
+        async fn load_permissions(&self) -> Option<SkAppPermissions> {
+            let worker = SkApplication::default().worker();
+            let package = self.obj().appstream().package();
+            let app_id = package.name();
+            let metadata = flatpak_utils::installed_metadata(&package)?;
+
+            let system = package.remote().installation().filter(|i| !i.is_user());
+            let user = Self::user_installation();
+
+            let mut layers = Vec::new();
+            for (installation, id) in [
+                (&system, None),
+                (&system, Some(app_id.as_str())),
+                (&user, None),
+            ] {
+                let Some(installation) = installation else {
+                    continue;
+                };
+
+                match worker.load_overrides(installation, id).await {
+                    Ok(overrides) => layers.push(overrides),
+                    Err(err) => warn!("Unable to load overrides: {}", err.message()),
+                }
+            }
+
+            let user_overrides = match &user {
+                Some(user) => match worker.load_overrides(user, Some(app_id.as_str())).await {
+                    Ok(overrides) => Some(overrides),
+                    Err(err) => {
+                        warn!("Unable to load user overrides: {}", err.message());
+                        None
+                    }
+                },
+                None => None,
+            };
+
+            let base =
+                SkAppPermissions::from_metadata(&PermissionOverrides::apply(&metadata, &layers));
+            let permissions = match &user_overrides {
+                Some(overrides) => {
+                    layers.push(overrides.clone());
+                    SkAppPermissions::from_metadata(&PermissionOverrides::apply(&metadata, &layers))
+                }
+                None => base.clone(),
+            };
+
+            self.updating_overrides.set(true);
+            for (row, key, value) in self.override_rows() {
+                row.set_active(permissions.contains_entry(key, value));
+            }
+            self.updating_overrides.set(false);
+
+            self.user_overrides.replace(user_overrides);
+            self.base_permissions.replace(Some(base));
+
+            Some(permissions)
+        }
+
+        /// Shows the permissions of the permission store, which got granted
+        /// through portals
+        async fn load_portal_permissions(&self) {
+            for row in self.portal_rows.take() {
+                self.portal_group.remove(&row);
+            }
+
+            let app_id = self.obj().appstream().package().name();
+            let permissions = match PortalPermission::load(&app_id).await {
+                Ok(permissions) => permissions,
+                Err(err) => {
+                    warn!("Unable to load portal permissions: {}", err.message());
+                    return;
+                }
+            };
+
+            let mut rows = Vec::new();
+            for permission in permissions {
+                let row = SkContextDetailRow::new(&permission.context_details()[0], false);
+
+                let revoke_button = gtk::Button::with_label(&i18n("Revoke"));
+                revoke_button.set_valign(gtk::Align::Center);
+                revoke_button.connect_clicked(clone!(
+                    #[weak(rename_to = this)]
+                    self,
+                    move |btn| {
+                        btn.set_sensitive(false);
+
+                        let permission = permission.clone();
+                        let fut = clone!(
+                            #[weak]
+                            this,
+                            async move {
+                                let app_id = this.obj().appstream().package().name();
+                                if let Err(err) = permission.revoke(&app_id).await {
+                                    this.show_error(&err.message());
+                                }
+
+                                this.load_portal_permissions().await;
+                                this.update_state();
+                            }
+                        );
+                        crate::main::spawn_future_local(fut);
+                    }
+                ));
+                row.add_suffix(&revoke_button);
+
+                self.portal_group.add(&row);
+                rows.push(row);
+            }
+
+            self.portal_rows.replace(rows);
+        }
+
+        /// Grants or revokes a permission with an override of the current
+        /// user. If the app requests the permission anyway, the override gets
+        /// removed instead.
+        async fn change_override(&self, key: &str, value: &str, active: bool) {
+            let Some(overrides) = self.user_overrides.borrow().clone() else {
+                return;
+            };
+            let Some(user) = Self::user_installation() else {
+                return;
+            };
+
+            let requested = self
+                .base_permissions
+                .borrow()
+                .as_ref()
+                .is_some_and(|p| p.contains_entry(key, value));
+            overrides.set_context_entry(key, value, (active != requested).then_some(active));
+
+            let worker = SkApplication::default().worker();
+            let app_id = self.obj().appstream().package().name();
+            let task = worker
+                .save_overrides(&user, Some(app_id.as_str()), &overrides)
+                .await;
+            self.run_task(task).await;
+        }
+
+        pub async fn reset_overrides(&self) {
+            let Some(user) = Self::user_installation() else {
+                return;
+            };
+
+            let worker = SkApplication::default().worker();
+            let app_id = self.obj().appstream().package().name();
+            let task = worker.reset_overrides(&user, Some(app_id.as_str())).await;
+            self.run_task(task).await;
+        }
+
+        /// Runs a Flatpak task and displays its progress
+        pub async fn run_task(&self, task: Result<SkTask, Error>) {
+            let task = match task {
+                Ok(task) => task,
+                Err(err) => {
+                    self.show_error(&err.message());
+                    return;
+                }
+            };
+
+            self.progress_bar.set_fraction(0.0);
+            let binding = task
+                .bind_property("progress", &self.progress_bar.get(), "fraction")
+                .build();
+
+            self.task.replace(Some(task.clone()));
+            self.update_state();
+
+            if let Err(err) = task.await_result().await {
+                self.show_error(&err.message());
+            }
+
+            binding.unbind();
+            self.task.replace(None);
+
+            // The installed state has changed
+            self.load_dry_run().await;
+            self.load_addons().await;
+        }
+
+        pub fn dry_run(&self) -> Option<SkDryRun> {
+            self.dry_run.borrow().clone()
+        }
+
+        fn show_error(&self, message: &str) {
+            let dialog = adw::AlertDialog::new(Some(&i18n("Operation Failed")), Some(message));
+            dialog.add_response("close", &i18n("_Close"));
+            dialog.present(Some(&*self.obj()));
+        }
+
+        /// Uninstalls the package. If the app has stored data, the user can
+        /// choose whether it gets kept, archived or deleted.
+        pub async fn uninstall(&self) {
+            let worker = SkApplication::default().worker();
+            let appstream = self.obj().appstream();
+            let package = appstream.package();
+
+            let data_size = if package.kind() == SkPackageKind::App {
+                let dry_run = match worker
+                    .uninstall_flatpak(&package, Some(DataRemoval::Delete), true)
+                    .await
+                {
+                    Ok(task) => task.await_result().await.map(|_| task.result_dry_run()),
+                    Err(err) => Err(err),
+                };
+
+                match dry_run {
+                    Ok(dry_run) => dry_run
+                        .and_then(|d| d.removed_data_size())
+                        .unwrap_or_default(),
+                    Err(err) => {
+                        self.show_error(&err.message());
+                        return;
+                    }
+                }
+            } else {
+                0
+            };
+
+            // Nothing to remove, uninstall right away
+            if data_size == 0 {
+                let task = worker.uninstall_flatpak(&package, None, false).await;
+                self.run_task(task).await;
+                return;
+            }
+
+            let heading = i18n_f("Uninstall “{}”?", &[&appstream.name()]);
+            let body = i18n_f(
+                "The app has stored {} of settings and data. Keeping it allows to continue where you left off after reinstalling the app.",
+                &[&glib::format_size(data_size)],
+            );
+            let dialog = adw::AlertDialog::new(Some(&heading), Some(&body));
+            dialog.add_response("cancel", &i18n("_Cancel"));
+            dialog.add_response("keep", &i18n("_Keep Data"));
+            dialog.add_response("backup", &i18n("_Archive Data"));
+            dialog.add_response("delete", &i18n("_Delete Data"));
+            dialog.set_response_appearance("delete", adw::ResponseAppearance::Destructive);
+            dialog.set_close_response("cancel");
+
+            dialog.connect_response(
+                None,
+                clone!(
+                    #[weak(rename_to = this)]
+                    self,
+                    move |_, response| {
+                        let remove_data = match response {
+                            "keep" => None,
+                            "backup" => Some(DataRemoval::Backup),
+                            "delete" => Some(DataRemoval::Delete),
+                            _ => return,
+                        };
+
+                        let fut = clone!(
+                            #[weak]
+                            this,
+                            async move {
+                                let worker = SkApplication::default().worker();
+                                let package = this.obj().appstream().package();
+                                let task =
+                                    worker.uninstall_flatpak(&package, remove_data, false).await;
+                                this.run_task(task).await;
+                            }
+                        );
+                        crate::main::spawn_future_local(fut);
+                    }
+                ),
+            );
+
+            dialog.present(Some(&*self.obj()));
+        }
+
+        pub async fn clear_cache(&self) {
+            let worker = SkApplication::default().worker();
+            let package = self.obj().appstream().package();
+
+            let size = match worker.clear_app_cache(&package, true).await {
+                Ok(task) => task
+                    .await_result()
+                    .await
+                    .map(|_| task.result_data_dry_run()),
+                Err(err) => Err(err),
+            };
+            let size = match size {
+                Ok(size) => size.unwrap_or_default(),
+                Err(err) => {
+                    self.show_error(&err.message());
+                    return;
+                }
+            };
+
+            let body = i18n_f(
+                "{} of temporary files will be deleted. The app should be closed beforehand.",
+                &[&glib::format_size(size)],
+            );
+            let dialog = adw::AlertDialog::new(Some(&i18n("Clear Cache?")), Some(&body));
+            dialog.add_response("cancel", &i18n("_Cancel"));
+            dialog.add_response("clear", &i18n("_Clear"));
+            dialog.set_response_appearance("clear", adw::ResponseAppearance::Destructive);
+            dialog.set_close_response("cancel");
+
+            dialog.connect_response(
+                Some("clear"),
+                clone!(
+                    #[weak(rename_to = this)]
+                    self,
+                    move |_, _| {
+                        let fut = clone!(
+                            #[weak]
+                            this,
+                            async move {
+                                let worker = SkApplication::default().worker();
+                                let package = this.obj().appstream().package();
+                                let task = worker.clear_app_cache(&package, false).await;
+                                this.run_task(task).await;
+                            }
+                        );
+                        crate::main::spawn_future_local(fut);
+                    }
+                ),
+            );
+
+            dialog.present(Some(&*self.obj()));
+        }
+
+        pub async fn reset_data(&self) {
+            let worker = SkApplication::default().worker();
+            let appstream = self.obj().appstream();
+            let package = appstream.package();
+
+            let size = match worker
+                .reset_app_data(&package, DataRemoval::default(), true)
+                .await
+            {
+                Ok(task) => task
+                    .await_result()
+                    .await
+                    .map(|_| task.result_data_dry_run()),
+                Err(err) => Err(err),
+            };
+            let size = match size {
+                Ok(size) => size.unwrap_or_default(),
+                Err(err) => {
+                    self.show_error(&err.message());
+                    return;
+                }
+            };
+
+            let body = i18n_f(
+                "All settings and data of “{}” will be removed, which frees {}. The app should be closed beforehand.",
+                &[&appstream.name(), &glib::format_size(size)],
+            );
+            let dialog = adw::AlertDialog::new(Some(&i18n("Reset App Data?")), Some(&body));
+            dialog.add_response("cancel", &i18n("_Cancel"));
+            dialog.add_response("backup", &i18n("Move to _Backup"));
+            dialog.add_response("trash", &i18n("Move to _Trash"));
+            dialog.set_response_appearance("trash", adw::ResponseAppearance::Destructive);
+            dialog.set_close_response("cancel");
+
+            dialog.connect_response(
+                None,
+                clone!(
+                    #[weak(rename_to = this)]
+                    self,
+                    move |_, response| {
+                        let removal = match response {
+                            "trash" => DataRemoval::Trash,
+                            "backup" => DataRemoval::Backup,
+                            _ => return,
+                        };
+
+                        let fut = clone!(
+                            #[weak]
+                            this,
+                            async move {
+                                let worker = SkApplication::default().worker();
+                                let package = this.obj().appstream().package();
+                                let task = worker.reset_app_data(&package, removal, false).await;
+                                this.run_task(task).await;
+                            }
+                        );
+                        crate::main::spawn_future_local(fut);
+                    }
+                ),
+            );
+
+            dialog.present(Some(&*self.obj()));
+        }
+
+        fn setup_links(&self) {
+            let mut rows = Vec::new();
+            for url in self.obj().appstream().urls() {
+                let (title, icon_name, url) = match url {
+                    ProjectUrl::Homepage(url) => (i18n("Homepage"), "go-home-symbolic", url),
+                    ProjectUrl::BugTracker(url) => {
+                        (i18n("Report an Issue"), "computer-fail-symbolic", url)
+                    }
+                    ProjectUrl::Help(url) => (i18n("Help"), "help-about-symbolic", url),
+                    ProjectUrl::Donation(url) => (i18n("Donate"), "emote-love-symbolic", url),
+                    ProjectUrl::Translate(url) => (
+                        i18n("Contribute Translations"),
+                        "preferences-desktop-locale-symbolic",
+                        url,
+                    ),
+                    ProjectUrl::VcsBrowser(url) => {
+                        (i18n("Source Code"), "text-x-generic-symbolic", url)
+                    }
+                    _ => continue,
+                };
+
+                let row = adw::ActionRow::builder()
+                    .title(title)
+                    .subtitle(glib::markup_escape_text(url.as_str()))
+                    .activatable(true)
+                    .build();
+                row.add_prefix(&gtk::Image::from_icon_name(icon_name));
+                row.add_suffix(&gtk::Image::from_icon_name("adw-external-link-symbolic"));
+
+                row.connect_activated(move |row| {
+                    let window = row.root().and_downcast::<gtk::Window>();
+                    gtk::show_uri(window.as_ref(), url.as_str(), gtk::gdk::CURRENT_TIME);
+                });
+
+                rows.push(row);
+            }
+
+            self.links_group.set_visible(!rows.is_empty());
+            for row in rows {
+                self.links_listbox.append(&row);
+            }
+        }
+
+        pub fn show_version_history(&self) {
+            let listbox = gtk::ListBox::builder()
+                .selection_mode(gtk::SelectionMode::None)
+                .valign(gtk::Align::Start)
+                .css_classes(["boxed-list"])
+                .build();
+
+            for release in self.obj().appstream().releases() {
+                let row = ui_utils::release_row(&release);
+                listbox.append(&row);
+            }
+
+            self.push_subpage(&listbox, &i18n("Version History"));
+        }
+
+        async fn load_addons(&self) {
+            let package = self.obj().appstream().package();
+            if package.kind() != SkPackageKind::App {
+                return;
+            }
+
+            let worker = SkApplication::default().worker();
+            let addons = match worker.addons(&package).await {
+                Ok(addons) => addons,
+                Err(err) => {
+                    warn!("Unable to load addons: {}", err.message());
+                    return;
+                }
+            };
+
+            // Addons can only be changed for installed apps
+            let app_installed = flatpak_utils::is_installed(&package);
+            if !app_installed {
+                let msg = i18n("Add-ons can be installed after the app has been installed.");
+                self.addons_group.set_description(Some(&msg));
+            }
+
+            let model = gio::ListStore::new::<SkAddon>();
+            model.extend_from_slice(&addons);
+            self.addons_group.set_visible(model.n_items() != 0);
+
+            self.addons_listbox.bind_model(
+                Some(&model),
+                clone!(
+                    #[weak(rename_to = this)]
+                    self,
+                    #[upgrade_or_panic]
+                    move |addon| {
+                        let addon: &SkAddon = addon.downcast_ref().unwrap();
+                        this.addon_row(addon, app_installed).upcast()
+                    }
+                ),
+            );
+        }
+
+        fn addon_row(&self, addon: &SkAddon, app_installed: bool) -> adw::ActionRow {
+            let appstream = addon.appstream();
+
+            let row = adw::ActionRow::builder()
+                .title(glib::markup_escape_text(&appstream.name()))
+                .subtitle(glib::markup_escape_text(&appstream.summary()))
+                .build();
+
+            let icon = gtk::Image::from_paintable(Some(&appstream.icon()));
+            icon.set_pixel_size(32);
+            row.add_prefix(&icon);
+
+            let switch = gtk::Switch::builder()
+                .active(addon.is_installed())
+                .sensitive(app_installed)
+                .valign(gtk::Align::Center)
+                .build();
+            row.add_suffix(&switch);
+            row.set_activatable_widget(Some(&switch));
+
+            switch.connect_state_set(clone!(
+                #[weak(rename_to = this)]
+                self,
+                #[weak]
+                addon,
+                #[upgrade_or]
+                glib::Propagation::Stop,
+                move |switch, state| {
+                    switch.set_sensitive(false);
+
+                    let fut = clone!(
+                        #[weak]
+                        this,
+                        #[weak]
+                        addon,
+                        async move {
+                            this.change_addon(&addon, state).await;
+                        }
+                    );
+                    crate::main::spawn_future_local(fut);
+
+                    glib::Propagation::Proceed
+                }
+            ));
+
+            row
+        }
+
+        async fn change_addon(&self, addon: &SkAddon, install: bool) {
+            let worker = SkApplication::default().worker();
+            let package = self.obj().appstream().package();
+
+            let addons = [addon.clone()];
+            let (install, uninstall): (&[SkAddon], &[SkAddon]) = if install {
+                (&addons, &[])
+            } else {
+                (&[], &addons)
+            };
+
+            let result = match worker
+                .change_flatpak_addons(&package, install, uninstall)
+                .await
+            {
+                Ok(task) => task.await_result().await,
+                Err(err) => Err(err),
+            };
+
+            if let Err(err) = result {
+                warn!("Unable to change addons: {}", err.message());
+            }
+
+            // Reload, so that the actual installed state gets displayed
+            self.load_addons().await;
+        }
+
+        fn show_context(&self, context: &SkContext) {
+            self.push_subpage(&SkContextBox::new(context), &i18n("Information"));
+        }
+
+        /// Pushes a page with additional information onto the navigation view
+        fn push_subpage(&self, child: &impl IsA<gtk::Widget>, title: &str) {
+            let Some(navigation_view) = self
+                .obj()
+                .ancestor(adw::NavigationView::static_type())
+                .and_downcast::<adw::NavigationView>()
+            else {
+                return;
+            };
+
+            let clamp = adw::Clamp::builder()
+                .margin_top(24)
+                .margin_bottom(12)
+                .margin_start(12)
+                .margin_end(12)
+                .tightening_threshold(275)
+                .child(child)
+                .build();
+            let scrolled_window = gtk::ScrolledWindow::builder().child(&clamp).build();
+
+            let toolbar_view = adw::ToolbarView::new();
+            toolbar_view.add_top_bar(&adw::HeaderBar::new());
+            toolbar_view.set_content(Some(&scrolled_window));
+
+            let page = adw::NavigationPage::new(&toolbar_view, title);
+            navigation_view.push(&page);
+        }
+
+        async fn load_screenshots(&self) {
+            for url in self.obj().appstream().screenshot_urls() {
+                match utils::load_screenshot(&url).await {
+                    Ok(texture) => {
+                        let picture = gtk::Picture::for_paintable(&texture);
+                        picture.set_hexpand(true);
+                        picture.set_content_fit(gtk::ContentFit::Contain);
+
+                        self.screenshots_carousel.append(&picture);
+                        self.screenshots_box.set_visible(true);
+                    }
+                    Err(err) => warn!("Unable to load screenshot {url}: {}", err.message()),
+                }
+            }
+        }
+    }
+}
+
+glib::wrapper! {
+    pub struct SkAppPage(
+        ObjectSubclass<imp::SkAppPage>)
+        @extends gtk::Widget, adw::NavigationPage;
+}
+
+#[gtk::template_callbacks]
+impl SkAppPage {
+    pub fn new(appstream: &SkPackageAppstream) -> Self {
+        glib::Object::builder()
+            .property("appstream", appstream)
+            .build()
+    }
+
+    #[template_callback]
+    fn install_clicked(&self) {
+        let package = self.appstream().package();
+
+        // The app is already installed from a different remote
+        let uninstall_before_install = self
+            .imp()
+            .dry_run()
+            .is_some_and(|d| d.is_replacing_remote().is_some());
+
+        let fut = clone!(
+            #[weak(rename_to = this)]
+            self,
+            async move {
+                let worker = SkApplication::default().worker();
+                let task = worker
+                    .install_flatpak(&package, &[], uninstall_before_install, false)
+                    .await;
+                this.imp().run_task(task).await;
+            }
+        );
+        crate::main::spawn_future_local(fut);
+    }
+
+    #[template_callback]
+    fn update_clicked(&self) {
+        let package = self.appstream().package();
+
+        let fut = clone!(
+            #[weak(rename_to = this)]
+            self,
+            async move {
+                let worker = SkApplication::default().worker();
+                let task = worker.update_flatpak(&package).await;
+                this.imp().run_task(task).await;
+            }
+        );
+        crate::main::spawn_future_local(fut);
+    }
+
+    #[template_callback]
+    fn uninstall_clicked(&self) {
+        let fut = clone!(
+            #[weak(rename_to = this)]
+            self,
+            async move {
+                this.imp().uninstall().await;
+            }
+        );
+        crate::main::spawn_future_local(fut);
+    }
+
+    #[template_callback]
+    fn launch_clicked(&self) {
+        let package = self.appstream().package();
+        if let Some(installation) = package.remote().installation() {
+            installation.launch_app(&package);
+        }
+    }
+
+    #[template_callback]
+    fn clear_cache_clicked(&self) {
+        let fut = clone!(
+            #[weak(rename_to = this)]
+            self,
+            async move {
+                this.imp().clear_cache().await;
+            }
+        );
+        crate::main::spawn_future_local(fut);
+    }
+
+    #[template_callback]
+    fn reset_data_clicked(&self) {
+        let fut = clone!(
+            #[weak(rename_to = this)]
+            self,
+            async move {
+                this.imp().reset_data().await;
+            }
+        );
+        crate::main::spawn_future_local(fut);
+    }
+
+    #[template_callback]
+    fn reset_overrides_clicked(&self) {
+        let fut = clone!(
+            #[weak(rename_to = this)]
+            self,
+            async move {
+                this.imp().reset_overrides().await;
+            }
+        );
+        crate::main::spawn_future_local(fut);
+    }
+
+    #[template_callback]
+    fn show_version_history(&self) {
+        self.imp().show_version_history();
+    }
+}
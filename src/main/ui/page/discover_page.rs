@@ -0,0 +1,172 @@
+// Souk - discover_page.rs
+// Copyright (C) 2024  Felix Häcker <haeckerfelix@gnome.org>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use adw::prelude::*;
+use adw::subclass::prelude::*;
+use glib::{clone, subclass};
+use gtk::{gdk, glib, CompositeTemplate};
+
+use crate::main::appstream::{SkCollection, SkPackageAppstream};
+use crate::main::ui::main::SkApplicationWindow;
+use crate::main::SkApplication;
+
+mod imp {
+    use super::*;
+
+    #[derive(Debug, Default, CompositeTemplate)]
+    #[template(resource = "/de/haeckerfelix/Souk/gtk/page/discover_page.ui")]
+    pub struct SkDiscoverPage {
+        #[template_child]
+        pub stack: TemplateChild<gtk::Stack>,
+        #[template_child]
+        pub status_page: TemplateChild<adw::StatusPage>,
+        #[template_child]
+        pub collections_view: TemplateChild<gtk::ScrolledWindow>,
+        #[template_child]
+        pub collections_box: TemplateChild<gtk::Box>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for SkDiscoverPage {
+        const NAME: &'static str = "SkDiscoverPage";
+        type ParentType = adw::Bin;
+        type Type = super::SkDiscoverPage;
+
+        fn class_init(klass: &mut Self::Class) {
+            Self::bind_template(klass);
+        }
+
+        fn instance_init(obj: &subclass::InitializingObject<Self>) {
+            obj.init_template();
+        }
+    }
+
+    impl ObjectImpl for SkDiscoverPage {
+        fn constructed(&self) {
+            self.parent_constructed();
+
+            // DND support for sideloading
+            let drop_target =
+                gtk::DropTarget::new(gdk::FileList::static_type(), gdk::DragAction::COPY);
+            drop_target.connect_drop(move |_, data, _, _| {
+                if let Ok(filelist) = data.get::<gdk::FileList>() {
+                    let app = SkApplication::default();
+                    app.open(&filelist.files(), "");
+                    return true;
+                }
+                false
+            });
+            self.obj().add_controller(drop_target);
+        }
+    }
+
+    impl WidgetImpl for SkDiscoverPage {}
+
+    impl BinImpl for SkDiscoverPage {}
+
+    impl SkDiscoverPage {
+        pub fn collection_group(&self, collection: &SkCollection) -> adw::PreferencesGroup {
+            let listbox = gtk::ListBox::builder()
+                .selection_mode(gtk::SelectionMode::None)
+                .css_classes(["boxed-list"])
+                .build();
+
+            listbox.bind_model(Some(&collection.apps()), |appstream| {
+                let appstream: &SkPackageAppstream = appstream.downcast_ref().unwrap();
+
+                let icon = gtk::Image::from_paintable(Some(&appstream.icon()));
+                icon.set_pixel_size(48);
+                icon.add_css_class("icon-dropshadow");
+
+                let row = adw::ActionRow::builder()
+                    .title(glib::markup_escape_text(&appstream.name()))
+                    .subtitle(glib::markup_escape_text(&appstream.summary()))
+                    .subtitle_lines(2)
+                    .activatable(true)
+                    .build();
+
+                row.add_prefix(&icon);
+                row.add_suffix(&gtk::Image::from_icon_name("go-next-symbolic"));
+                row.into()
+            });
+
+            listbox.connect_row_activated(clone!(
+                #[weak(rename_to = this)]
+                self,
+                #[weak]
+                collection,
+                move |_, row| {
+                    let position = row.index() as u32;
+                    let appstream = collection
+                        .apps()
+                        .item(position)
+                        .and_downcast::<SkPackageAppstream>();
+                    let window = this.obj().root().and_downcast::<SkApplicationWindow>();
+
+                    if let (Some(appstream), Some(window)) = (appstream, window) {
+                        window.show_app(&appstream);
+                    }
+                }
+            ));
+
+            let group = adw::PreferencesGroup::builder()
+                .title(glib::markup_escape_text(&collection.title()))
+                .build();
+
+            if !collection.description().is_empty() {
+                group.set_description(Some(&glib::markup_escape_text(&collection.description())));
+            }
+
+            group.add(&listbox);
+            group
+        }
+    }
+}
+
+glib::wrapper! {
+    pub struct SkDiscoverPage(
+        ObjectSubclass<imp::SkDiscoverPage>)
+        @extends gtk::Widget, adw::Bin;
+}
+
+impl SkDiscoverPage {
+    /// Displays the curated collections. Falls back to the sideload hint if
+    /// there are no collections.
+    pub fn set_collections(&self, collections: &[SkCollection]) {
+        let imp = self.imp();
+
+        while let Some(child) = imp.collections_box.first_child() {
+            imp.collections_box.remove(&child);
+        }
+
+        for collection in collections {
+            let group = imp.collection_group(collection);
+            imp.collections_box.append(&group);
+        }
+
+        if collections.is_empty() {
+            imp.stack.set_visible_child(&imp.status_page.get());
+        } else {
+            imp.stack.set_visible_child(&imp.collections_view.get());
+        }
+    }
+}
+
+impl Default for SkDiscoverPage {
+    fn default() -> Self {
+        glib::Object::new()
+    }
+}
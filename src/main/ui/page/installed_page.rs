@@ -14,15 +14,27 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
-use std::cell::Cell;
+use std::cell::{Cell, OnceCell, RefCell};
+use std::cmp::{Ordering, Reverse};
+use std::rc::Rc;
 
 use adw::prelude::*;
 use adw::subclass::prelude::*;
-use glib::{clone, subclass, ParamSpec, Properties};
-use gtk::{glib, CompositeTemplate};
+use glib::{clone, subclass, ParamSpec, Properties, SignalHandlerId};
+use gtk::{gio, glib, CompositeTemplate};
 
-use crate::main::flatpak::package::SkPackage;
+use crate::main::appstream::SkPackageAppstream;
+use crate::main::flatpak::package::{
+    SkInstalledPackage, SkInstalledSortKind, SkPackage, SkPackageExt, SkPackageKind,
+    SkPackageModel, SkPackageSubrefKind,
+};
+use crate::main::flatpak::permissions::{PermissionRisk, SkAppPermissions};
+use crate::main::flatpak::utils as flatpak_utils;
+use crate::main::i18n::{i18n, ni18n_f};
+use crate::main::ui::context::SkContextDetailRow;
 use crate::main::ui::installation::SkInstallationListBox;
+use crate::main::ui::main::SkApplicationWindow;
+use crate::main::ui::utils as ui_utils;
 use crate::main::SkApplication;
 
 mod imp {
@@ -40,6 +52,26 @@ mod imp {
         installation_listbox: TemplateChild<SkInstallationListBox>,
         #[template_child]
         listbox: TemplateChild<gtk::ListBox>,
+        #[template_child]
+        runtime_listbox: TemplateChild<gtk::ListBox>,
+        #[template_child]
+        security_listbox: TemplateChild<gtk::ListBox>,
+        #[template_child]
+        search_entry: TemplateChild<gtk::SearchEntry>,
+
+        /// All `SkInstalledPackage` of the selected installation
+        store: OnceCell<gio::ListStore>,
+        filter: OnceCell<gtk::CustomFilter>,
+        sorter: OnceCell<gtk::CustomSorter>,
+
+        /// Shown package kind, `None` shows all kinds
+        kind: Cell<Option<SkPackageKind>>,
+        /// Shown subref kinds, in addition to the main packages
+        subref_kinds: RefCell<Vec<SkPackageSubrefKind>>,
+        sort_kind: Cell<SkInstalledSortKind>,
+
+        packages_handler: RefCell<Option<(SkPackageModel, SignalHandlerId)>>,
+        reload_pending: Cell<bool>,
     }
 
     #[glib::object_subclass]
@@ -50,6 +82,7 @@ mod imp {
 
         fn class_init(klass: &mut Self::Class) {
             Self::bind_template(klass);
+            Self::Type::bind_template_callbacks(klass);
         }
 
         fn instance_init(obj: &subclass::InitializingObject<Self>) {
@@ -74,6 +107,10 @@ mod imp {
             self.parent_constructed();
             let worker = SkApplication::default().worker();
 
+            self.kind.set(Some(SkPackageKind::App));
+            self.setup_model();
+            self.setup_actions();
+
             // Different installation got selected
             self.installation_listbox.connect_local(
                 "notify::selected-installation",
@@ -94,6 +131,12 @@ mod imp {
             let inst = worker.installations().preferred();
             self.installation_listbox.set_selected_installation(&inst);
         }
+
+        fn dispose(&self) {
+            if let Some((packages, handler)) = self.packages_handler.take() {
+                packages.disconnect(handler);
+            }
+        }
     }
 
     impl WidgetImpl for SkInstalledPage {}
@@ -101,36 +144,614 @@ mod imp {
     impl BinImpl for SkInstalledPage {}
 
     impl SkInstalledPage {
-        fn update_selected_installation(&self) {
-            self.listbox.unbind_model();
+        fn setup_model(&self) {
+            let store = gio::ListStore::new::<SkInstalledPackage>();
 
-            let inst = self.installation_listbox.selected_installation().unwrap();
-            self.listbox.bind_model(Some(&inst.packages()), |package| {
-                let package: &SkPackage = package.downcast_ref().unwrap();
+            let filter = gtk::CustomFilter::new(clone!(
+                #[weak(rename_to = this)]
+                self,
+                #[upgrade_or]
+                false,
+                move |obj| this.is_visible(obj.downcast_ref().unwrap())
+            ));
+
+            let sorter = gtk::CustomSorter::new(clone!(
+                #[weak(rename_to = this)]
+                self,
+                #[upgrade_or]
+                gtk::Ordering::Equal,
+                move |a, b| this
+                    .compare(a.downcast_ref().unwrap(), b.downcast_ref().unwrap())
+                    .into()
+            ));
+
+            let model = gtk::FilterListModel::new(Some(store.clone()), Some(filter.clone()));
+            let model = gtk::SortListModel::new(Some(model), Some(sorter.clone()));
+
+            self.listbox.bind_model(
+                Some(&model),
+                clone!(
+                    #[weak(rename_to = this)]
+                    self,
+                    #[upgrade_or_panic]
+                    move |installed| {
+                        let installed: &SkInstalledPackage = installed.downcast_ref().unwrap();
+                        this.package_row(installed).upcast()
+                    }
+                ),
+            );
+
+            let placeholder = adw::StatusPage::builder()
+                .icon_name("system-search-symbolic")
+                .title(i18n("No Packages Found"))
+                .build();
+            placeholder.add_css_class("compact");
+            self.listbox.set_placeholder(Some(&placeholder));
+
+            self.store.set(store).unwrap();
+            self.filter.set(filter).unwrap();
+            self.sorter.set(sorter).unwrap();
+        }
+
+        fn setup_actions(&self) {
+            let actions = gio::SimpleActionGroup::new();
+            self.obj().insert_action_group("installed", Some(&actions));
+
+            let kind = gio::SimpleAction::new_stateful(
+                "kind",
+                Some(glib::VariantTy::STRING),
+                &"app".to_variant(),
+            );
+            kind.connect_activate(clone!(
+                #[weak(rename_to = this)]
+                self,
+                move |action, target| {
+                    let Some(target) = target.and_then(|t| t.str()) else {
+                        return;
+                    };
+
+                    this.kind.set(match target {
+                        "app" => Some(SkPackageKind::App),
+                        "runtime" => Some(SkPackageKind::Runtime),
+                        _ => None,
+                    });
+
+                    action.set_state(&target.to_variant());
+                    this.filter_changed();
+                }
+            ));
+            actions.add_action(&kind);
+
+            for (name, subref_kind) in [
+                ("show-locale", SkPackageSubrefKind::Locale),
+                ("show-debug", SkPackageSubrefKind::Debug),
+                ("show-sources", SkPackageSubrefKind::Sources),
+            ] {
+                let action = gio::SimpleAction::new_stateful(name, None, &false.to_variant());
+                action.connect_activate(clone!(
+                    #[weak(rename_to = this)]
+                    self,
+                    move |action, _| {
+                        let show = !action.state().and_then(|s| s.get::<bool>()).unwrap();
+                        action.set_state(&show.to_variant());
+
+                        let mut subref_kinds = this.subref_kinds.borrow_mut();
+                        subref_kinds.retain(|k| *k != subref_kind);
+                        if show {
+                            subref_kinds.push(subref_kind);
+                        }
+                        drop(subref_kinds);
+
+                        this.filter_changed();
+                    }
+                ));
+                actions.add_action(&action);
+            }
+
+            let sort = gio::SimpleAction::new_stateful(
+                "sort",
+                Some(glib::VariantTy::STRING),
+                &"name".to_variant(),
+            );
+            sort.connect_activate(clone!(
+                #[weak(rename_to = this)]
+                self,
+                move |action, target| {
+                    let Some(target) = target.and_then(|t| t.str()) else {
+                        return;
+                    };
+
+                    this.sort_kind.set(SkInstalledSortKind::from(target));
+                    action.set_state(&target.to_variant());
+                    this.sorter
+                        .get()
+                        .unwrap()
+                        .changed(gtk::SorterChange::Different);
+                }
+            ));
+            actions.add_action(&sort);
+
+            let export_backup = gio::SimpleAction::new("export-backup", None);
+            export_backup.connect_activate(clone!(
+                #[weak(rename_to = this)]
+                self,
+                move |_, _| this.show_backup_selection()
+            ));
+            actions.add_action(&export_backup);
+
+            let import_backup = gio::SimpleAction::new("import-backup", None);
+            import_backup.connect_activate(clone!(
+                #[weak(rename_to = this)]
+                self,
+                move |_, _| this.show_backup_filechooser(None)
+            ));
+            actions.add_action(&import_backup);
+        }
+
+        /// Lets the user choose which apps of the selected installation get
+        /// exported, all apps are selected by default
+        fn show_backup_selection(&self) {
+            let mut packages: Vec<SkPackage> = self
+                .store
+                .get()
+                .unwrap()
+                .iter::<SkInstalledPackage>()
+                .flatten()
+                .map(|installed| installed.package())
+                .filter(|package| package.kind() == SkPackageKind::App)
+                .collect();
+            packages.sort_by_key(|p| p.appstream().name().to_lowercase());
 
-                let uninstall_button = gtk::Button::from_icon_name("user-trash-symbolic");
-                uninstall_button.connect_clicked(clone!(
+            let dialog = adw::AlertDialog::new(
+                Some(&i18n("Export Backup")),
+                Some(&i18n(
+                    "Choose the apps whose data gets included in the backup",
+                )),
+            );
+            dialog.add_response("cancel", &i18n("_Cancel"));
+            dialog.add_response("export", &i18n("_Export"));
+            dialog.set_response_appearance("export", adw::ResponseAppearance::Suggested);
+            dialog.set_response_enabled("export", !packages.is_empty());
+            dialog.set_close_response("cancel");
+
+            let listbox = gtk::ListBox::new();
+            listbox.set_selection_mode(gtk::SelectionMode::None);
+            listbox.add_css_class("boxed-list");
+
+            let selected = Rc::new(Cell::new(packages.len()));
+            let mut selection = Vec::new();
+            for package in packages {
+                let check = gtk::CheckButton::new();
+                check.set_active(true);
+                check.set_valign(gtk::Align::Center);
+                check.connect_toggled(clone!(
                     #[weak]
-                    package,
-                    move |btn| {
-                        btn.set_sensitive(false);
-
-                        let worker = SkApplication::default().worker();
-                        let fut = async move {
-                            let _ = worker.uninstall_flatpak(&package, false).await;
-                        };
-                        crate::main::spawn_future_local(fut);
+                    dialog,
+                    #[strong]
+                    selected,
+                    move |check| {
+                        if check.is_active() {
+                            selected.set(selected.get() + 1);
+                        } else {
+                            selected.set(selected.get() - 1);
+                        }
+                        dialog.set_response_enabled("export", selected.get() > 0);
                     }
                 ));
 
                 let row = adw::ActionRow::builder()
-                    .title(package.name())
-                    .subtitle(package.remote().name())
+                    .title(glib::markup_escape_text(&package.appstream().name()))
+                    .activatable_widget(&check)
+                    .build();
+                row.add_prefix(&check);
+                listbox.append(&row);
+
+                selection.push((package, check));
+            }
+
+            let scrolled_window = gtk::ScrolledWindow::builder()
+                .hscrollbar_policy(gtk::PolicyType::Never)
+                .max_content_height(360)
+                .propagate_natural_height(true)
+                .child(&listbox)
+                .build();
+            dialog.set_extra_child(Some(&scrolled_window));
+
+            dialog.connect_response(
+                Some("export"),
+                clone!(
+                    #[weak(rename_to = this)]
+                    self,
+                    move |_, _| {
+                        let packages = selection
+                            .iter()
+                            .filter(|(_, check)| check.is_active())
+                            .map(|(package, _)| package.clone())
+                            .collect();
+                        this.show_backup_filechooser(Some(packages));
+                    }
+                ),
+            );
+
+            dialog.present(Some(&*self.obj()));
+        }
+
+        /// Exports the passed apps, or imports a backup if `None`
+        fn show_backup_filechooser(&self, export: Option<Vec<SkPackage>>) {
+            let window = self.obj().root().and_downcast::<gtk::Window>();
+
+            let dialog = if export.is_some() {
+                let dialog = gtk::FileChooserNative::new(
+                    Some(&i18n("Export Backup")),
+                    window.as_ref(),
+                    gtk::FileChooserAction::Save,
+                    Some(&i18n("_Export")),
+                    None,
+                );
+
+                let date = glib::DateTime::now_local()
+                    .and_then(|now| now.format("%Y-%m-%d"))
+                    .unwrap_or_default();
+                dialog.set_current_name(&format!("souk-backup-{date}.tar.gz"));
+                dialog
+            } else {
+                gtk::FileChooserNative::new(
+                    Some(&i18n("Import Backup")),
+                    window.as_ref(),
+                    gtk::FileChooserAction::Open,
+                    Some(&i18n("_Import")),
+                    None,
+                )
+            };
+            dialog.set_modal(true);
+
+            let filter = gtk::FileFilter::new();
+            filter.set_name(Some(&i18n("Backup Archives")));
+            filter.add_pattern("*.tar.gz");
+            dialog.add_filter(&filter);
+
+            dialog.connect_response(clone!(
+                #[weak(rename_to = this)]
+                self,
+                move |dialog, resp| {
+                    let Some(file) = dialog.file() else {
+                        return;
+                    };
+                    if resp != gtk::ResponseType::Accept {
+                        return;
+                    }
+
+                    let export = export.clone();
+                    let fut = clone!(
+                        #[weak]
+                        this,
+                        async move {
+                            this.run_backup_task(&file, export).await;
+                        }
+                    );
+                    crate::main::spawn_future_local(fut);
+                }
+            ));
+
+            dialog.show();
+        }
+
+        /// Exports the selected apps, or imports a backup into the selected
+        /// installation
+        async fn run_backup_task(&self, file: &gio::File, export: Option<Vec<SkPackage>>) {
+            let Some(inst) = self.installation_listbox.selected_installation() else {
+                return;
+            };
+            let worker = SkApplication::default().worker();
+
+            let task = match &export {
+                Some(packages) => worker.export_backup(packages, file).await,
+                None => worker.import_backup(file, &inst).await,
+            };
+
+            let result = match task {
+                Ok(task) => task.await_result().await,
+                Err(err) => Err(err),
+            };
+
+            let (heading, body) = match result {
+                Ok(_) if export.is_some() => (i18n("Backup Exported"), None),
+                Ok(_) => (i18n("Backup Restored"), None),
+                Err(err) => (i18n("Operation Failed"), Some(err.message())),
+            };
+
+            let dialog = adw::AlertDialog::new(Some(&heading), body.as_deref());
+            dialog.add_response("close", &i18n("_Close"));
+            dialog.present(Some(&*self.obj()));
+        }
+
+        fn update_selected_installation(&self) {
+            if let Some((packages, handler)) = self.packages_handler.take() {
+                packages.disconnect(handler);
+            }
+
+            let inst = self.installation_listbox.selected_installation().unwrap();
+            let packages = inst.packages();
+
+            // Reload once the installation got refreshed
+            let handler = packages.connect_items_changed(clone!(
+                #[weak(rename_to = this)]
+                self,
+                move |_, _, _, _| this.queue_reload()
+            ));
+            self.packages_handler.replace(Some((packages, handler)));
+
+            self.queue_reload();
+        }
+
+        /// Packages are added / removed one by one when an installation gets
+        /// refreshed, reload only once after all changes
+        fn queue_reload(&self) {
+            if self.reload_pending.replace(true) {
+                return;
+            }
+
+            glib::idle_add_local_once(clone!(
+                #[weak(rename_to = this)]
+                self,
+                move || {
+                    let fut = async move {
+                        this.reload_pending.set(false);
+                        this.reload().await;
+                    };
+                    crate::main::spawn_future_local(fut);
+                }
+            ));
+        }
+
+        async fn reload(&self) {
+            let Some(inst) = self.installation_listbox.selected_installation() else {
+                return;
+            };
+
+            let worker = SkApplication::default().worker();
+            let installed = SkInstalledPackage::load(&inst, &worker).await;
+
+            // Another installation got selected in the meantime
+            if self.installation_listbox.selected_installation().as_ref() != Some(&inst) {
+                return;
+            }
+
+            let store = self.store.get().unwrap();
+            store.splice(0, store.n_items(), &installed);
+
+            self.update_runtimes(&installed);
+            self.update_security(&installed);
+        }
+
+        pub fn filter_changed(&self) {
+            self.filter
+                .get()
+                .unwrap()
+                .changed(gtk::FilterChange::Different);
+        }
+
+        fn is_visible(&self, installed: &SkInstalledPackage) -> bool {
+            let package = installed.package();
+
+            if self.kind.get().is_some_and(|kind| kind != package.kind()) {
+                return false;
+            }
+
+            let subref_kind = package.subref_kind();
+            if subref_kind != SkPackageSubrefKind::None
+                && !self.subref_kinds.borrow().contains(&subref_kind)
+            {
+                return false;
+            }
+
+            installed.matches(&self.search_entry.text())
+        }
+
+        fn compare(&self, a: &SkInstalledPackage, b: &SkInstalledPackage) -> Ordering {
+            let by_name = || {
+                let a = a.appstream().name().to_lowercase();
+                let b = b.appstream().name().to_lowercase();
+                a.cmp(&b)
+            };
+
+            match self.sort_kind.get() {
+                SkInstalledSortKind::Name => by_name(),
+                // Largest / most recent first
+                SkInstalledSortKind::Size => b
+                    .installed_size()
+                    .cmp(&a.installed_size())
+                    .then_with(by_name),
+                SkInstalledSortKind::InstallDate => b
+                    .install_date()
+                    .map(|d| d.to_unix())
+                    .cmp(&a.install_date().map(|d| d.to_unix()))
+                    .then_with(by_name),
+            }
+        }
+
+        fn package_row(&self, installed: &SkInstalledPackage) -> adw::ActionRow {
+            let appstream = installed.appstream();
+            let package = installed.package();
+
+            let icon = gtk::Image::from_paintable(Some(&appstream.icon()));
+            icon.set_pixel_size(32);
+
+            let subtitle = format!(
+                "{} · {}",
+                package.remote().name(),
+                glib::format_size(installed.installed_size())
+            );
+
+            let row = adw::ActionRow::builder()
+                .title(glib::markup_escape_text(&appstream.name()))
+                .subtitle(glib::markup_escape_text(&subtitle))
+                .activatable(package.kind() == SkPackageKind::App)
+                .build();
+            row.add_prefix(&icon);
+
+            row.connect_activated(clone!(
+                #[weak(rename_to = this)]
+                self,
+                #[weak]
+                appstream,
+                move |_| this.show_app(&appstream)
+            ));
+
+            let uninstall_button = gtk::Button::from_icon_name("user-trash-symbolic");
+            uninstall_button.set_valign(gtk::Align::Center);
+            uninstall_button.set_tooltip_text(Some(&i18n("Uninstall")));
+            uninstall_button.add_css_class("flat");
+            uninstall_button.connect_clicked(clone!(
+                #[weak]
+                package,
+                move |btn| {
+                    btn.set_sensitive(false);
+
+                    let worker = SkApplication::default().worker();
+                    let fut = async move {
+                        let _ = worker.uninstall_flatpak(&package, None, false).await;
+                    };
+                    crate::main::spawn_future_local(fut);
+                }
+            ));
+
+            row.add_suffix(&uninstall_button);
+            row
+        }
+
+        /// Shows each runtime together with the apps which depend on it
+        fn update_runtimes(&self, installed: &[SkInstalledPackage]) {
+            while let Some(child) = self.runtime_listbox.first_child() {
+                self.runtime_listbox.remove(&child);
+            }
+
+            let mut runtimes: Vec<&SkInstalledPackage> = installed
+                .iter()
+                .filter(|i| {
+                    let package = i.package();
+                    package.kind() == SkPackageKind::Runtime
+                        && package.subref_kind() == SkPackageSubrefKind::None
+                })
+                .collect();
+            runtimes.sort_by_key(|r| r.appstream().name().to_lowercase());
+
+            for runtime in runtimes {
+                let runtime_ref = runtime.package().info().ref_;
+                let apps: Vec<&SkInstalledPackage> = installed
+                    .iter()
+                    .filter(|i| i.runtime() == runtime_ref)
+                    .collect();
+
+                let subtitle = if apps.is_empty() {
+                    i18n("Not used by any app")
+                } else {
+                    ni18n_f(
+                        "Used by {} app",
+                        "Used by {} apps",
+                        apps.len() as u32,
+                        &[&apps.len().to_string()],
+                    )
+                };
+
+                let row = adw::ExpanderRow::builder()
+                    .title(glib::markup_escape_text(&runtime.appstream().name()))
+                    .subtitle(glib::markup_escape_text(&format!(
+                        "{} · {}",
+                        runtime.package().branch(),
+                        subtitle
+                    )))
+                    .enable_expansion(!apps.is_empty())
                     .build();
 
-                row.add_suffix(&uninstall_button);
-                row.into()
+                for app in apps {
+                    let appstream = app.appstream();
+                    let icon = gtk::Image::from_paintable(Some(&appstream.icon()));
+                    icon.set_pixel_size(32);
+
+                    let app_row = adw::ActionRow::builder()
+                        .title(glib::markup_escape_text(&appstream.name()))
+                        .activatable(true)
+                        .build();
+                    app_row.add_prefix(&icon);
+                    app_row.connect_activated(clone!(
+                        #[weak(rename_to = this)]
+                        self,
+                        #[weak]
+                        appstream,
+                        move |_| this.show_app(&appstream)
+                    ));
+
+                    row.add_row(&app_row);
+                }
+
+                self.runtime_listbox.append(&row);
+            }
+        }
+
+        /// Ranks the apps by the risk score of their permissions, together with
+        /// the permissions which contribute the most
+        fn update_security(&self, installed: &[SkInstalledPackage]) {
+            while let Some(child) = self.security_listbox.first_child() {
+                self.security_listbox.remove(&child);
+            }
+
+            let mut apps: Vec<(&SkInstalledPackage, PermissionRisk)> = installed
+                .iter()
+                .filter(|i| {
+                    let package = i.package();
+                    package.kind() == SkPackageKind::App
+                        && package.subref_kind() == SkPackageSubrefKind::None
+                })
+                .filter_map(|i| {
+                    let metadata = flatpak_utils::installed_metadata(&i.package())?;
+                    let permissions = SkAppPermissions::from_metadata(&metadata);
+                    Some((i, PermissionRisk::new(&permissions)))
+                })
+                .collect();
+            apps.sort_by_cached_key(|(app, risk)| {
+                (Reverse(risk.score), app.appstream().name().to_lowercase())
             });
+
+            for (app, risk) in apps {
+                let appstream = app.appstream();
+                let icon = gtk::Image::from_paintable(Some(&appstream.icon()));
+                icon.set_pixel_size(32);
+
+                let top = risk.top_contributions(3);
+                let subtitle = if top.is_empty() {
+                    i18n("No risky permissions")
+                } else {
+                    top.iter().map(|d| d.title()).collect::<Vec<_>>().join(", ")
+                };
+
+                let row = adw::ExpanderRow::builder()
+                    .title(glib::markup_escape_text(&appstream.name()))
+                    .subtitle(glib::markup_escape_text(&subtitle))
+                    .enable_expansion(!top.is_empty())
+                    .build();
+                row.add_prefix(&icon);
+
+                let score = gtk::Label::new(Some(&risk.score.to_string()));
+                score.set_valign(gtk::Align::Center);
+                score.set_tooltip_text(Some(&i18n("Risk Score")));
+                score.add_css_class("badge");
+                score.add_css_class("numeric");
+                score.add_css_class(ui_utils::level_css_color(risk.level()));
+                row.add_suffix(&score);
+
+                for detail in &top {
+                    row.add_row(&SkContextDetailRow::new(detail, false));
+                }
+
+                self.security_listbox.append(&row);
+            }
+        }
+
+        fn show_app(&self, appstream: &SkPackageAppstream) {
+            if let Some(window) = self.obj().root().and_downcast::<SkApplicationWindow>() {
+                window.show_app(appstream);
+            }
         }
     }
 }
@@ -141,6 +762,14 @@ glib::wrapper! {
         @extends gtk::Widget, adw::Bin;
 }
 
+#[gtk::template_callbacks]
+impl SkInstalledPage {
+    #[template_callback]
+    fn search_changed(&self) {
+        self.imp().filter_changed();
+    }
+}
+
 impl Default for SkInstalledPage {
     fn default() -> Self {
         glib::Object::new()
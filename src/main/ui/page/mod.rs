@@ -14,6 +14,10 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
+mod app_page;
+mod discover_page;
 mod installed_page;
 
+pub use app_page::SkAppPage;
+pub use discover_page::SkDiscoverPage;
 pub use installed_page::SkInstalledPage;
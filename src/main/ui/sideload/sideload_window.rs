@@ -24,10 +24,12 @@ use gtk::{gio, glib, CompositeTemplate};
 
 use super::SkRemoteRow;
 use crate::main::app::SkApplication;
-use crate::main::context::SkContext;
+use crate::main::appstream::SkRelease;
+use crate::main::context::{SkContext, SkContextDetailLevel};
 use crate::main::error::Error;
 use crate::main::flatpak::installation::SkRemote;
 use crate::main::flatpak::package::{SkPackage, SkPackageExt, SkPackageKind};
+use crate::main::flatpak::permissions::describe_policy_violation;
 use crate::main::flatpak::sideload::{SkSideloadKind, SkSideloadable};
 use crate::main::flatpak::SkFlatpakOperationKind;
 use crate::main::i18n::{i18n, i18n_f};
@@ -35,6 +37,7 @@ use crate::main::task::{SkOperation, SkOperationStatus, SkTask};
 use crate::main::ui::badge::SkBadge;
 use crate::main::ui::context::{SkContextBox, SkContextDetailRow};
 use crate::main::ui::installation::SkInstallationListBox;
+use crate::main::ui::utils;
 use crate::main::ui::SkProgressBar;
 use crate::shared::{config, WorkerError};
 
@@ -87,12 +90,20 @@ mod imp {
         #[template_child]
         package_context_listbox: TemplateChild<gtk::ListBox>,
         #[template_child]
+        release_notes_group: TemplateChild<adw::PreferencesGroup>,
+        #[template_child]
+        release_notes_listbox: TemplateChild<gtk::ListBox>,
+        #[template_child]
         warn_group: TemplateChild<adw::PreferencesGroup>,
         #[template_child]
         no_updates_row: TemplateChild<adw::ActionRow>,
         #[template_child]
         replacing_remote_row: TemplateChild<adw::ActionRow>,
         #[template_child]
+        hardware_row: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        policy_row: TemplateChild<adw::ActionRow>,
+        #[template_child]
         remotes_group: TemplateChild<adw::PreferencesGroup>,
         #[template_child]
         remotes_listbox: TemplateChild<gtk::ListBox>,
@@ -266,6 +277,17 @@ mod imp {
                 self.package_repository_badge
                     .set_value(package.remote().name());
 
+                // Release notes
+                let releases = ListStore::new::<SkRelease>();
+                releases.extend_from_slice(&package.release_notes());
+                self.release_notes_group
+                    .set_visible(releases.n_items() != 0);
+                self.release_notes_listbox
+                    .bind_model(Some(&releases), |release| {
+                        let release: &SkRelease = release.downcast_ref().unwrap();
+                        utils::release_row(release).upcast()
+                    });
+
                 // Context information
                 let contexts = ListStore::new::<SkContext>();
 
@@ -278,6 +300,21 @@ mod imp {
                 let permissions_context = dry_run.package().permissions_context();
                 contexts.append(&permissions_context);
 
+                if let Some(changes_context) = dry_run.package().permission_changes_context() {
+                    contexts.append(&changes_context);
+                }
+
+                let license_context = package.license_context();
+                contexts.append(&license_context);
+
+                let hardware_context = a.hardware_context();
+                contexts.append(&hardware_context);
+
+                if is_app {
+                    let content_rating_context = a.content_rating_context();
+                    contexts.append(&content_rating_context);
+                }
+
                 self.package_context_listbox.bind_model(
                     Some(&contexts),
                     clone!(
@@ -319,9 +356,33 @@ mod imp {
                     self.replacing_remote_row.set_visible(false);
                 }
 
+                // Show warning when the app won't work well on this device
+                let hardware = hardware_context.summary();
+                let hardware_issue = matches!(
+                    hardware.level(),
+                    SkContextDetailLevel::Moderate
+                        | SkContextDetailLevel::Warning
+                        | SkContextDetailLevel::Bad
+                );
+                self.hardware_row.set_visible(hardware_issue);
+                self.hardware_row.set_title(&hardware.title());
+                self.hardware_row.set_subtitle(&hardware.description());
+
+                // Show warning when the permission policy discourages the installation
+                let policy_warnings = dry_run
+                    .policy_warnings()
+                    .iter()
+                    .map(describe_policy_violation)
+                    .collect::<Vec<_>>();
+                self.policy_row.set_visible(!policy_warnings.is_empty());
+                self.policy_row.set_subtitle(&policy_warnings.join("\n"));
+
                 // Show / hide warning preferences group
                 self.warn_group.set_visible(
-                    self.no_updates_row.is_visible() || self.replacing_remote_row.is_visible(),
+                    self.no_updates_row.is_visible()
+                        || self.replacing_remote_row.is_visible()
+                        || self.hardware_row.is_visible()
+                        || self.policy_row.is_visible(),
                 );
 
                 // We don't support updating .flatpakrefs through sideloading, since the
@@ -554,6 +615,9 @@ mod imp {
                         WorkerError::DryRunRuntimeNotFound(runtime) => {
                             self.show_missing_runtime_message(&runtime)
                         }
+                        WorkerError::PolicyBlocked(violation) => {
+                            self.show_error_message(&describe_policy_violation(&violation))
+                        }
                         _ => self.show_error_message(&err.to_string()),
                     },
                     _ => self.show_error_message(&err.message()),
@@ -14,8 +14,11 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
+use adw::prelude::*;
 use gtk::glib;
-use gtk::prelude::*;
+
+use crate::main::appstream::SkRelease;
+use crate::main::context::SkContextDetailLevel;
 
 pub fn size_to_markup(size: &str) -> String {
     if let Ok(size) = size.parse::<u64>() {
@@ -37,6 +40,17 @@ pub fn size_to_markup(size: &str) -> String {
     }
 }
 
+pub fn level_css_color(level: SkContextDetailLevel) -> &'static str {
+    match level {
+        SkContextDetailLevel::Neutral => "color-neutral",
+        SkContextDetailLevel::Good => "color-green",
+        SkContextDetailLevel::Minor => "color-blue",
+        SkContextDetailLevel::Moderate => "color-orange",
+        SkContextDetailLevel::Warning => "color-yellow",
+        SkContextDetailLevel::Bad => "color-red",
+    }
+}
+
 pub fn remove_css_colors<T: IsA<gtk::Widget>>(widget: &T) {
     let css_classes = vec![
         "color-neutral",
@@ -51,3 +65,35 @@ pub fn remove_css_colors<T: IsA<gtk::Widget>>(widget: &T) {
         widget.remove_css_class(class);
     }
 }
+
+/// Creates a row which displays a release, with the description as expandable
+/// content
+pub fn release_row(release: &SkRelease) -> adw::ExpanderRow {
+    let row = adw::ExpanderRow::new();
+    row.set_title(&glib::markup_escape_text(&release.title()));
+
+    let mut subtitle = vec![release.kind().title()];
+    let urgency = release.urgency().title();
+    if !urgency.is_empty() {
+        subtitle.push(urgency);
+    }
+    row.set_subtitle(&glib::markup_escape_text(&subtitle.join(" · ")));
+
+    if release.description().is_empty() {
+        row.set_enable_expansion(false);
+    } else {
+        let label = gtk::Label::builder()
+            .label(release.description())
+            .wrap(true)
+            .xalign(0.0)
+            .selectable(true)
+            .margin_top(12)
+            .margin_bottom(12)
+            .margin_start(12)
+            .margin_end(12)
+            .build();
+        row.add_row(&label);
+    }
+
+    row
+}
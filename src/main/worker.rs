@@ -24,16 +24,23 @@ use gtk::prelude::*;
 use gtk::subclass::prelude::*;
 use gtk::{gio, glib};
 
+use crate::main::appstream::SkPackageAppstream;
 use crate::main::dbus_proxy::WorkerProxy;
 use crate::main::error::Error;
+use crate::main::flatpak::addon::SkAddon;
 use crate::main::flatpak::installation::{SkInstallation, SkInstallationModel, SkRemote};
-use crate::main::flatpak::package::SkPackage;
+use crate::main::flatpak::package::{SkPackage, SkPackageExt};
+use crate::main::flatpak::permissions::PermissionOverrides;
 use crate::main::flatpak::sideload::{SkSideloadKind, SkSideloadable};
 use crate::main::flatpak::utils;
 use crate::main::task::{SkTask, SkTaskModel};
 use crate::shared::flatpak::info::RemoteInfo;
+use crate::shared::flatpak::DiskUsage;
 use crate::shared::task::response::{TaskResponse, TaskResponseKind};
-use crate::shared::task::{AppstreamTask, AppstreamTaskKind, FlatpakTask};
+use crate::shared::task::{
+    AppstreamLookup, AppstreamTask, AppstreamTaskKind, BackupTask, BackupTaskKind, DataRemoval,
+    DataTask, DataTaskKind, DownloadTask, FlatpakTask, OverrideTask, OverrideTaskKind,
+};
 
 /// Number of tasks that are completed and still remain in log
 const KEEP_COMPLETED_TASKS: u32 = 5;
@@ -118,6 +125,8 @@ mod imp {
         pub async fn receive_task_response(&self, sender: Sender<bool>) {
             let mut response = self.proxy.receive_task_response().await.unwrap();
             sender.send(true).await.unwrap();
+            // Close the channel, so that any further `wait_ready` calls return immediately
+            drop(sender);
 
             while let Some(response) = response.next().await {
                 let response_json = response.args().unwrap().task_response_json;
@@ -158,15 +167,18 @@ glib::wrapper! {
 }
 
 impl SkWorker {
-    /// Install new Flatpak
+    /// Install new Flatpak, optionally together with addons
     pub async fn install_flatpak(
         &self,
         package: &SkPackage,
+        addons: &[SkAddon],
         uninstall_before_install: bool,
         dry_run: bool,
     ) -> Result<SkTask, Error> {
         let info = package.info();
-        let task_data = FlatpakTask::new_install(&info, uninstall_before_install, dry_run);
+        let addons = addons.iter().map(|a| a.info().ref_).collect();
+        let task_data =
+            FlatpakTask::new_install(&info, uninstall_before_install, dry_run).with_addons(addons);
 
         let task = SkTask::new(&task_data.into());
         self.imp().run_task(&task).await?;
@@ -222,13 +234,42 @@ impl SkWorker {
         Ok(task)
     }
 
-    /// Uninstall Flatpak
+    /// Update an installed Flatpak to the latest available commit
+    pub async fn update_flatpak(&self, package: &SkPackage) -> Result<SkTask, Error> {
+        let task_data = FlatpakTask::new_update(&package.info());
+
+        let task = SkTask::new(&task_data.into());
+        self.imp().run_task(&task).await?;
+
+        Ok(task)
+    }
+
+    /// Uninstall Flatpak, and optionally remove the sandbox data of the app
     pub async fn uninstall_flatpak(
         &self,
         package: &SkPackage,
+        remove_data: Option<DataRemoval>,
         dry_run: bool,
     ) -> Result<SkTask, Error> {
-        let task_data = FlatpakTask::new_uninstall(&package.info(), dry_run);
+        let task_data = FlatpakTask::new_uninstall(&package.info(), remove_data, dry_run);
+
+        let task = SkTask::new(&task_data.into());
+        self.imp().run_task(&task).await?;
+
+        Ok(task)
+    }
+
+    /// Installs and uninstalls addons of an installed Flatpak in a single
+    /// transaction
+    pub async fn change_flatpak_addons(
+        &self,
+        package: &SkPackage,
+        install: &[SkAddon],
+        uninstall: &[SkAddon],
+    ) -> Result<SkTask, Error> {
+        let install = install.iter().map(|a| a.info().ref_).collect();
+        let uninstall = uninstall.iter().map(|a| a.info().ref_).collect();
+        let task_data = FlatpakTask::new_change_addons(&package.info(), install, uninstall);
 
         let task = SkTask::new(&task_data.into());
         self.imp().run_task(&task).await?;
@@ -257,6 +298,200 @@ impl SkWorker {
         Ok(task)
     }
 
+    /// Looks up appstream components by id, provided id or mimetype. Returns
+    /// an entry for each remote which provides a matching component.
+    pub async fn lookup_appstream(
+        &self,
+        lookup: AppstreamLookup,
+    ) -> Result<Vec<SkPackageAppstream>, Error> {
+        let task_data = AppstreamTask::new(AppstreamTaskKind::Lookup(lookup));
+
+        let task = SkTask::new(&task_data.into());
+        self.imp().run_task(&task).await?;
+        task.await_result().await?;
+
+        let components = task.result_appstream_lookup().unwrap_or_default();
+        Ok(components
+            .iter()
+            .map(SkPackageAppstream::from_component_info)
+            .collect())
+    }
+
+    /// Discovers the addons which are available for an app, from appstream
+    /// data and the extension points of the app
+    pub async fn addons(&self, package: &SkPackage) -> Result<Vec<SkAddon>, Error> {
+        let task_data = AppstreamTask::new(AppstreamTaskKind::Addons(package.info()));
+
+        let task = SkTask::new(&task_data.into());
+        self.imp().run_task(&task).await?;
+        task.await_result().await?;
+
+        let addons = task.result_addons().unwrap_or_default();
+        Ok(addons.iter().map(SkAddon::new).collect())
+    }
+
+    /// Calculates the disk usage of an installed package, including its
+    /// exclusively used runtimes / extensions and its sandbox data
+    pub async fn disk_usage(&self, package: &SkPackage) -> Result<DiskUsage, Error> {
+        let task_data = DataTask::new(DataTaskKind::DiskUsage, &package.info(), false);
+
+        let task = SkTask::new(&task_data.into());
+        self.imp().run_task(&task).await?;
+        task.await_result().await?;
+
+        Ok(task.result_disk_usage().unwrap_or_default())
+    }
+
+    /// Deletes the cache of an installed app. A dry run returns the size
+    /// which would be freed.
+    pub async fn clear_app_cache(
+        &self,
+        package: &SkPackage,
+        dry_run: bool,
+    ) -> Result<SkTask, Error> {
+        let task_data = DataTask::new(DataTaskKind::ClearCache, &package.info(), dry_run);
+
+        let task = SkTask::new(&task_data.into());
+        self.imp().run_task(&task).await?;
+
+        Ok(task)
+    }
+
+    /// Resets the data of an installed app, by moving its data directory into
+    /// the trash or into a backup. A dry run returns the size which would be
+    /// freed.
+    pub async fn reset_app_data(
+        &self,
+        package: &SkPackage,
+        removal: DataRemoval,
+        dry_run: bool,
+    ) -> Result<SkTask, Error> {
+        let kind = DataTaskKind::ResetData(removal);
+        let task_data = DataTask::new(kind, &package.info(), dry_run);
+
+        let task = SkTask::new(&task_data.into());
+        self.imp().run_task(&task).await?;
+
+        Ok(task)
+    }
+
+    /// Exports the data of the installed packages, together with their ref and
+    /// remote, into a single archive
+    pub async fn export_backup(
+        &self,
+        packages: &[SkPackage],
+        file: &File,
+    ) -> Result<SkTask, Error> {
+        let kind = BackupTaskKind::Export {
+            packages: packages.iter().map(|p| p.info()).collect(),
+            path: file.path().unwrap().to_string_lossy().to_string(),
+        };
+        let task_data = BackupTask::new(kind);
+
+        let task = SkTask::new(&task_data.into());
+        self.imp().run_task(&task).await?;
+
+        Ok(task)
+    }
+
+    /// Installs the missing apps of a backup archive into the installation,
+    /// and restores their data
+    pub async fn import_backup(
+        &self,
+        file: &File,
+        installation: &SkInstallation,
+    ) -> Result<SkTask, Error> {
+        let kind = BackupTaskKind::Import {
+            path: file.path().unwrap().to_string_lossy().to_string(),
+            installation: installation.info(),
+        };
+        let task_data = BackupTask::new(kind);
+
+        let task = SkTask::new(&task_data.into());
+        self.imp().run_task(&task).await?;
+
+        Ok(task)
+    }
+
+    /// Loads the permission overrides of an app, or the global overrides if
+    /// `app_id` is `None`
+    pub async fn load_overrides(
+        &self,
+        installation: &SkInstallation,
+        app_id: Option<&str>,
+    ) -> Result<PermissionOverrides, Error> {
+        let task_data = OverrideTask::new(OverrideTaskKind::Load, &installation.info(), app_id);
+
+        let task = SkTask::new(&task_data.into());
+        self.imp().run_task(&task).await?;
+        task.await_result().await?;
+
+        let data = task.result_overrides().unwrap_or_default();
+        Ok(PermissionOverrides::from_data(&data))
+    }
+
+    /// Replaces the permission overrides of an app, or the global overrides
+    /// if `app_id` is `None`. Changing the overrides of a system installation
+    /// requires administrator privileges.
+    pub async fn save_overrides(
+        &self,
+        installation: &SkInstallation,
+        app_id: Option<&str>,
+        overrides: &PermissionOverrides,
+    ) -> Result<SkTask, Error> {
+        let kind = OverrideTaskKind::Save(overrides.to_data());
+        let task_data = OverrideTask::new(kind, &installation.info(), app_id);
+
+        let task = SkTask::new(&task_data.into());
+        self.imp().run_task(&task).await?;
+
+        Ok(task)
+    }
+
+    /// Removes all permission overrides of an app, or the global overrides
+    /// if `app_id` is `None`. Changing the overrides of a system installation
+    /// requires administrator privileges.
+    pub async fn reset_overrides(
+        &self,
+        installation: &SkInstallation,
+        app_id: Option<&str>,
+    ) -> Result<SkTask, Error> {
+        let task_data = OverrideTask::new(OverrideTaskKind::Reset, &installation.info(), app_id);
+
+        let task = SkTask::new(&task_data.into());
+        self.imp().run_task(&task).await?;
+
+        Ok(task)
+    }
+
+    /// Downloads a sideloadable Flatpak file (eg. a flatpakref from a website)
+    /// into the cache directory
+    pub async fn download_sideloadable(&self, url: &str) -> Result<SkTask, Error> {
+        let kind = SkSideloadKind::determine_kind(&File::for_uri(url));
+        if kind == SkSideloadKind::None {
+            return Err(Error::UnsupportedSideloadType);
+        }
+
+        self.download(url, kind.max_download_size(), &kind.content_types())
+            .await
+    }
+
+    /// Downloads a file into the cache directory. Only files which match the
+    /// content types (any if empty) and the maximum size get accepted.
+    pub async fn download(
+        &self,
+        url: &str,
+        max_size: u64,
+        content_types: &[&str],
+    ) -> Result<SkTask, Error> {
+        let task_data = DownloadTask::new(url, max_size, content_types);
+
+        let task = SkTask::new(&task_data.into());
+        self.imp().run_task(&task).await?;
+
+        Ok(task)
+    }
+
     /// Cancel a worker task
     pub async fn cancel_task(&self, task: &SkTask) -> Result<(), Error> {
         let task_json = serde_json::to_string(&task.data())?;
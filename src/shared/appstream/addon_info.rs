@@ -0,0 +1,42 @@
+// Souk - addon_info.rs
+// Copyright (C) 2024  Felix Häcker <haeckerfelix@gnome.org>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use derivative::Derivative;
+use gtk::glib;
+use serde::{Deserialize, Serialize};
+
+use crate::shared::appstream::ComponentRelations;
+use crate::shared::flatpak::info::PackageInfo;
+
+/// An addon of an app, which is either an appstream `addon` component, or a
+/// ref which matches one of the `Extension` points of the app metadata
+#[derive(Default, Derivative, Deserialize, Serialize, Clone, PartialEq, Eq, Hash, glib::Boxed)]
+#[boxed_type(name = "AddonInfo")]
+#[derivative(Debug)]
+pub struct AddonInfo {
+    /// The Flatpak package (ref + remote) of the addon
+    pub package: PackageInfo,
+    /// The extension point of the app, eg. `org.gimp.GIMP.Plugin`
+    pub extension_point: Option<String>,
+    pub is_installed: bool,
+
+    #[derivative(Debug = "ignore")]
+    pub icon: Option<Vec<u8>>,
+    /// Json serialized appstream component, if the addon has one
+    #[derivative(Debug = "ignore")]
+    pub appstream_component: Option<String>,
+    pub relations: ComponentRelations,
+}
@@ -0,0 +1,36 @@
+// Souk - component_info.rs
+// Copyright (C) 2024  Felix Häcker <haeckerfelix@gnome.org>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use derivative::Derivative;
+use serde::{Deserialize, Serialize};
+
+use crate::shared::appstream::ComponentRelations;
+use crate::shared::flatpak::info::PackageInfo;
+
+/// An appstream component which is available from a Flatpak remote
+#[derive(Default, Derivative, Deserialize, Serialize, Clone, PartialEq, Eq, Hash)]
+#[derivative(Debug)]
+pub struct ComponentInfo {
+    /// The Flatpak package (ref + remote) that provides the component
+    pub package: PackageInfo,
+
+    #[derivative(Debug = "ignore")]
+    pub icon: Option<Vec<u8>>,
+    /// Json serialized appstream component
+    #[derivative(Debug = "ignore")]
+    pub appstream_component: String,
+    pub relations: ComponentRelations,
+}
@@ -0,0 +1,185 @@
+// Souk - license.rs
+// Copyright (C) 2024  Felix Häcker <haeckerfelix@gnome.org>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Classification of SPDX license expressions, which is used for the license
+//! context of the UI and the license rules of the
+//! [PermissionPolicy](crate::shared::flatpak::PermissionPolicy).
+
+use super::SkLicenseKind;
+
+/// SPDX identifiers of common free software licenses, without "-only",
+/// "-or-later" or "+" suffix
+const FREE_LICENSES: &[&str] = &[
+    "0BSD",
+    "AFL-3.0",
+    "AGPL-3.0",
+    "Apache-2.0",
+    "Artistic-2.0",
+    "BlueOak-1.0.0",
+    "BSD-2-Clause",
+    "BSD-3-Clause",
+    "BSL-1.0",
+    "CC-BY-3.0",
+    "CC-BY-4.0",
+    "CC-BY-SA-3.0",
+    "CC-BY-SA-4.0",
+    "CC0-1.0",
+    "CECILL-2.1",
+    "curl",
+    "ECL-2.0",
+    "EPL-1.0",
+    "EPL-2.0",
+    "EUPL-1.1",
+    "EUPL-1.2",
+    "FSFAP",
+    "FTL",
+    "GFDL-1.3",
+    "GPL-2.0",
+    "GPL-3.0",
+    "IJG",
+    "ISC",
+    "LGPL-2.0",
+    "LGPL-2.1",
+    "LGPL-3.0",
+    "libpng-2.0",
+    "MIT",
+    "MPL-1.1",
+    "MPL-2.0",
+    "MS-PL",
+    "OFL-1.1",
+    "OpenSSL",
+    "PSF-2.0",
+    "Python-2.0",
+    "Unlicense",
+    "Vim",
+    "WTFPL",
+    "X11",
+    "Zlib",
+];
+
+/// Classifies a SPDX license expression, eg. "GPL-3.0-or-later AND CC0-1.0"
+pub fn license_kind(expression: &str) -> SkLicenseKind {
+    let tokens = tokenize(expression);
+    let mut pos = 0;
+    let kind = parse_or(&tokens, &mut pos);
+
+    // Trailing garbage means that we didn't understand the expression
+    if pos != tokens.len() {
+        return SkLicenseKind::Unknown;
+    }
+
+    kind
+}
+
+/// Returns all license identifiers of a SPDX license expression
+pub fn license_ids(expression: &str) -> Vec<String> {
+    tokenize(expression)
+        .into_iter()
+        .filter(|t| !matches!(t.as_str(), "(" | ")" | "AND" | "OR" | "WITH"))
+        .collect()
+}
+
+fn tokenize(expression: &str) -> Vec<String> {
+    expression
+        .replace('(', " ( ")
+        .replace(')', " ) ")
+        .split_whitespace()
+        .map(|t| match t.to_ascii_uppercase().as_str() {
+            "AND" | "OR" | "WITH" => t.to_ascii_uppercase(),
+            _ => t.to_string(),
+        })
+        .collect()
+}
+
+fn parse_or(tokens: &[String], pos: &mut usize) -> SkLicenseKind {
+    let mut kinds = vec![parse_and(tokens, pos)];
+    while tokens.get(*pos).map(String::as_str) == Some("OR") {
+        *pos += 1;
+        kinds.push(parse_and(tokens, pos));
+    }
+
+    // One free alternative is enough
+    if kinds.contains(&SkLicenseKind::Free) {
+        SkLicenseKind::Free
+    } else if kinds.contains(&SkLicenseKind::Unknown) {
+        SkLicenseKind::Unknown
+    } else {
+        SkLicenseKind::Proprietary
+    }
+}
+
+fn parse_and(tokens: &[String], pos: &mut usize) -> SkLicenseKind {
+    let mut kinds = vec![parse_term(tokens, pos)];
+    while tokens.get(*pos).map(String::as_str) == Some("AND") {
+        *pos += 1;
+        kinds.push(parse_term(tokens, pos));
+    }
+
+    // All parts have to be free
+    if kinds.contains(&SkLicenseKind::Proprietary) {
+        SkLicenseKind::Proprietary
+    } else if kinds.contains(&SkLicenseKind::Unknown) {
+        SkLicenseKind::Unknown
+    } else {
+        SkLicenseKind::Free
+    }
+}
+
+fn parse_term(tokens: &[String], pos: &mut usize) -> SkLicenseKind {
+    let Some(token) = tokens.get(*pos) else {
+        return SkLicenseKind::Unknown;
+    };
+    *pos += 1;
+
+    let kind = if token == "(" {
+        let kind = parse_or(tokens, pos);
+        if tokens.get(*pos).map(String::as_str) == Some(")") {
+            *pos += 1;
+        }
+        kind
+    } else {
+        license_id_kind(token)
+    };
+
+    // License exceptions only grant additional permissions
+    if tokens.get(*pos).map(String::as_str) == Some("WITH") {
+        *pos += 2;
+    }
+
+    kind
+}
+
+fn license_id_kind(id: &str) -> SkLicenseKind {
+    if id.starts_with("LicenseRef-proprietary") {
+        return SkLicenseKind::Proprietary;
+    }
+
+    if id.starts_with("LicenseRef-free") {
+        return SkLicenseKind::Free;
+    }
+
+    let id = id.trim_end_matches('+');
+    let id = id
+        .strip_suffix("-only")
+        .or_else(|| id.strip_suffix("-or-later"))
+        .unwrap_or(id);
+
+    if FREE_LICENSES.iter().any(|l| l.eq_ignore_ascii_case(id)) {
+        SkLicenseKind::Free
+    } else {
+        SkLicenseKind::Unknown
+    }
+}
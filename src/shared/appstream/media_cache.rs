@@ -0,0 +1,203 @@
+// Souk - media_cache.rs
+// Copyright (C) 2024  Felix Häcker <haeckerfelix@gnome.org>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::collections::hash_map::DefaultHasher;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use futures_util::AsyncReadExt;
+use isahc::http::StatusCode;
+use uuid::Uuid;
+
+use crate::shared::{http, path, WorkerError};
+
+/// Extension of files which are still getting written
+const PART_EXTENSION: &str = ".part";
+
+/// A size limited cache for remote appstream media, like icons and
+/// screenshots. If the cache exceeds its size, the least recently used files
+/// get evicted.
+#[derive(Debug, Clone)]
+pub struct MediaCache {
+    dir: PathBuf,
+    /// Maximum size of the whole cache in bytes
+    max_size: u64,
+    /// Maximum size of a single file in bytes
+    max_file_size: u64,
+}
+
+impl MediaCache {
+    pub fn icons() -> Self {
+        Self {
+            dir: path::ICON_CACHE.clone(),
+            max_size: 50 * 1024 * 1024,
+            max_file_size: 1024 * 1024,
+        }
+    }
+
+    pub fn screenshots() -> Self {
+        Self {
+            dir: path::SCREENSHOT_CACHE.clone(),
+            max_size: 250 * 1024 * 1024,
+            max_file_size: 10 * 1024 * 1024,
+        }
+    }
+
+    /// Returns the path of the cached file, or downloads the file if it isn't
+    /// cached yet. Blocks till the file is available.
+    pub fn fetch(&self, url: &str) -> Result<PathBuf, WorkerError> {
+        if let Some(path) = self.lookup(url) {
+            return Ok(path);
+        }
+
+        let response = isahc::send(http::get_request(url)?)?;
+        self.check_response(response.status(), response.body().len())?;
+
+        // The announced size is only a hint, read at most one byte more than
+        // allowed to detect larger files
+        let mut bytes = Vec::new();
+        response
+            .into_body()
+            .take(self.max_file_size + 1)
+            .read_to_end(&mut bytes)?;
+        self.store(url, &bytes)
+    }
+
+    /// Returns the path of the cached file, or downloads the file if it isn't
+    /// cached yet.
+    pub async fn fetch_async(&self, url: &str) -> Result<PathBuf, WorkerError> {
+        if let Some(path) = self.lookup(url) {
+            return Ok(path);
+        }
+
+        let response = isahc::send_async(http::get_request(url)?).await?;
+        self.check_response(response.status(), response.body().len())?;
+
+        let mut bytes = Vec::new();
+        response
+            .into_body()
+            .take(self.max_file_size + 1)
+            .read_to_end(&mut bytes)
+            .await?;
+        self.store(url, &bytes)
+    }
+
+    /// Returns the path of the cached file, if available
+    pub fn lookup(&self, url: &str) -> Option<PathBuf> {
+        let path = self.file_path(url);
+        let file = fs::File::options().write(true).open(&path).ok()?;
+
+        // Update modification time, so that the file counts as recently used
+        let _ = file.set_modified(SystemTime::now());
+        Some(path)
+    }
+
+    fn store(&self, url: &str, bytes: &[u8]) -> Result<PathBuf, WorkerError> {
+        if bytes.len() as u64 > self.max_file_size {
+            return Err(self.too_large_error());
+        }
+
+        fs::create_dir_all(&self.dir)?;
+        let path = self.file_path(url);
+
+        // Written to a temporary file first, so that a concurrent lookup never
+        // returns a partially written file
+        let part_path = path.with_extension(format!("{}{PART_EXTENSION}", Uuid::new_v4()));
+        if let Err(err) = fs::write(&part_path, bytes).and_then(|_| fs::rename(&part_path, &path)) {
+            let _ = fs::remove_file(&part_path);
+            return Err(err.into());
+        }
+
+        self.evict(&path);
+        Ok(path)
+    }
+
+    fn check_response(&self, status: StatusCode, len: Option<u64>) -> Result<(), WorkerError> {
+        if !status.is_success() {
+            let msg = format!("Server responded with {status}");
+            return Err(WorkerError::Download(msg));
+        }
+
+        if len.is_some_and(|len| len > self.max_file_size) {
+            return Err(self.too_large_error());
+        }
+
+        Ok(())
+    }
+
+    /// Removes the least recently used files till the cache doesn't exceed
+    /// its maximum size anymore. The `keep` file never gets removed.
+    fn evict(&self, keep: &Path) {
+        let Ok(entries) = fs::read_dir(&self.dir) else {
+            return;
+        };
+
+        let mut files: Vec<(PathBuf, u64, SystemTime)> = entries
+            .flatten()
+            .filter_map(|entry| {
+                if entry
+                    .file_name()
+                    .to_string_lossy()
+                    .ends_with(PART_EXTENSION)
+                {
+                    return None;
+                }
+
+                let metadata = entry.metadata().ok()?;
+                let modified = metadata.modified().ok()?;
+                metadata
+                    .is_file()
+                    .then(|| (entry.path(), metadata.len(), modified))
+            })
+            .collect();
+
+        let mut size: u64 = files.iter().map(|(_, len, _)| len).sum();
+        files.sort_by_key(|(_, _, modified)| *modified);
+
+        for (path, len, _) in files {
+            if size <= self.max_size {
+                break;
+            }
+
+            if path != keep && fs::remove_file(&path).is_ok() {
+                debug!("Evicted {path:?} from media cache");
+                size -= len;
+            }
+        }
+    }
+
+    /// Cached files are named by the hash of their url
+    fn file_path(&self, url: &str) -> PathBuf {
+        let mut hasher = DefaultHasher::new();
+        url.hash(&mut hasher);
+
+        let extension = Path::new(url)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .filter(|ext| ext.len() <= 4 && ext.chars().all(char::is_alphanumeric))
+            .unwrap_or("bin");
+
+        self.dir.join(format!("{:x}.{extension}", hasher.finish()))
+    }
+
+    fn too_large_error(&self) -> WorkerError {
+        let size = gtk::glib::format_size(self.max_file_size);
+        WorkerError::Download(format!("File is larger than {size}"))
+    }
+}
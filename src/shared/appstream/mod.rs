@@ -14,6 +14,19 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
+mod addon_info;
+mod component_info;
+pub mod license;
+mod license_kind;
+mod media_cache;
 mod operation_kind;
+mod relations;
+mod remote_sync_result;
 
+pub use addon_info::AddonInfo;
+pub use component_info::ComponentInfo;
+pub use license_kind::SkLicenseKind;
+pub use media_cache::MediaCache;
 pub use operation_kind::AppstreamOperationKind;
+pub use relations::{ComponentRelations, DisplaySide, RelationCompare, RelationItem};
+pub use remote_sync_result::{RemoteSyncResult, RemoteSyncStatus};
@@ -0,0 +1,161 @@
+// Souk - relations.rs
+// Copyright (C) 2024  Felix Häcker <haeckerfelix@gnome.org>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use appstream::xmltree::{Element, XMLNode};
+use gtk::glib;
+use serde::{Deserialize, Serialize};
+
+/// Hardware relations of an appstream component, see
+/// <https://www.freedesktop.org/software/appstream/docs/chap-Metadata.html#tag-relations>
+#[derive(Default, Deserialize, Serialize, Eq, PartialEq, Debug, Clone, Hash, glib::Boxed)]
+#[boxed_type(name = "ComponentRelations")]
+pub struct ComponentRelations {
+    pub requires: Vec<RelationItem>,
+    pub recommends: Vec<RelationItem>,
+    pub supports: Vec<RelationItem>,
+}
+
+#[derive(Deserialize, Serialize, Eq, PartialEq, Debug, Clone, Hash)]
+pub enum RelationItem {
+    /// Display length in logical pixels
+    DisplayLength {
+        compare: RelationCompare,
+        side: DisplaySide,
+        length: u32,
+    },
+    /// Input control, eg. "keyboard" or "touch"
+    Control(String),
+    /// Physical memory in MiB
+    Memory(u64),
+    /// Internet connection, eg. "always" or "first-run"
+    Internet(String),
+}
+
+#[derive(Default, Deserialize, Serialize, Eq, PartialEq, Debug, Copy, Clone, Hash)]
+pub enum RelationCompare {
+    Eq,
+    Ne,
+    Lt,
+    Gt,
+    Le,
+    #[default]
+    Ge,
+}
+
+#[derive(Default, Deserialize, Serialize, Eq, PartialEq, Debug, Copy, Clone, Hash)]
+pub enum DisplaySide {
+    #[default]
+    Shortest,
+    Longest,
+}
+
+impl ComponentRelations {
+    /// Parses the relations of a `<component>` element
+    pub fn from_element(component: &Element) -> Self {
+        Self {
+            requires: Self::parse_items(component.get_child("requires")),
+            recommends: Self::parse_items(component.get_child("recommends")),
+            supports: Self::parse_items(component.get_child("supports")),
+        }
+    }
+
+    fn parse_items(element: Option<&Element>) -> Vec<RelationItem> {
+        let Some(element) = element else {
+            return Vec::new();
+        };
+
+        element
+            .children
+            .iter()
+            .filter_map(|child| match child {
+                XMLNode::Element(e) => RelationItem::from_element(e),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+impl RelationItem {
+    fn from_element(element: &Element) -> Option<Self> {
+        let text = element.get_text()?.trim().to_string();
+        let attr = |name: &str| element.attributes.get(name).map(String::as_str);
+
+        let item = match element.name.as_str() {
+            "display_length" => {
+                let compare = attr("compare").and_then(RelationCompare::parse);
+                let side = match attr("side") {
+                    Some("longest") => DisplaySide::Longest,
+                    _ => DisplaySide::Shortest,
+                };
+
+                Self::DisplayLength {
+                    compare: compare.unwrap_or_default(),
+                    side,
+                    length: Self::parse_display_length(&text)?,
+                }
+            }
+            "control" => Self::Control(text),
+            "memory" => Self::Memory(text.parse().ok()?),
+            "internet" => Self::Internet(text),
+            _ => return None,
+        };
+
+        Some(item)
+    }
+
+    /// Display lengths can be either specified in logical pixels, or by using
+    /// one of the predefined size names
+    fn parse_display_length(value: &str) -> Option<u32> {
+        let length = match value {
+            "xsmall" => 360,
+            "small" => 420,
+            "medium" => 760,
+            "large" => 900,
+            "xlarge" => 1200,
+            value => value.parse().ok()?,
+        };
+
+        Some(length)
+    }
+}
+
+impl RelationCompare {
+    fn parse(value: &str) -> Option<Self> {
+        let compare = match value {
+            "eq" => Self::Eq,
+            "ne" => Self::Ne,
+            "lt" => Self::Lt,
+            "gt" => Self::Gt,
+            "le" => Self::Le,
+            "ge" => Self::Ge,
+            _ => return None,
+        };
+
+        Some(compare)
+    }
+
+    /// Checks whether `value` satisfies the relation to `reference`
+    pub fn matches(&self, value: u32, reference: u32) -> bool {
+        match self {
+            Self::Eq => value == reference,
+            Self::Ne => value != reference,
+            Self::Lt => value < reference,
+            Self::Gt => value > reference,
+            Self::Le => value <= reference,
+            Self::Ge => value >= reference,
+        }
+    }
+}
@@ -0,0 +1,78 @@
+// Souk - remote_sync_result.rs
+// Copyright (C) 2024  Felix Häcker <haeckerfelix@gnome.org>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::fs;
+
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+
+use crate::shared::flatpak::info::RemoteInfo;
+use crate::shared::path;
+
+#[derive(Default, Deserialize, Serialize, Eq, PartialEq, Debug, Clone, Hash)]
+pub enum RemoteSyncStatus {
+    /// Appstream data got successfully downloaded and imported
+    Success,
+    /// Remote doesn't provide any appstream data (e.g. disabled, or no url)
+    Skipped,
+    /// Appstream data couldn't get retrieved. Contains the error message.
+    Failed(String),
+    #[default]
+    None,
+}
+
+/// The outcome of syncing the appstream data of a single Flatpak remote
+#[derive(Default, Deserialize, Serialize, Eq, PartialEq, Debug, Clone, Hash)]
+pub struct RemoteSyncResult {
+    pub remote: RemoteInfo,
+    pub status: RemoteSyncStatus,
+    /// Unix timestamp of the last successful sync. Gets preserved across
+    /// failed syncs, so it's possible to tell how old the cached data is.
+    pub last_success: Option<i64>,
+}
+
+impl RemoteSyncResult {
+    pub fn new(remote: RemoteInfo, status: RemoteSyncStatus, last_success: Option<i64>) -> Self {
+        Self {
+            remote,
+            status,
+            last_success,
+        }
+    }
+
+    pub fn is_failed(&self) -> bool {
+        matches!(self.status, RemoteSyncStatus::Failed(_))
+    }
+
+    /// Loads the stored sync results of the last appstream update, indexed by
+    /// remote hash
+    pub fn load() -> IndexMap<String, Self> {
+        let Ok(json) = fs::read_to_string(path::APPSTREAM_SYNC_STATUS.as_path()) else {
+            return IndexMap::new();
+        };
+
+        serde_json::from_str(&json).unwrap_or_else(|err| {
+            warn!("Unable to parse appstream sync status: {}", err.to_string());
+            IndexMap::new()
+        })
+    }
+
+    /// Stores the sync results, indexed by remote hash
+    pub fn store(results: &IndexMap<String, Self>) -> std::io::Result<()> {
+        let json = serde_json::to_string(results)?;
+        fs::write(path::APPSTREAM_SYNC_STATUS.as_path(), json)
+    }
+}
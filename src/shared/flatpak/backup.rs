@@ -0,0 +1,53 @@
+// Souk - backup.rs
+// Copyright (C) 2024  Felix Häcker <haeckerfelix@gnome.org>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use serde::{Deserialize, Serialize};
+
+use crate::shared::flatpak::info::PackageInfo;
+
+/// Name of the manifest inside of a backup archive. All other entries of the
+/// archive are the sandbox data directories of the apps (`<app-id>/...`).
+pub const BACKUP_MANIFEST: &str = "souk-backup.json";
+
+/// Increased on incompatible changes of the archive layout
+pub const BACKUP_VERSION: u32 = 1;
+
+/// Describes which apps are included in a backup archive
+#[derive(Default, Deserialize, Serialize, Eq, PartialEq, Debug, Clone, Hash)]
+pub struct BackupManifest {
+    pub version: u32,
+    /// The backed up apps, with the remote they were installed from
+    pub packages: Vec<PackageInfo>,
+}
+
+impl BackupManifest {
+    pub fn new(packages: Vec<PackageInfo>) -> Self {
+        Self {
+            version: BACKUP_VERSION,
+            packages,
+        }
+    }
+}
+
+#[derive(Default, Deserialize, Serialize, Eq, PartialEq, Debug, Clone, Hash)]
+pub enum BackupOperationKind {
+    /// The sandbox data of an app gets added to the archive
+    Export,
+    /// The sandbox data of an app gets restored from the archive
+    Restore,
+    #[default]
+    None,
+}
@@ -0,0 +1,57 @@
+// Souk - disk_usage.rs
+// Copyright (C) 2024  Felix Häcker <haeckerfelix@gnome.org>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use gtk::glib;
+use serde::{Deserialize, Serialize};
+
+/// Disk usage of an installed app, including its sandbox data
+#[derive(Default, Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Hash, glib::Boxed)]
+#[boxed_type(name = "DiskUsage", nullable)]
+pub struct DiskUsage {
+    /// Installed size of the deployed app itself
+    pub deploy_size: u64,
+    /// Runtimes and extensions which aren't used by any other installed app,
+    /// and which therefore would become unused if the app gets uninstalled
+    pub exclusive_refs: Vec<RefDiskUsage>,
+
+    /// Size of `~/.var/app/<app-id>/cache`
+    pub cache_size: u64,
+    /// Size of `~/.var/app/<app-id>/config`
+    pub config_size: u64,
+    /// Size of `~/.var/app/<app-id>/data`
+    pub data_size: u64,
+}
+
+impl DiskUsage {
+    pub fn exclusive_size(&self) -> u64 {
+        self.exclusive_refs.iter().map(|r| r.size).sum()
+    }
+
+    pub fn user_data_size(&self) -> u64 {
+        self.cache_size + self.config_size + self.data_size
+    }
+
+    pub fn total_size(&self) -> u64 {
+        self.deploy_size + self.exclusive_size() + self.user_data_size()
+    }
+}
+
+#[derive(Default, Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RefDiskUsage {
+    pub ref_: String,
+    /// Installed size in bytes
+    pub size: u64,
+}
@@ -19,6 +19,7 @@ use serde::{Deserialize, Serialize};
 
 use super::DryRunPackage;
 use crate::shared::flatpak::info::RemoteInfo;
+use crate::shared::flatpak::PolicyViolation;
 
 #[derive(Default, Deserialize, Debug, Serialize, Clone, PartialEq, Eq, Hash, glib::Boxed)]
 #[boxed_type(name = "DryRun", nullable)]
@@ -39,4 +40,9 @@ pub struct DryRun {
     /// Whether the package is already installed from a different remote, and
     /// the old app needs to get uninstalled first
     pub is_replacing_remote: Option<RemoteInfo>,
+    /// Size of the sandbox data which gets removed along with the package, if
+    /// the uninstall task removes the app data
+    pub removed_data_size: Option<u64>,
+    /// Rules of the permission policy which only warn about the package
+    pub policy_warnings: Vec<PolicyViolation>,
 }
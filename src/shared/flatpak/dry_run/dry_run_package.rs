@@ -19,8 +19,9 @@ use flatpak::TransactionOperation;
 use gtk::glib;
 use serde::{Deserialize, Serialize};
 
+use crate::shared::appstream::ComponentRelations;
 use crate::shared::flatpak::info::{PackageInfo, RemoteInfo};
-use crate::shared::flatpak::FlatpakOperationKind;
+use crate::shared::flatpak::{FlatpakOperationKind, PermissionChange};
 
 #[derive(Default, Derivative, Deserialize, Serialize, Clone, PartialEq, Eq, Hash, glib::Boxed)]
 #[boxed_type(name = "DryRunPackage")]
@@ -37,11 +38,18 @@ pub struct DryRunPackage {
     /// Json serialized appstream component
     #[derivative(Debug = "ignore")]
     pub appstream_component: Option<String>,
+    /// Hardware relations of the appstream component
+    pub relations: ComponentRelations,
     /// Flatpak metadata
     #[derivative(Debug = "ignore")]
     pub metadata: String,
     #[derivative(Debug = "ignore")]
     pub old_metadata: Option<String>,
+    /// Appstream version of the already installed ref (when getting updated)
+    pub old_version: Option<String>,
+    /// Permission differences between `old_metadata` and `metadata` (when
+    /// getting updated)
+    pub permission_changes: Option<Vec<PermissionChange>>,
 }
 
 impl DryRunPackage {
@@ -58,7 +66,7 @@ impl DryRunPackage {
             download_size: operation.download_size(),
             installed_size: operation.installed_size(),
             metadata: operation.metadata().unwrap().to_data().to_string(),
-            old_metadata: operation.metadata().map(|m| m.to_data().to_string()),
+            old_metadata: operation.old_metadata().map(|m| m.to_data().to_string()),
             ..Default::default()
         }
     }
@@ -14,8 +14,20 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
+pub mod backup;
+mod disk_usage;
 pub mod dry_run;
 pub mod info;
 mod operation_kind;
+mod permission_change;
+mod permission_policy;
+pub mod permissions;
+mod policy_violation;
+mod service_config;
 
+pub use disk_usage::{DiskUsage, RefDiskUsage};
 pub use operation_kind::FlatpakOperationKind;
+pub use permission_change::{PermissionChange, PermissionChangeKind};
+pub use permission_policy::{PermissionPolicy, PolicyAction, PolicyRule};
+pub use policy_violation::PolicyViolation;
+pub use service_config::{ServiceConfig, SERVICE_CONFIG};
@@ -0,0 +1,268 @@
+// Souk - permission_change.rs
+// Copyright (C) 2024  Felix Häcker <haeckerfelix@gnome.org>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+
+use glib::{KeyFile, KeyFileFlags};
+use gtk::glib;
+use serde::{Deserialize, Serialize};
+
+/// `[Context]` keys which contain a list of permissions
+const CONTEXT_KEYS: [&str; 6] = [
+    "shared",
+    "sockets",
+    "devices",
+    "features",
+    "filesystems",
+    "persistent",
+];
+const BUS_POLICY_GROUPS: [&str; 2] = ["Session Bus Policy", "System Bus Policy"];
+const ENVIRONMENT_GROUP: &str = "Environment";
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PermissionChangeKind {
+    Added,
+    Removed,
+    /// The permission grants more access than before, e.g. `home:ro` to
+    /// `home`, or bus policy `talk` to `own`
+    Upgraded,
+    /// The permission grants less access than before, e.g. `home` to
+    /// `home:ro`
+    Downgraded,
+    /// The value changed, but can't be compared (e.g. environment variables),
+    /// or grants the same access (e.g. `home` to `home:rw`)
+    Changed,
+}
+
+/// A single difference between the metadata of the installed and the new
+/// version of a package
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PermissionChange {
+    pub kind: PermissionChangeKind,
+    /// Metadata group, e.g. `Context` or `Session Bus Policy`
+    pub group: String,
+    /// `[Context]` key (e.g. `filesystems`), bus name or variable name
+    pub key: String,
+    /// Old value, e.g. `home:ro`, `talk` or the value of a variable
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+}
+
+impl PermissionChange {
+    /// Returns all added, removed and changed permissions between two
+    /// Flatpak metadata files
+    pub fn from_metadata(old_metadata: &str, new_metadata: &str) -> Vec<Self> {
+        let old = KeyFile::new();
+        let new = KeyFile::new();
+        if let Err(err) = old
+            .load_from_data(old_metadata, KeyFileFlags::NONE)
+            .and_then(|_| new.load_from_data(new_metadata, KeyFileFlags::NONE))
+        {
+            warn!("Unable to parse metadata for permission changes: {err}");
+            return Vec::new();
+        }
+
+        let mut changes = Vec::new();
+
+        for key in CONTEXT_KEYS {
+            let old_entries = Self::context_entries(&old, key);
+            let new_entries = Self::context_entries(&new, key);
+            Self::compare(&mut changes, "Context", key, &old_entries, &new_entries);
+        }
+
+        for group in BUS_POLICY_GROUPS.into_iter().chain([ENVIRONMENT_GROUP]) {
+            let old_entries = Self::group_entries(&old, group);
+            let new_entries = Self::group_entries(&new, group);
+
+            for (name, old_value) in &old_entries {
+                if !new_entries.contains_key(name) {
+                    changes.push(Self::new(
+                        PermissionChangeKind::Removed,
+                        group,
+                        name,
+                        Some(old_value),
+                        None,
+                    ));
+                }
+            }
+
+            for (name, new_value) in &new_entries {
+                let kind = match old_entries.get(name) {
+                    None => PermissionChangeKind::Added,
+                    Some(old_value) if old_value == new_value => continue,
+                    Some(_) if group == ENVIRONMENT_GROUP => PermissionChangeKind::Changed,
+                    Some(old_value) => Self::rank_change(
+                        Self::policy_rank(old_value),
+                        Self::policy_rank(new_value),
+                    ),
+                };
+
+                changes.push(Self::new(
+                    kind,
+                    group,
+                    name,
+                    old_entries.get(name).map(String::as_str),
+                    Some(new_value),
+                ));
+            }
+        }
+
+        changes
+    }
+
+    /// Whether the change grants additional access
+    pub fn is_extending(&self) -> bool {
+        match self.kind {
+            PermissionChangeKind::Added => !Self::is_revoking(self.new_value.as_deref()),
+            PermissionChangeKind::Removed => Self::is_revoking(self.old_value.as_deref()),
+            PermissionChangeKind::Upgraded | PermissionChangeKind::Changed => true,
+            PermissionChangeKind::Downgraded => false,
+        }
+    }
+
+    /// Negated entries and `none` policies revoke access instead of
+    /// granting it
+    fn is_revoking(value: Option<&str>) -> bool {
+        value.is_some_and(|v| v.starts_with('!') || v == "none")
+    }
+
+    fn new(
+        kind: PermissionChangeKind,
+        group: &str,
+        key: &str,
+        old_value: Option<&str>,
+        new_value: Option<&str>,
+    ) -> Self {
+        Self {
+            kind,
+            group: group.into(),
+            key: key.into(),
+            old_value: old_value.map(Into::into),
+            new_value: new_value.map(Into::into),
+        }
+    }
+
+    /// Compares two `[Context]` lists. Entries are matched by their identity,
+    /// so `home:ro` and `home` or `x11` and `!x11` are detected as change of
+    /// the same permission.
+    fn compare(
+        changes: &mut Vec<Self>,
+        group: &str,
+        key: &str,
+        old_entries: &BTreeMap<String, String>,
+        new_entries: &BTreeMap<String, String>,
+    ) {
+        for (identity, old_value) in old_entries {
+            if !new_entries.contains_key(identity) {
+                changes.push(Self::new(
+                    PermissionChangeKind::Removed,
+                    group,
+                    key,
+                    Some(old_value),
+                    None,
+                ));
+            }
+        }
+
+        for (identity, new_value) in new_entries {
+            let kind = match old_entries.get(identity) {
+                None => PermissionChangeKind::Added,
+                Some(old_value) if old_value == new_value => continue,
+                Some(old_value) => {
+                    Self::rank_change(Self::entry_rank(old_value), Self::entry_rank(new_value))
+                }
+            };
+
+            changes.push(Self::new(
+                kind,
+                group,
+                key,
+                old_entries.get(identity).map(String::as_str),
+                Some(new_value),
+            ));
+        }
+    }
+
+    /// Maps the identity of a `[Context]` list entry (without negation and
+    /// access suffix) to the entry itself. Later entries take precedence.
+    fn context_entries(keyfile: &KeyFile, key: &str) -> BTreeMap<String, String> {
+        let mut entries = BTreeMap::new();
+
+        if let Ok(list) = keyfile.string_list("Context", key) {
+            for entry in list {
+                let identity = entry.as_str().trim_start_matches('!');
+                let identity = identity
+                    .strip_suffix(":ro")
+                    .or_else(|| identity.strip_suffix(":rw"))
+                    .or_else(|| identity.strip_suffix(":create"))
+                    .unwrap_or(identity);
+
+                entries.insert(identity.to_string(), entry.to_string());
+            }
+        }
+
+        entries
+    }
+
+    fn group_entries(keyfile: &KeyFile, group: &str) -> BTreeMap<String, String> {
+        let mut entries = BTreeMap::new();
+
+        if let Ok(keys) = keyfile.keys(group) {
+            for key in keys {
+                let value = keyfile.string(group, &key).unwrap_or_default();
+                entries.insert(key.to_string(), value.to_string());
+            }
+        }
+
+        entries
+    }
+
+    /// Values with the same rank grant the same access, e.g. `home` and
+    /// `home:rw`
+    fn rank_change(old_rank: u8, new_rank: u8) -> PermissionChangeKind {
+        match new_rank.cmp(&old_rank) {
+            Ordering::Greater => PermissionChangeKind::Upgraded,
+            Ordering::Less => PermissionChangeKind::Downgraded,
+            Ordering::Equal => PermissionChangeKind::Changed,
+        }
+    }
+
+    /// How much access a `[Context]` list entry grants. `:create`
+    /// additionally creates the path if it doesn't exist.
+    fn entry_rank(entry: &str) -> u8 {
+        if entry.starts_with('!') {
+            0
+        } else if entry.ends_with(":ro") {
+            1
+        } else if entry.ends_with(":create") {
+            3
+        } else {
+            2
+        }
+    }
+
+    /// How much access a bus policy grants
+    fn policy_rank(policy: &str) -> u8 {
+        match policy {
+            "none" => 0,
+            "see" => 1,
+            "talk" => 2,
+            "own" => 3,
+            _ => 2,
+        }
+    }
+}
@@ -0,0 +1,387 @@
+// Souk - permission_policy.rs
+// Copyright (C) 2024  Felix Häcker <haeckerfelix@gnome.org>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::fs;
+use std::path::Path;
+
+use glib::KeyFile;
+use gtk::glib;
+use serde::Deserialize;
+
+use super::permissions::{PermissionSet, SkPermissionSummary};
+use super::PolicyViolation;
+use crate::shared::appstream::{license, SkLicenseKind};
+use crate::shared::path;
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum PolicyAction {
+    Deny,
+    Warn,
+}
+
+/// A single rule of the permission policy
+#[derive(Deserialize, Debug, Clone)]
+pub struct PolicyRule {
+    pub action: PolicyAction,
+    /// Either a permission summary flag (e.g. `sandbox-escape`), or a
+    /// `[Context]` entry (e.g. `devices=all`)
+    pub condition: String,
+    /// Reason which gets shown to the user
+    #[serde(default)]
+    pub message: Option<String>,
+}
+
+/// Restricts which apps can be installed, based on their permissions,
+/// licenses and remotes. The organization policy in
+/// [path::SYSTEM_POLICY_FILE](crate::shared::path::SYSTEM_POLICY_FILE) gets
+/// combined with the policy of the user, which can only add restrictions.
+///
+/// The policy gets evaluated by the worker, directly on the metadata of the
+/// package.
+///
+/// ```json
+/// {
+///   "allowed-remotes": ["flathub"],
+///   "allow-proprietary": false,
+///   "blocked-licenses": ["AGPL-3.0-or-later"],
+///   "rules": [
+///     { "action": "deny", "condition": "sandbox-escape" },
+///     { "action": "warn", "condition": "full-system-bus-access" },
+///     { "action": "deny", "condition": "devices=all", "message": "No device access" }
+///   ]
+/// }
+/// ```
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "kebab-case")]
+pub struct PermissionPolicy {
+    /// Only packages from these remotes are allowed, if set
+    #[serde(default)]
+    pub allowed_remotes: Option<Vec<String>>,
+    /// Whether apps with a proprietary license, or which download
+    /// proprietary extra data, can be installed
+    #[serde(default)]
+    pub allow_proprietary: Option<bool>,
+    /// SPDX license identifiers of apps which cannot be installed
+    #[serde(default)]
+    pub blocked_licenses: Vec<String>,
+    #[serde(default)]
+    pub rules: Vec<PolicyRule>,
+}
+
+impl PermissionPolicy {
+    /// Loads the organization and the user policy
+    pub fn load() -> Self {
+        let system = Self::from_file(&path::SYSTEM_POLICY_FILE).unwrap_or_default();
+        let user = Self::from_file(&path::POLICY_FILE).unwrap_or_default();
+
+        let allowed_remotes = match (system.allowed_remotes, user.allowed_remotes) {
+            (Some(system), Some(user)) => {
+                Some(system.into_iter().filter(|r| user.contains(r)).collect())
+            }
+            (system, user) => system.or(user),
+        };
+
+        let allow_proprietary = match (system.allow_proprietary, user.allow_proprietary) {
+            (Some(system), Some(user)) => Some(system && user),
+            (system, user) => system.or(user),
+        };
+
+        let mut blocked_licenses = system.blocked_licenses;
+        blocked_licenses.extend(user.blocked_licenses);
+
+        let mut rules = system.rules;
+        rules.extend(user.rules);
+
+        Self {
+            allowed_remotes,
+            allow_proprietary,
+            blocked_licenses,
+            rules,
+        }
+    }
+
+    fn from_file(file: &Path) -> Option<Self> {
+        let data = fs::read_to_string(file).ok()?;
+        match serde_json::from_str(&data) {
+            Ok(policy) => Some(policy),
+            Err(err) => {
+                warn!(
+                    "Unable to parse permission policy {}: {err}",
+                    file.display()
+                );
+                None
+            }
+        }
+    }
+
+    /// Returns all rules which match the package. The license and permission
+    /// rules only get evaluated for apps, `license` is the SPDX expression
+    /// of the appstream component.
+    pub fn evaluate(
+        &self,
+        ref_: &str,
+        remote: &str,
+        metadata: Option<&KeyFile>,
+        license: Option<&str>,
+    ) -> Vec<PolicyViolation> {
+        let mut violations = Vec::new();
+
+        if let Some(allowed_remotes) = &self.allowed_remotes {
+            if !allowed_remotes.iter().any(|r| r == remote) {
+                violations.push(PolicyViolation {
+                    ref_: ref_.into(),
+                    rule: "allowed-remotes".into(),
+                    message: None,
+                    blocking: true,
+                });
+            }
+        }
+
+        if !ref_.starts_with("app/") {
+            return violations;
+        }
+
+        let extra_data = metadata.is_some_and(|m| m.has_group("Extra Data"));
+        violations.extend(self.evaluate_license(ref_, license, extra_data));
+
+        let Some(metadata) = metadata else {
+            return violations;
+        };
+
+        let permissions = PermissionSet::from_metadata(metadata);
+        let summary = permissions.summary();
+
+        for rule in &self.rules {
+            let matches = match rule.condition.split_once('=') {
+                Some((key, value)) => permissions.contains_entry(key, value),
+                None => match SkPermissionSummary::from_name(&rule.condition) {
+                    Some(flag) => summary.contains(flag),
+                    None => {
+                        warn!("Unknown permission policy condition: {}", rule.condition);
+                        false
+                    }
+                },
+            };
+
+            if matches {
+                violations.push(PolicyViolation {
+                    ref_: ref_.into(),
+                    rule: rule.condition.clone(),
+                    message: rule.message.clone(),
+                    blocking: rule.action == PolicyAction::Deny,
+                });
+            }
+        }
+
+        violations
+    }
+
+    fn evaluate_license(
+        &self,
+        ref_: &str,
+        license: Option<&str>,
+        extra_data: bool,
+    ) -> Vec<PolicyViolation> {
+        let mut violations = Vec::new();
+
+        if self.allow_proprietary == Some(false) {
+            let kind = license.map(license::license_kind).unwrap_or_default();
+            if kind == SkLicenseKind::Proprietary || extra_data {
+                violations.push(PolicyViolation {
+                    ref_: ref_.into(),
+                    rule: "allow-proprietary".into(),
+                    message: None,
+                    blocking: true,
+                });
+            }
+        }
+
+        for id in license.map(license::license_ids).unwrap_or_default() {
+            if self
+                .blocked_licenses
+                .iter()
+                .any(|blocked| blocked.eq_ignore_ascii_case(&id))
+            {
+                violations.push(PolicyViolation {
+                    ref_: ref_.into(),
+                    rule: format!("blocked-licenses={id}"),
+                    message: None,
+                    blocking: true,
+                });
+            }
+        }
+
+        violations
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use glib::KeyFileFlags;
+
+    use super::*;
+
+    fn metadata(context: &str) -> KeyFile {
+        let keyfile = KeyFile::new();
+        let data = format!("[Application]\nname=org.example.App\n\n{context}");
+        keyfile.load_from_data(&data, KeyFileFlags::NONE).unwrap();
+        keyfile
+    }
+
+    fn policy(rules: &[(PolicyAction, &str)]) -> PermissionPolicy {
+        PermissionPolicy {
+            rules: rules
+                .iter()
+                .map(|(action, condition)| PolicyRule {
+                    action: *action,
+                    condition: condition.to_string(),
+                    message: None,
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    fn matching_rules(policy: &PermissionPolicy, context: &str) -> Vec<String> {
+        policy
+            .evaluate(
+                "app/org.example.App/x86_64/stable",
+                "flathub",
+                Some(&metadata(context)),
+                None,
+            )
+            .into_iter()
+            .map(|v| v.rule)
+            .collect()
+    }
+
+    #[test]
+    fn summary_condition() {
+        let policy = policy(&[
+            (PolicyAction::Deny, "sandbox-escape"),
+            (PolicyAction::Warn, "full-device-access"),
+            (PolicyAction::Warn, "network-access"),
+        ]);
+
+        for filesystem in ["home/.ssh", "~/.gnupg:create", "xdg-config/../.ssh"] {
+            let context = format!("[Context]\nfilesystems={filesystem};\n");
+            assert_eq!(matching_rules(&policy, &context), ["sandbox-escape"]);
+        }
+
+        let context = "[Context]\nfilesystems=home/.ssh:ro;xdg-run/pipewire-0;\n";
+        assert_eq!(matching_rules(&policy, context), ["full-device-access"]);
+
+        let context =
+            "[Context]\nshared=network;\n[Session Bus Policy]\norg.freedesktop.Flatpak=talk\n";
+        assert_eq!(
+            matching_rules(&policy, context),
+            ["sandbox-escape", "network-access"]
+        );
+
+        // Only seeing the name on the bus doesn't allow any interaction
+        let context = "[Session Bus Policy]\norg.freedesktop.Flatpak=see\n";
+        assert!(matching_rules(&policy, context).is_empty());
+    }
+
+    #[test]
+    fn entry_condition() {
+        let policy = policy(&[
+            (PolicyAction::Deny, "devices=all"),
+            (PolicyAction::Deny, "filesystems=home"),
+        ]);
+
+        let context = "[Context]\ndevices=dri;all;\nfilesystems=home:ro;\n";
+        assert_eq!(
+            matching_rules(&policy, context),
+            ["devices=all", "filesystems=home"]
+        );
+
+        // Later entries revoke earlier ones
+        let context = "[Context]\ndevices=all;!all;dri;\nfilesystems=home;!home;\n";
+        assert!(matching_rules(&policy, context).is_empty());
+    }
+
+    #[test]
+    fn unknown_condition() {
+        let policy = policy(&[(PolicyAction::Deny, "no-such-flag")]);
+        assert!(SkPermissionSummary::from_name("no-such-flag").is_none());
+        assert!(matching_rules(&policy, "[Context]\ndevices=all;\n").is_empty());
+    }
+
+    #[test]
+    fn action_and_remotes() {
+        let mut policy = policy(&[(PolicyAction::Warn, "full-system-bus-access")]);
+        policy.allowed_remotes = Some(vec!["fedora".into()]);
+
+        let keyfile = metadata("[Context]\nsockets=system-bus;\n");
+        let violations = policy.evaluate(
+            "app/org.example.App/x86_64/stable",
+            "flathub",
+            Some(&keyfile),
+            None,
+        );
+        assert_eq!(violations.len(), 2);
+        assert_eq!(violations[0].rule, "allowed-remotes");
+        assert!(violations[0].blocking);
+        assert_eq!(violations[1].rule, "full-system-bus-access");
+        assert!(!violations[1].blocking);
+
+        // Permission rules only apply to apps
+        let violations = policy.evaluate(
+            "runtime/org.example.Platform/x86_64/1",
+            "fedora",
+            Some(&keyfile),
+            Some("LicenseRef-proprietary"),
+        );
+        assert!(violations.is_empty());
+    }
+
+    #[test]
+    fn license() {
+        let policy = PermissionPolicy {
+            allow_proprietary: Some(false),
+            blocked_licenses: vec!["agpl-3.0-or-later".into()],
+            ..Default::default()
+        };
+        let rules = |context: &str, license: Option<&str>| -> Vec<String> {
+            policy
+                .evaluate(
+                    "app/org.example.App/x86_64/stable",
+                    "flathub",
+                    Some(&metadata(context)),
+                    license,
+                )
+                .into_iter()
+                .map(|v| v.rule)
+                .collect()
+        };
+
+        assert!(rules("", Some("GPL-3.0-or-later OR LicenseRef-proprietary")).is_empty());
+        assert_eq!(
+            rules("", Some("MIT AND LicenseRef-proprietary")),
+            ["allow-proprietary"]
+        );
+        assert_eq!(
+            rules("", Some("(MIT OR AGPL-3.0-or-later)")),
+            ["blocked-licenses=AGPL-3.0-or-later"]
+        );
+
+        // Extra data gets downloaded from a third party during the installation
+        let context = "[Extra Data]\nuri=https://example.org/app.deb\n";
+        assert_eq!(rules(context, Some("MIT")), ["allow-proprietary"]);
+    }
+}
@@ -0,0 +1,70 @@
+// Souk - feature_permission.rs
+// Copyright (C) 2024  Felix Häcker <haeckerfelix@gnome.org>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use gtk::glib;
+
+use super::SkPermissionSummary;
+
+#[glib::flags(name = "SkFeaturePermission")]
+pub enum SkFeaturePermission {
+    #[flags_value(name = "none")]
+    NONE = 1 << 0,
+    #[flags_value(name = "unknown")]
+    UNKNOWN = 1 << 1,
+    #[flags_value(name = "devel")]
+    DEVEL = 1 << 2,
+    #[flags_value(name = "multiarch")]
+    MULTIARCH = 1 << 3,
+    #[flags_value(name = "bluetooth")]
+    BLUETOOTH = 1 << 4,
+    #[flags_value(name = "canbus")]
+    CANBUS = 1 << 5,
+    #[flags_value(name = "per-app-dev-shm")]
+    PER_APP_DEV_SHM = 1 << 6,
+}
+
+impl SkFeaturePermission {
+    pub fn permission_summary(&self) -> SkPermissionSummary {
+        let mut summary = SkPermissionSummary::empty();
+
+        if self.contains(Self::DEVEL) {
+            summary |= SkPermissionSummary::DEBUGGING_ACCESS;
+        }
+
+        if self.contains(Self::BLUETOOTH) || self.contains(Self::CANBUS) {
+            summary |= SkPermissionSummary::FULL_DEVICE_ACCESS;
+        }
+
+        if self.contains(Self::UNKNOWN) {
+            summary |= SkPermissionSummary::UNKNOWN;
+        }
+
+        summary
+    }
+}
+
+impl From<&str> for SkFeaturePermission {
+    fn from(value: &str) -> Self {
+        match value {
+            "devel" => Self::DEVEL,
+            "multiarch" => Self::MULTIARCH,
+            "bluetooth" => Self::BLUETOOTH,
+            "canbus" => Self::CANBUS,
+            "per-app-dev-shm" => Self::PER_APP_DEV_SHM,
+            _ => Self::UNKNOWN,
+        }
+    }
+}
@@ -0,0 +1,231 @@
+// Souk - filesystem_details.rs
+// Copyright (C) 2024  Felix Häcker <haeckerfelix@gnome.org>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::SkFilesystemPermissionKind;
+
+/// Classification of a `filesystems` entry, after resolving `..`
+/// components
+pub enum FilesystemDetails<'a> {
+    Home {
+        can_write: bool,
+        subdir: Option<&'a str>,
+    },
+    Host(bool),
+    HostOs(bool),
+    HostEtc(bool),
+    Desktop {
+        can_write: bool,
+        subdir: Option<&'a str>,
+    },
+    Documents {
+        can_write: bool,
+        subdir: Option<&'a str>,
+    },
+    Download {
+        can_write: bool,
+        subdir: Option<&'a str>,
+    },
+    Music {
+        can_write: bool,
+        subdir: Option<&'a str>,
+    },
+    Pictures {
+        can_write: bool,
+        subdir: Option<&'a str>,
+    },
+    Public {
+        can_write: bool,
+        subdir: Option<&'a str>,
+    },
+    Videos {
+        can_write: bool,
+        subdir: Option<&'a str>,
+    },
+    Templates {
+        can_write: bool,
+        subdir: Option<&'a str>,
+    },
+    Config {
+        can_write: bool,
+        subdir: Option<&'a str>,
+    },
+    Cache {
+        can_write: bool,
+        subdir: Option<&'a str>,
+    },
+    Data {
+        can_write: bool,
+        subdir: Option<&'a str>,
+    },
+    Runtime {
+        can_write: bool,
+        name: &'a str,
+    },
+    /// `xdg-run/pipewire-0`, which bypasses the screencast / camera portals
+    Pipewire,
+    SshKeys(bool),
+    GnupgKeys(bool),
+    /// Mount points of removable devices and additional drives
+    RemovableMedia {
+        can_write: bool,
+        path: &'a str,
+    },
+    /// Path which escapes its base folder with `..`
+    Traversal {
+        can_write: bool,
+        path: &'a str,
+    },
+    Path {
+        can_write: bool,
+        path: &'a str,
+    },
+}
+
+impl<'a> FilesystemDetails<'a> {
+    pub fn new(can_write: bool, path: &'a str) -> Self {
+        let trimmed = path.trim_end_matches('/');
+        if ["/run/media", "/media", "/mnt"]
+            .iter()
+            .any(|m| trimmed == *m || trimmed.starts_with(&format!("{m}/")))
+        {
+            return Self::RemovableMedia { can_write, path };
+        }
+
+        let (permission, subdir) = match path.split_once('/') {
+            Some(("", path)) => (path, None),
+            Some((path, subdir)) => (path, Some(subdir)),
+            None => (path, None),
+        };
+
+        let is_in = |subdir: Option<&str>, dir: &str| {
+            subdir.is_some_and(|s| s == dir || s.starts_with(&format!("{dir}/")))
+        };
+
+        match permission {
+            "home" | "~" if is_in(subdir, ".ssh") => Self::SshKeys(can_write),
+            "home" | "~" if is_in(subdir, ".gnupg") => Self::GnupgKeys(can_write),
+            "home" | "~" => Self::Home { can_write, subdir },
+            "host" => Self::Host(can_write),
+            "host-os" => Self::HostOs(can_write),
+            "host-etc" => Self::HostEtc(can_write),
+            "xdg-desktop" => Self::Desktop { can_write, subdir },
+            "xdg-documents" => Self::Documents { can_write, subdir },
+            "xdg-download" => Self::Download { can_write, subdir },
+            "xdg-music" => Self::Music { can_write, subdir },
+            "xdg-pictures" => Self::Pictures { can_write, subdir },
+            "xdg-public-share" => Self::Public { can_write, subdir },
+            "xdg-videos" => Self::Videos { can_write, subdir },
+            "xdg-templates" => Self::Templates { can_write, subdir },
+            "xdg-config" => Self::Config { can_write, subdir },
+            "xdg-cache" => Self::Cache { can_write, subdir },
+            "xdg-data" => Self::Data { can_write, subdir },
+            "xdg-run" if subdir == Some("pipewire-0") => Self::Pipewire,
+            "xdg-run" => Self::Runtime {
+                can_write,
+                name: subdir.unwrap_or("*"),
+            },
+            _ => Self::Path { can_write, path },
+        }
+    }
+
+    /// Calls `f` with the details of the path
+    pub fn with_details<R>(
+        path: &str,
+        kind: SkFilesystemPermissionKind,
+        f: impl FnOnce(&FilesystemDetails) -> R,
+    ) -> R {
+        let can_write = kind.can_write();
+
+        match Self::resolve(path) {
+            Some(resolved) => f(&FilesystemDetails::new(can_write, &resolved)),
+            None => f(&FilesystemDetails::Traversal { can_write, path }),
+        }
+    }
+
+    /// Resolves `..` components, e.g. `xdg-config/../.ssh` becomes
+    /// `~/.ssh`. Returns `None` if the path escapes the home directory, or
+    /// a folder without known location.
+    pub fn resolve(path: &str) -> Option<String> {
+        if !path.split('/').any(|c| c == "..") {
+            return Some(path.to_string());
+        }
+
+        let (root, rest) = path.split_once('/').unwrap_or((path, ""));
+        let (prefix, base) = match root {
+            "" => ("", ""),
+            "home" | "~" => ("~", ""),
+            "xdg-desktop" => ("~", "Desktop"),
+            "xdg-documents" => ("~", "Documents"),
+            "xdg-download" => ("~", "Downloads"),
+            "xdg-music" => ("~", "Music"),
+            "xdg-pictures" => ("~", "Pictures"),
+            "xdg-public-share" => ("~", "Public"),
+            "xdg-videos" => ("~", "Videos"),
+            "xdg-templates" => ("~", "Templates"),
+            "xdg-config" => ("~", ".config"),
+            "xdg-cache" => ("~", ".cache"),
+            "xdg-data" => ("~", ".local/share"),
+            _ => return None,
+        };
+
+        let mut components: Vec<&str> = base.split('/').filter(|c| !c.is_empty()).collect();
+        for component in rest.split('/') {
+            match component {
+                "" | "." => (),
+                ".." => {
+                    // Absolute paths can't get above the root directory
+                    if components.pop().is_none() && !prefix.is_empty() {
+                        return None;
+                    }
+                }
+                _ => components.push(component),
+            }
+        }
+
+        if components.is_empty() {
+            return Some(if prefix.is_empty() { "/" } else { prefix }.to_string());
+        }
+
+        Some(format!("{prefix}/{}", components.join("/")))
+    }
+
+    pub const fn can_write(&self) -> bool {
+        match self {
+            Self::Host(can_write)
+            | Self::HostOs(can_write)
+            | Self::HostEtc(can_write)
+            | Self::SshKeys(can_write)
+            | Self::GnupgKeys(can_write) => *can_write,
+            Self::Home { can_write, .. }
+            | Self::Desktop { can_write, .. }
+            | Self::Documents { can_write, .. }
+            | Self::Download { can_write, .. }
+            | Self::Music { can_write, .. }
+            | Self::Pictures { can_write, .. }
+            | Self::Public { can_write, .. }
+            | Self::Videos { can_write, .. }
+            | Self::Templates { can_write, .. }
+            | Self::Config { can_write, .. }
+            | Self::Cache { can_write, .. }
+            | Self::Data { can_write, .. }
+            | Self::Runtime { can_write, .. }
+            | Self::RemovableMedia { can_write, .. }
+            | Self::Traversal { can_write, .. }
+            | Self::Path { can_write, .. } => *can_write,
+            Self::Pipewire => true,
+        }
+    }
+}
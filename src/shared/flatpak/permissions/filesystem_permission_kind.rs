@@ -27,10 +27,29 @@ pub enum SkFilesystemPermissionKind {
 }
 
 impl SkFilesystemPermissionKind {
+    /// Splits a `filesystems` entry into its path and access mode, e.g.
+    /// `home:ro` becomes `home` and [Self::ReadOnly]
+    pub fn from_flatpak(value: &str) -> (&str, Self) {
+        if let Some(path) = value.strip_suffix(":rw") {
+            (path, Self::ReadWrite)
+        } else if let Some(path) = value.strip_suffix(":create") {
+            (path, Self::Create)
+        } else if let Some(path) = value.strip_suffix(":ro") {
+            (path, Self::ReadOnly)
+        } else {
+            (value, Self::ReadWrite)
+        }
+    }
+
     pub const fn can_write(&self) -> bool {
         match self {
             Self::ReadOnly => false,
             Self::ReadWrite | Self::Create => true,
         }
     }
+
+    /// `:create` additionally creates the folder if it doesn't exist yet
+    pub const fn creates(&self) -> bool {
+        matches!(self, Self::Create)
+    }
 }
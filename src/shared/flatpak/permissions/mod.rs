@@ -0,0 +1,35 @@
+// Souk - mod.rs
+// Copyright (C) 2024  Felix Häcker <haeckerfelix@gnome.org>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+mod device_permission;
+mod feature_permission;
+mod filesystem_details;
+mod filesystem_permission_kind;
+mod permission_set;
+mod permission_summary;
+mod service_policy;
+mod socket_permission;
+mod subsystem_permission;
+
+pub use device_permission::SkDevicePermission;
+pub use feature_permission::SkFeaturePermission;
+pub use filesystem_details::FilesystemDetails;
+pub use filesystem_permission_kind::SkFilesystemPermissionKind;
+pub use permission_set::{FilesystemEntry, PermissionSet, ServiceEntry};
+pub use permission_summary::SkPermissionSummary;
+pub use service_policy::SkServicePolicy;
+pub use socket_permission::SkSocketPermission;
+pub use subsystem_permission::SkSubsystemPermission;
@@ -0,0 +1,221 @@
+// Souk - permission_set.rs
+// Copyright (C) 2024  Felix Häcker <haeckerfelix@gnome.org>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use glib::KeyFile;
+use gtk::glib;
+
+use super::*;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FilesystemEntry {
+    pub path: String,
+    pub kind: SkFilesystemPermissionKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServiceEntry {
+    pub name: String,
+    pub is_system: bool,
+    pub policy: SkServicePolicy,
+}
+
+/// The permissions of a flatpak metadata / overrides keyfile, with negated
+/// entries (e.g. `!x11`) already applied
+#[derive(Debug, Clone)]
+pub struct PermissionSet {
+    pub filesystems: Vec<FilesystemEntry>,
+    pub services: Vec<ServiceEntry>,
+    pub devices: SkDevicePermission,
+    pub sockets: SkSocketPermission,
+    pub subsystems: SkSubsystemPermission,
+    pub features: SkFeaturePermission,
+    pub environment: Vec<(String, String)>,
+    pub persistent: Vec<String>,
+}
+
+impl PermissionSet {
+    pub fn from_metadata(keyfile: &KeyFile) -> Self {
+        let mut filesystems: Vec<FilesystemEntry> = Vec::new();
+        for (filesystem, negated) in Self::context_entries(keyfile, "filesystems") {
+            let (path, kind) = SkFilesystemPermissionKind::from_flatpak(&filesystem);
+            filesystems.retain(|f| f.path != path);
+
+            if !negated {
+                filesystems.push(FilesystemEntry {
+                    path: path.to_string(),
+                    kind,
+                });
+            }
+        }
+
+        let mut services = Self::services(keyfile, "Session Bus Policy", false);
+        services.extend(Self::services(keyfile, "System Bus Policy", true));
+
+        let mut devices = SkDevicePermission::empty();
+        for (device, negated) in Self::context_entries(keyfile, "devices") {
+            let value = SkDevicePermission::from(device.as_str());
+            if !negated {
+                devices |= value;
+            } else if value != SkDevicePermission::UNKNOWN {
+                devices.remove(value);
+            }
+        }
+        if devices.is_empty() {
+            devices = SkDevicePermission::NONE;
+        }
+
+        let mut sockets = SkSocketPermission::empty();
+        for (socket, negated) in Self::context_entries(keyfile, "sockets") {
+            let value = SkSocketPermission::from(socket.as_str());
+            if !negated {
+                sockets |= value;
+            } else if value != SkSocketPermission::UNKNOWN {
+                sockets.remove(value);
+            }
+        }
+        if sockets.is_empty() {
+            sockets = SkSocketPermission::NONE;
+        }
+
+        let mut subsystems = SkSubsystemPermission::empty();
+        for (subsystem, negated) in Self::context_entries(keyfile, "shared") {
+            let value = SkSubsystemPermission::from(subsystem.as_str());
+            if !negated {
+                subsystems |= value;
+            } else if value != SkSubsystemPermission::UNKNOWN {
+                subsystems.remove(value);
+            }
+        }
+        if subsystems.is_empty() {
+            subsystems = SkSubsystemPermission::NONE;
+        }
+
+        let mut features = SkFeaturePermission::empty();
+        for (feature, negated) in Self::context_entries(keyfile, "features") {
+            let value = SkFeaturePermission::from(feature.as_str());
+            if !negated {
+                features |= value;
+            } else if value != SkFeaturePermission::UNKNOWN {
+                features.remove(value);
+            }
+        }
+        if features.is_empty() {
+            features = SkFeaturePermission::NONE;
+        }
+
+        let mut environment = Vec::new();
+        if let Ok(variables) = keyfile.keys("Environment") {
+            for name in variables {
+                let value = keyfile
+                    .string("Environment", &name)
+                    .map(|v| v.to_string())
+                    .unwrap_or_default();
+                environment.push((name.to_string(), value));
+            }
+        }
+
+        let persistent = keyfile
+            .string_list("Context", "persistent")
+            .map(|list| list.iter().map(|p| p.to_string()).collect())
+            .unwrap_or_default();
+
+        Self {
+            filesystems,
+            services,
+            devices,
+            sockets,
+            subsystems,
+            features,
+            environment,
+            persistent,
+        }
+    }
+
+    /// Returns the entries of a `[Context]` list, and whether they are
+    /// negated (e.g. `!x11`). The order is kept, since later entries
+    /// take precedence over earlier ones.
+    fn context_entries(keyfile: &KeyFile, key: &str) -> Vec<(String, bool)> {
+        keyfile
+            .string_list("Context", key)
+            .map(|list| {
+                list.iter()
+                    .map(|entry| match entry.strip_prefix('!') {
+                        Some(entry) => (entry.to_string(), true),
+                        None => (entry.to_string(), false),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    fn services(keyfile: &KeyFile, group: &str, is_system: bool) -> Vec<ServiceEntry> {
+        let mut services = Vec::new();
+
+        if let Ok(service_list) = keyfile.keys(group) {
+            for service in service_list {
+                let policy = keyfile
+                    .string(group, &service)
+                    .map(|p| SkServicePolicy::from(p.as_str()))
+                    .unwrap_or_default();
+
+                // `none` revokes the access to a service
+                if policy == SkServicePolicy::None {
+                    continue;
+                }
+
+                services.push(ServiceEntry {
+                    name: service.to_string(),
+                    is_system,
+                    policy,
+                });
+            }
+        }
+
+        services
+    }
+
+    /// Combined summary of all permissions
+    pub fn summary(&self) -> SkPermissionSummary {
+        let mut summary = self.subsystems.permission_summary()
+            | self.devices.permission_summary()
+            | self.sockets.permission_summary()
+            | self.features.permission_summary();
+
+        for filesystem in &self.filesystems {
+            summary |= SkPermissionSummary::for_filesystem(&filesystem.path, filesystem.kind);
+        }
+        for service in &self.services {
+            summary |= SkPermissionSummary::for_service(&service.name, service.policy);
+        }
+        for (name, _) in &self.environment {
+            summary |= SkPermissionSummary::for_environment(name);
+        }
+
+        summary
+    }
+
+    /// Whether a `[Context]` entry, e.g. `sockets=x11`, is granted
+    pub fn contains_entry(&self, key: &str, value: &str) -> bool {
+        match key {
+            "shared" => self.subsystems.contains(value.into()),
+            "sockets" => self.sockets.contains(value.into()),
+            "devices" => self.devices.contains(value.into()),
+            "features" => self.features.contains(value.into()),
+            "filesystems" => self.filesystems.iter().any(|f| f.path == value),
+            _ => false,
+        }
+    }
+}
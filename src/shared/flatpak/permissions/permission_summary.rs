@@ -0,0 +1,102 @@
+// Souk - permission_summary.rs
+// Copyright (C) 2024  Felix Häcker <haeckerfelix@gnome.org>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use gtk::glib;
+
+use super::{FilesystemDetails, SkFilesystemPermissionKind, SkServicePolicy};
+use crate::shared::flatpak::SERVICE_CONFIG;
+
+/// Classification of the permissions of an app. This is used for the
+/// permission overview of the UI, and for the rules of the
+/// [PermissionPolicy](crate::shared::flatpak::PermissionPolicy).
+#[glib::flags(name = "SkPermissionSummary")]
+pub enum SkPermissionSummary {
+    #[flags_value(name = "full-device-access")]
+    FULL_DEVICE_ACCESS = 1 << 1,
+    #[flags_value(name = "full-session-bus-access")]
+    FULL_SESSION_BUS_ACCESS = 1 << 2,
+    #[flags_value(name = "full-system-bus-access")]
+    FULL_SYSTEM_BUS_ACCESS = 1 << 3,
+    #[flags_value(name = "read-data")]
+    READ_DATA = 1 << 4,
+    #[flags_value(name = "readwrite-data")]
+    READWRITE_DATA = 1 << 5,
+    #[flags_value(name = "network-access")]
+    NETWORK_ACCESS = 1 << 6,
+    #[flags_value(name = "sandbox-escape")]
+    SANDBOX_ESCAPE = 1 << 7,
+    #[flags_value(name = "debugging-access")]
+    DEBUGGING_ACCESS = 1 << 9,
+
+    #[flags_value(name = "unknown")]
+    UNKNOWN = 1 << 8,
+}
+
+impl SkPermissionSummary {
+    /// Looks up a single flag by its name, e.g. `sandbox-escape`
+    pub fn from_name(name: &str) -> Option<Self> {
+        glib::FlagsClass::new::<Self>()
+            .value_by_name(name)
+            .and_then(|value| Self::from_bits(value.value()))
+    }
+
+    pub fn for_filesystem(path: &str, kind: SkFilesystemPermissionKind) -> Self {
+        let mut summary = FilesystemDetails::with_details(path, kind, |details| match details {
+            FilesystemDetails::Pipewire => Self::FULL_DEVICE_ACCESS,
+            // The ssh and gpg configs allow running arbitrary commands on the
+            // host
+            FilesystemDetails::SshKeys(true) | FilesystemDetails::GnupgKeys(true) => {
+                Self::READWRITE_DATA | Self::SANDBOX_ESCAPE
+            }
+            FilesystemDetails::Traversal { .. } => Self::READWRITE_DATA | Self::UNKNOWN,
+            _ if details.can_write() => Self::READWRITE_DATA,
+            _ => Self::READ_DATA,
+        });
+
+        if kind.can_write() && path.contains("flatpak/overrides") {
+            summary |= Self::SANDBOX_ESCAPE;
+        }
+
+        summary
+    }
+
+    pub fn for_service(name: &str, policy: SkServicePolicy) -> Self {
+        let mut summary = Self::empty();
+
+        // Only seeing a name on the bus doesn't allow any interaction
+        if policy <= SkServicePolicy::See || SERVICE_CONFIG.is_ignored(name) {
+            return summary;
+        }
+
+        if SERVICE_CONFIG.is_sensitive(name) {
+            summary |= Self::READWRITE_DATA;
+        }
+
+        if SERVICE_CONFIG.is_dangerous(name) {
+            summary |= Self::SANDBOX_ESCAPE;
+        }
+
+        summary
+    }
+
+    pub fn for_environment(name: &str) -> Self {
+        match name {
+            "DBUS_SESSION_BUS_ADDRESS" => Self::FULL_SESSION_BUS_ACCESS,
+            "DBUS_SYSTEM_BUS_ADDRESS" => Self::FULL_SYSTEM_BUS_ACCESS,
+            _ => Self::empty(),
+        }
+    }
+}
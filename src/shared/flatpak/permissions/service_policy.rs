@@ -0,0 +1,45 @@
+// Souk - service_policy.rs
+// Copyright (C) 2024  Felix Häcker <haeckerfelix@gnome.org>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use glib::Enum;
+use gtk::glib;
+
+/// The access level of a `[Session Bus Policy]` / `[System Bus Policy]` entry
+#[derive(Copy, Debug, Clone, Default, Eq, PartialEq, PartialOrd, Ord, Enum)]
+#[repr(u32)]
+#[enum_type(name = "SkServicePolicy")]
+pub enum SkServicePolicy {
+    /// Explicitly denied, e.g. to revoke a permission with an override
+    None,
+    /// Can see the name on the bus
+    See,
+    /// Can send messages to the name and receive replies
+    #[default]
+    Talk,
+    /// Can own the name, and therefore receives messages meant for it
+    Own,
+}
+
+impl From<&str> for SkServicePolicy {
+    fn from(value: &str) -> Self {
+        match value {
+            "none" => Self::None,
+            "see" => Self::See,
+            "own" => Self::Own,
+            _ => Self::Talk,
+        }
+    }
+}
@@ -0,0 +1,32 @@
+// Souk - policy_violation.rs
+// Copyright (C) 2024  Felix Häcker <haeckerfelix@gnome.org>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use serde::{Deserialize, Serialize};
+
+/// A rule of the permission policy which matched a package, see
+/// [path::SYSTEM_POLICY_FILE](crate::shared::path::SYSTEM_POLICY_FILE)
+#[derive(Default, Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PolicyViolation {
+    /// Ref of the package which matched the rule
+    pub ref_: String,
+    /// The matched rule, e.g. `sandbox-escape`, `devices=all` or
+    /// `allowed-remotes`
+    pub rule: String,
+    /// Reason which is set in the policy file
+    pub message: Option<String>,
+    /// Whether the installation gets blocked, or only a warning is shown
+    pub blocking: bool,
+}
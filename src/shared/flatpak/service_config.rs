@@ -0,0 +1,183 @@
+// Souk - service_config.rs
+// Copyright (C) 2024  Felix Häcker <haeckerfelix@gnome.org>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::fs;
+use std::path::Path;
+use std::sync::LazyLock;
+
+use serde::Deserialize;
+
+use crate::shared::path;
+
+/// Services which are commonly used for desktop integration, and therefore
+/// don't get treated as additional permission
+const DEFAULT_IGNORED: &[&str] = &[
+    "org.kde.StatusNotifier",
+    "org.mpris.MediaPlayer",
+    "org.freedesktop.Notifications",
+    "com.canonical.AppMenu.Registrar",
+    "com.canonical.indicator.application",
+    "com.canonical.Unity.LauncherEntry",
+    "org.a11y.Bus",
+];
+
+/// Services which give access to sensitive data or settings
+const DEFAULT_SENSITIVE: &[&str] = &[
+    "org.gnome.SessionManager",
+    "org.freedesktop.PackageKit",
+    "org.freedesktop.NetworkManager",
+    "org.freedesktop.UDisks2",
+    "ca.desrt.dconf",
+    "org.gnome.SettingsDaemon",
+    "org.freedesktop.secrets",
+    "org.freedesktop.Flatpak",
+];
+
+/// Services which allow executing commands outside of the sandbox, or
+/// changing permissions
+const DEFAULT_DANGEROUS: &[&str] = &[
+    "org.freedesktop.Flatpak",
+    "org.freedesktop.systemd1",
+    "org.freedesktop.impl.portal.PermissionStore",
+];
+
+pub static SERVICE_CONFIG: LazyLock<ServiceConfig> = LazyLock::new(ServiceConfig::load);
+
+/// Lists of D-Bus service patterns, which can be extended by the system and
+/// the user. A pattern either is a prefix of the bus name (e.g.
+/// `org.mpris.MediaPlayer`), or contains `*` wildcards (e.g.
+/// `org.example.*.Helper`). Patterns which only consist of wildcards get
+/// rejected.
+///
+/// Ignored services don't count as permission, so only the system config can
+/// add them. Otherwise a user config could weaken the
+/// [PermissionPolicy](super::PermissionPolicy) of the organization.
+///
+/// ```json
+/// {
+///   "ignored": ["org.example.Tray*"],
+///   "sensitive": ["org.example.Vault"],
+///   "dangerous": ["org.example.HostCommand"]
+/// }
+/// ```
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct ServiceConfig {
+    #[serde(default)]
+    pub ignored: Vec<String>,
+    #[serde(default)]
+    pub sensitive: Vec<String>,
+    #[serde(default)]
+    pub dangerous: Vec<String>,
+}
+
+impl ServiceConfig {
+    fn load() -> Self {
+        let mut config = Self {
+            ignored: DEFAULT_IGNORED.iter().map(|s| s.to_string()).collect(),
+            sensitive: DEFAULT_SENSITIVE.iter().map(|s| s.to_string()).collect(),
+            dangerous: DEFAULT_DANGEROUS.iter().map(|s| s.to_string()).collect(),
+        };
+
+        if let Some(mut system) = Self::from_file(&path::SYSTEM_SERVICES_FILE) {
+            config.ignored.append(&mut system.ignored);
+            config.sensitive.append(&mut system.sensitive);
+            config.dangerous.append(&mut system.dangerous);
+        }
+
+        if let Some(mut user) = Self::from_file(&path::SERVICES_FILE) {
+            if !user.ignored.is_empty() {
+                warn!("Ignored services can only be added by the system service config");
+            }
+            config.sensitive.append(&mut user.sensitive);
+            config.dangerous.append(&mut user.dangerous);
+        }
+
+        config
+    }
+
+    fn from_file(file: &Path) -> Option<Self> {
+        let data = fs::read_to_string(file).ok()?;
+        match serde_json::from_str::<Self>(&data) {
+            Ok(mut config) => {
+                // Empty or wildcard-only patterns would match every service
+                for patterns in [
+                    &mut config.ignored,
+                    &mut config.sensitive,
+                    &mut config.dangerous,
+                ] {
+                    patterns.retain(|pattern| {
+                        let valid = !pattern.chars().all(|c| c == '*');
+                        if !valid {
+                            warn!(
+                                "Rejecting service pattern \"{pattern}\" of {}",
+                                file.display()
+                            );
+                        }
+                        valid
+                    });
+                }
+
+                Some(config)
+            }
+            Err(err) => {
+                warn!("Unable to parse service config {}: {err}", file.display());
+                None
+            }
+        }
+    }
+
+    /// Dangerous services never get ignored, even if they match an ignored
+    /// pattern
+    pub fn is_ignored(&self, name: &str) -> bool {
+        !self.is_dangerous(name) && Self::matches_any(&self.ignored, name)
+    }
+
+    pub fn is_sensitive(&self, name: &str) -> bool {
+        Self::matches_any(&self.sensitive, name)
+    }
+
+    pub fn is_dangerous(&self, name: &str) -> bool {
+        Self::matches_any(&self.dangerous, name)
+    }
+
+    fn matches_any(patterns: &[String], name: &str) -> bool {
+        patterns.iter().any(|pattern| Self::matches(pattern, name))
+    }
+
+    fn matches(pattern: &str, name: &str) -> bool {
+        if !pattern.contains('*') {
+            return name.starts_with(pattern);
+        }
+
+        // Simple glob matching, `*` matches any sequence of characters
+        let mut parts = pattern.split('*');
+        let first = parts.next().unwrap_or_default();
+        let Some(mut rest) = name.strip_prefix(first) else {
+            return false;
+        };
+
+        let parts: Vec<&str> = parts.collect();
+        let (last, middle) = parts.split_last().unwrap();
+        for part in middle {
+            match rest.find(part) {
+                Some(index) => rest = &rest[index + part.len()..],
+                None => return false,
+            }
+        }
+
+        rest.ends_with(last)
+    }
+}
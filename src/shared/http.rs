@@ -0,0 +1,43 @@
+// Souk - http.rs
+// Copyright (C) 2024  Felix Häcker <haeckerfelix@gnome.org>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::time::Duration;
+
+use isahc::config::{Configurable, RedirectPolicy};
+use isahc::Request;
+
+use crate::shared::WorkerError;
+
+/// Maximum time to establish the connection
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The transfer gets aborted if no data is received for this long
+const STALL_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Download links (e.g. of Flathub or GitHub releases) often redirect
+const MAX_REDIRECTS: u32 = 10;
+
+/// Builds a GET request with the timeouts and redirect policy which are used
+/// for all downloads. There's no overall timeout, large files can take a
+/// while on slow connections.
+pub fn get_request(url: &str) -> Result<Request<()>, WorkerError> {
+    Request::get(url)
+        .connect_timeout(CONNECT_TIMEOUT)
+        .low_speed_timeout(1, STALL_TIMEOUT)
+        .redirect_policy(RedirectPolicy::Limit(MAX_REDIRECTS))
+        .body(())
+        .map_err(|err| WorkerError::Download(err.to_string()))
+}
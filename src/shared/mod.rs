@@ -16,6 +16,7 @@
 
 pub mod appstream;
 pub mod flatpak;
+pub mod http;
 pub mod task;
 mod worker_error;
 
@@ -15,7 +15,7 @@
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::LazyLock;
 
 use gtk::glib;
@@ -34,18 +34,97 @@ pub static CONFIG_DIR: LazyLock<PathBuf> = LazyLock::new(|| {
     path
 });
 
+/// Local override of the bundled curated collections
+pub static COLLECTIONS_FILE: LazyLock<PathBuf> = LazyLock::new(|| {
+    let mut file = CONFIG_DIR.clone();
+    file.push("collections.json");
+    file
+});
+
+/// Permission policy of the current user
+pub static POLICY_FILE: LazyLock<PathBuf> = LazyLock::new(|| {
+    let mut file = CONFIG_DIR.clone();
+    file.push("permission-policy.json");
+    file
+});
+
+/// Permission policy of the organization, which can't be relaxed by users
+pub static SYSTEM_POLICY_FILE: LazyLock<PathBuf> = LazyLock::new(|| {
+    let mut file = PathBuf::from("/etc");
+    file.push(config::NAME);
+    file.push("permission-policy.json");
+    file
+});
+
+/// Additional ignored / sensitive / dangerous D-Bus services of the current user
+pub static SERVICES_FILE: LazyLock<PathBuf> = LazyLock::new(|| {
+    let mut file = CONFIG_DIR.clone();
+    file.push("services.json");
+    file
+});
+
+/// Additional ignored / sensitive / dangerous D-Bus services of the system
+pub static SYSTEM_SERVICES_FILE: LazyLock<PathBuf> = LazyLock::new(|| {
+    let mut file = PathBuf::from("/etc");
+    file.push(config::NAME);
+    file.push("services.json");
+    file
+});
+
 pub static CACHE_DIR: LazyLock<PathBuf> = LazyLock::new(|| {
     let mut path = glib::user_cache_dir();
     path.push(config::NAME);
     path
 });
 
+/// Files which got downloaded by the worker, each download has its own
+/// `<task-uuid>` subdirectory
+pub static DOWNLOAD_DIR: LazyLock<PathBuf> = LazyLock::new(|| {
+    let mut path = CACHE_DIR.clone();
+    path.push("downloads");
+    path
+});
+
 pub static APPSTREAM_CACHE: LazyLock<PathBuf> = LazyLock::new(|| {
     let mut file = CACHE_DIR.clone();
     file.push("appstream.xmlb");
     file
 });
 
+pub static APPSTREAM_SYNC_STATUS: LazyLock<PathBuf> = LazyLock::new(|| {
+    let mut file = CACHE_DIR.clone();
+    file.push("appstream-sync.json");
+    file
+});
+
+pub static ICON_CACHE: LazyLock<PathBuf> = LazyLock::new(|| {
+    let mut path = CACHE_DIR.clone();
+    path.push("icons");
+    path
+});
+
+pub static SCREENSHOT_CACHE: LazyLock<PathBuf> = LazyLock::new(|| {
+    let mut path = CACHE_DIR.clone();
+    path.push("screenshots");
+    path
+});
+
+/// Sandbox data of Flatpak apps, `~/.var/app/<app-id>`
+pub static APP_DATA_DIR: LazyLock<PathBuf> = LazyLock::new(|| {
+    let mut path = glib::home_dir();
+    path.push(".var");
+    path.push("app");
+    path
+});
+
+/// Backups of app sandbox data, which got created when resetting app data.
+/// Only the latest backups of each app are kept.
+pub static APP_DATA_BACKUP_DIR: LazyLock<PathBuf> = LazyLock::new(|| {
+    let mut path = DATA_DIR.clone();
+    path.push("app-data-backups");
+    path
+});
+
 pub static BIN_DIR: LazyLock<PathBuf> = LazyLock::new(|| {
     let mut path = glib::home_dir();
     path.push(".local");
@@ -57,6 +136,23 @@ pub fn init() -> std::io::Result<()> {
     fs::create_dir_all(DATA_DIR.to_owned())?;
     fs::create_dir_all(CONFIG_DIR.to_owned())?;
     fs::create_dir_all(CACHE_DIR.to_owned())?;
+    fs::create_dir_all(ICON_CACHE.to_owned())?;
+    fs::create_dir_all(SCREENSHOT_CACHE.to_owned())?;
     fs::create_dir_all(BIN_DIR.to_owned())?;
     Ok(())
 }
+
+/// Removes the directory of a downloaded file, once it isn't needed anymore.
+/// Files outside of [DOWNLOAD_DIR] are left untouched.
+pub fn remove_download(file: &Path) {
+    let Some(dir) = file
+        .parent()
+        .filter(|d| d.parent() == Some(DOWNLOAD_DIR.as_path()))
+    else {
+        return;
+    };
+
+    if let Err(err) = fs::remove_dir_all(dir) {
+        warn!("Unable to remove download {}: {err}", dir.display());
+    }
+}
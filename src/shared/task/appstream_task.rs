@@ -18,6 +18,7 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use super::FlatpakTask;
+use crate::shared::flatpak::info::PackageInfo;
 use crate::shared::task::{Task, TaskKind};
 
 #[derive(Default, Deserialize, Serialize, Eq, PartialEq, Debug, Clone, Hash)]
@@ -62,6 +63,24 @@ pub enum AppstreamTaskKind {
     /// `set_dry_run_package_appstream`, we need to convert the [FlatpakTask]
     /// into a [AppstreamTask] using the kind `Dependency`
     Dependency,
+    /// Looks up all components matching the query, across all remotes and
+    /// installations
+    Lookup(AppstreamLookup),
+    /// Discovers all addons which are available for an app
+    Addons(PackageInfo),
     #[default]
     None,
 }
+
+#[derive(Deserialize, Serialize, Eq, PartialEq, Debug, Clone, Hash)]
+pub enum AppstreamLookup {
+    /// Component id, e.g. `org.gnome.Maps`
+    Id(String),
+    /// Multiple component ids, the results keep the order of the ids
+    Ids(Vec<String>),
+    /// Id listed in `<provides>`, e.g. an old / renamed component id
+    Provides(String),
+    /// Mimetype which can be opened by the component, e.g.
+    /// `application/vnd.oasis.opendocument.text`
+    Mimetype(String),
+}
@@ -0,0 +1,65 @@
+// Souk - backup_task.rs
+// Copyright (C) 2024  Felix Häcker <haeckerfelix@gnome.org>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::shared::flatpak::info::{InstallationInfo, PackageInfo};
+use crate::shared::task::{Task, TaskKind};
+
+/// Export / import of the sandbox data of apps into / from an archive
+#[derive(Default, Deserialize, Serialize, Eq, PartialEq, Debug, Clone, Hash)]
+pub struct BackupTask {
+    pub uuid: String,
+    pub kind: BackupTaskKind,
+}
+
+impl BackupTask {
+    pub fn new(kind: BackupTaskKind) -> Self {
+        Self {
+            uuid: Uuid::new_v4().to_string(),
+            kind,
+        }
+    }
+}
+
+impl From<BackupTask> for Task {
+    fn from(backup_task: BackupTask) -> Self {
+        Task {
+            uuid: backup_task.uuid.clone(),
+            cancellable: false,
+            kind: TaskKind::Backup(Box::new(backup_task)),
+        }
+    }
+}
+
+#[derive(Default, Deserialize, Serialize, Eq, PartialEq, Debug, Clone, Hash)]
+pub enum BackupTaskKind {
+    /// Writes the data of the installed packages, together with their ref and
+    /// remote, into the archive at `path`
+    Export {
+        packages: Vec<PackageInfo>,
+        path: String,
+    },
+    /// Installs the apps of the archive at `path` which are missing in the
+    /// installation, and restores their data
+    Import {
+        path: String,
+        installation: InstallationInfo,
+    },
+    #[default]
+    None,
+}
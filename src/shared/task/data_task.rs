@@ -0,0 +1,81 @@
+// Souk - data_task.rs
+// Copyright (C) 2024  Felix Häcker <haeckerfelix@gnome.org>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::shared::flatpak::info::PackageInfo;
+use crate::shared::task::{Task, TaskKind};
+
+/// Operations on the sandbox data of an installed app (`~/.var/app/<app-id>`)
+#[derive(Default, Deserialize, Serialize, Eq, PartialEq, Debug, Clone, Hash)]
+pub struct DataTask {
+    pub uuid: String,
+    pub kind: DataTaskKind,
+    /// The installed package whose data is affected
+    pub package: PackageInfo,
+    /// Only calculate the size which would be freed, without changing any
+    /// files
+    pub dry_run: bool,
+}
+
+impl DataTask {
+    pub fn new(kind: DataTaskKind, package: &PackageInfo, dry_run: bool) -> Self {
+        Self {
+            uuid: Uuid::new_v4().to_string(),
+            kind,
+            package: package.clone(),
+            dry_run,
+        }
+    }
+}
+
+impl From<DataTask> for Task {
+    fn from(data_task: DataTask) -> Self {
+        Task {
+            uuid: data_task.uuid.clone(),
+            cancellable: false,
+            kind: TaskKind::Data(Box::new(data_task)),
+        }
+    }
+}
+
+#[derive(Default, Deserialize, Serialize, Eq, PartialEq, Debug, Clone, Hash)]
+pub enum DataTaskKind {
+    /// Calculates the disk usage of the app, its exclusively used runtimes and
+    /// its sandbox data
+    DiskUsage,
+    /// Deletes the content of `~/.var/app/<app-id>/cache`
+    ClearCache,
+    /// Removes the whole `~/.var/app/<app-id>` directory, so that the app
+    /// starts with a clean state
+    ResetData(DataRemoval),
+    #[default]
+    None,
+}
+
+/// What happens with a data directory which gets removed
+#[derive(Default, Deserialize, Serialize, Eq, PartialEq, Debug, Clone, Copy, Hash)]
+pub enum DataRemoval {
+    /// Move the directory into the trash
+    #[default]
+    Trash,
+    /// Move the directory into a timestamped backup, see
+    /// [path::APP_DATA_BACKUP_DIR](crate::shared::path::APP_DATA_BACKUP_DIR)
+    Backup,
+    /// Delete the directory permanently
+    Delete,
+}
@@ -0,0 +1,53 @@
+// Souk - download_task.rs
+// Copyright (C) 2024  Felix Häcker <haeckerfelix@gnome.org>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::shared::task::{Task, TaskKind};
+
+#[derive(Default, Deserialize, Serialize, Eq, PartialEq, Debug, Clone, Hash)]
+pub struct DownloadTask {
+    pub uuid: String,
+    /// The http(s) url of the file which should get downloaded
+    pub url: String,
+    /// Maximum accepted file size in bytes
+    pub max_size: u64,
+    /// Accepted content types (without parameters, eg. `text/plain`). If empty,
+    /// any content type gets accepted.
+    pub content_types: Vec<String>,
+}
+
+impl DownloadTask {
+    pub fn new(url: &str, max_size: u64, content_types: &[&str]) -> Self {
+        Self {
+            uuid: Uuid::new_v4().to_string(),
+            url: url.to_string(),
+            max_size,
+            content_types: content_types.iter().map(|t| t.to_string()).collect(),
+        }
+    }
+}
+
+impl From<DownloadTask> for Task {
+    fn from(download_task: DownloadTask) -> Self {
+        Task {
+            uuid: download_task.uuid.clone(),
+            cancellable: true,
+            kind: TaskKind::Download(Box::new(download_task)),
+        }
+    }
+}
@@ -18,7 +18,7 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::shared::flatpak::info::{InstallationInfo, PackageInfo, RemoteInfo};
-use crate::shared::task::{Task, TaskKind};
+use crate::shared::task::{DataRemoval, Task, TaskKind};
 
 #[derive(Default, Deserialize, Serialize, Eq, PartialEq, Debug, Clone, Hash)]
 // TODO: This could be simplified by using PackageInfo
@@ -46,6 +46,15 @@ pub struct FlatpakTask {
     /// uninstalled first. This can be the case when a ref gets installed
     /// from a different remote, and the GPG keys wouldn't match for example.
     pub uninstall_before_install: bool,
+    /// Addon refs which get installed from the same remote as part of the
+    /// task ([FlatpakTaskKind::Install] or [FlatpakTaskKind::ChangeAddons])
+    pub install_addons: Vec<String>,
+    /// Addon refs which get uninstalled as part of the task
+    /// ([FlatpakTaskKind::ChangeAddons])
+    pub uninstall_addons: Vec<String>,
+    /// Removes the sandbox data of the app (`~/.var/app/<app-id>`) as part of
+    /// the task ([FlatpakTaskKind::Uninstall])
+    pub remove_data: Option<DataRemoval>,
 }
 
 impl FlatpakTask {
@@ -102,7 +111,49 @@ impl FlatpakTask {
         }
     }
 
-    pub fn new_uninstall(package: &PackageInfo, dry_run: bool) -> Self {
+    pub fn new_change_addons(
+        package: &PackageInfo,
+        install_addons: Vec<String>,
+        uninstall_addons: Vec<String>,
+    ) -> Self {
+        let installation = package.remote.installation.as_ref().unwrap().clone();
+
+        Self {
+            uuid: Uuid::new_v4().to_string(),
+            kind: FlatpakTaskKind::ChangeAddons,
+            installation,
+            ref_: Some(package.ref_.clone()),
+            remote: Some(package.remote.clone()),
+            install_addons,
+            uninstall_addons,
+            ..Default::default()
+        }
+    }
+
+    /// Installs the addons along with the package
+    pub fn with_addons(mut self, install_addons: Vec<String>) -> Self {
+        self.install_addons = install_addons;
+        self
+    }
+
+    pub fn new_update(package: &PackageInfo) -> Self {
+        let installation = package.remote.installation.as_ref().unwrap().clone();
+
+        Self {
+            uuid: Uuid::new_v4().to_string(),
+            kind: FlatpakTaskKind::Update,
+            installation,
+            ref_: Some(package.ref_.clone()),
+            remote: Some(package.remote.clone()),
+            ..Default::default()
+        }
+    }
+
+    pub fn new_uninstall(
+        package: &PackageInfo,
+        remove_data: Option<DataRemoval>,
+        dry_run: bool,
+    ) -> Self {
         let installation = package.remote.installation.as_ref().unwrap().clone();
 
         Self {
@@ -113,6 +164,7 @@ impl FlatpakTask {
             ref_: Some(package.ref_.clone()),
             remote: Some(package.remote.clone()),
             uninstall_before_install: false,
+            remove_data,
             ..Default::default()
         }
     }
@@ -134,6 +186,8 @@ pub enum FlatpakTaskKind {
     InstallRefFile,
     InstallBundleFile,
     Uninstall,
+    /// Installs and / or uninstalls addons of an already installed app
+    ChangeAddons,
     Update,
     UpdateInstallation,
     #[default]
@@ -146,6 +200,7 @@ impl FlatpakTaskKind {
             || self == &Self::InstallRefFile
             || self == &Self::InstallBundleFile
             || self == &Self::Uninstall
+            || self == &Self::ChangeAddons
             || self == &Self::Update
     }
 }
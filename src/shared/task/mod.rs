@@ -17,10 +17,18 @@
 pub mod response;
 
 mod appstream_task;
+mod backup_task;
+mod data_task;
+mod download_task;
 mod flatpak_task;
+mod override_task;
 #[allow(clippy::module_inception)]
 mod task;
 
-pub use appstream_task::{AppstreamTask, AppstreamTaskKind};
+pub use appstream_task::{AppstreamLookup, AppstreamTask, AppstreamTaskKind};
+pub use backup_task::{BackupTask, BackupTaskKind};
+pub use data_task::{DataRemoval, DataTask, DataTaskKind};
+pub use download_task::DownloadTask;
 pub use flatpak_task::{FlatpakTask, FlatpakTaskKind};
+pub use override_task::{OverrideTask, OverrideTaskKind};
 pub use task::{Task, TaskKind};
@@ -0,0 +1,72 @@
+// Souk - override_task.rs
+// Copyright (C) 2024  Felix Häcker <haeckerfelix@gnome.org>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::shared::flatpak::info::InstallationInfo;
+use crate::shared::task::{Task, TaskKind};
+
+/// Reading / writing of Flatpak permission overrides, which are stored in
+/// `<installation>/overrides/<app-id>` (or `global` for all apps)
+#[derive(Default, Deserialize, Serialize, Eq, PartialEq, Debug, Clone, Hash)]
+pub struct OverrideTask {
+    pub uuid: String,
+    pub kind: OverrideTaskKind,
+    /// The installation whose overrides are affected. Overrides of the user
+    /// installation also apply to apps from system installations.
+    pub installation: InstallationInfo,
+    /// The app id, or `None` for the global overrides which apply to all apps
+    pub app_id: Option<String>,
+}
+
+impl OverrideTask {
+    pub fn new(
+        kind: OverrideTaskKind,
+        installation: &InstallationInfo,
+        app_id: Option<&str>,
+    ) -> Self {
+        Self {
+            uuid: Uuid::new_v4().to_string(),
+            kind,
+            installation: installation.clone(),
+            app_id: app_id.map(Into::into),
+        }
+    }
+}
+
+impl From<OverrideTask> for Task {
+    fn from(override_task: OverrideTask) -> Self {
+        Task {
+            uuid: override_task.uuid.clone(),
+            cancellable: false,
+            kind: TaskKind::Override(Box::new(override_task)),
+        }
+    }
+}
+
+#[derive(Default, Deserialize, Serialize, Eq, PartialEq, Debug, Clone, Hash)]
+pub enum OverrideTaskKind {
+    /// Reads the overrides keyfile, returns an empty string if there are no
+    /// overrides
+    Load,
+    /// Replaces the overrides keyfile with the given keyfile data
+    Save(String),
+    /// Removes all overrides
+    Reset,
+    #[default]
+    None,
+}
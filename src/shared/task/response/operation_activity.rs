@@ -21,8 +21,10 @@ use serde::{Deserialize, Serialize};
 
 use super::OperationStatus;
 use crate::shared::appstream::AppstreamOperationKind;
+use crate::shared::flatpak::backup::BackupOperationKind;
 use crate::shared::flatpak::info::{PackageInfo, RemoteInfo};
 use crate::shared::flatpak::FlatpakOperationKind;
+use crate::shared::task::DataRemoval;
 
 #[derive(Default, Deserialize, Serialize, PartialEq, Debug, Clone, Eq, Hash)]
 pub struct OperationActivity {
@@ -32,8 +34,13 @@ pub struct OperationActivity {
 
     pub flatpak_operation: FlatpakOperationKind,
     pub appstream_operation: AppstreamOperationKind,
+    pub backup_operation: BackupOperationKind,
     pub package: Option<PackageInfo>,
     pub remote: Option<RemoteInfo>,
+    /// Url of the file which gets downloaded
+    pub download_url: Option<String>,
+    /// How the sandbox data of [OperationActivity::package] gets removed
+    pub data_removal: Option<DataRemoval>,
 }
 
 impl OperationActivity {
@@ -96,11 +103,7 @@ impl OperationActivity {
             AppstreamOperationKind::Compile
         };
 
-        let progress = if status == OperationStatus::Done {
-            100
-        } else {
-            0
-        };
+        let progress = if status.is_completed() { 100 } else { 0 };
 
         Self {
             status,
@@ -111,6 +114,55 @@ impl OperationActivity {
         }
     }
 
+    pub fn new_download(
+        url: &str,
+        status: OperationStatus,
+        progress: i32,
+        download_rate: u64,
+    ) -> Self {
+        Self {
+            status,
+            progress,
+            download_rate,
+            download_url: Some(url.to_string()),
+            ..Default::default()
+        }
+    }
+
+    pub fn new_remove_data(
+        package: &PackageInfo,
+        data_removal: DataRemoval,
+        status: OperationStatus,
+    ) -> Self {
+        let progress = if status.is_completed() { 100 } else { 0 };
+
+        Self {
+            status,
+            progress,
+            package: Some(package.clone()),
+            remote: Some(package.remote.clone()),
+            data_removal: Some(data_removal),
+            ..Default::default()
+        }
+    }
+
+    pub fn new_backup(
+        package: &PackageInfo,
+        backup_operation: BackupOperationKind,
+        status: OperationStatus,
+    ) -> Self {
+        let progress = if status.is_completed() { 100 } else { 0 };
+
+        Self {
+            status,
+            progress,
+            backup_operation,
+            package: Some(package.clone()),
+            remote: Some(package.remote.clone()),
+            ..Default::default()
+        }
+    }
+
     /// Required for [SkOperationModel] - we can't use the task uuid here, since
     /// it wouldn't differ for different operations
     pub fn identifier(&self) -> String {
@@ -118,6 +170,12 @@ impl OperationActivity {
             format!("{:?}:{:?}", self.flatpak_operation, self.package)
         } else if self.appstream_operation != AppstreamOperationKind::None {
             format!("{:?}:{:?}", self.appstream_operation, self.remote)
+        } else if let Some(url) = &self.download_url {
+            format!("Download:{url}")
+        } else if self.backup_operation != BackupOperationKind::None {
+            format!("{:?}:{:?}", self.backup_operation, self.package)
+        } else if self.data_removal.is_some() {
+            format!("RemoveData:{:?}", self.package)
         } else {
             error!("Unable to generate identifier for operation activity");
             String::new()
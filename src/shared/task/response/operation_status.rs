@@ -25,11 +25,21 @@ pub enum OperationStatus {
     Updating,
     Uninstalling,
     Processing,
+    Downloading,
     Done,
+    Skipped,
+    Failed,
     #[default]
     None,
 }
 
+impl OperationStatus {
+    /// Whether the operation is completed, regardless of its outcome
+    pub fn is_completed(&self) -> bool {
+        self == &Self::Done || self == &Self::Skipped || self == &Self::Failed
+    }
+}
+
 impl From<TransactionOperationType> for OperationStatus {
     fn from(op: TransactionOperationType) -> Self {
         match op {
@@ -16,7 +16,9 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::shared::appstream::{AddonInfo, ComponentInfo, RemoteSyncResult};
 use crate::shared::flatpak::dry_run::DryRun;
+use crate::shared::flatpak::DiskUsage;
 use crate::shared::WorkerError;
 
 #[derive(Default, Deserialize, Serialize, Eq, PartialEq, Debug, Clone, Hash)]
@@ -25,6 +27,20 @@ pub enum TaskResult {
     Done,
     /// Task completed successfully, with an [DryRun] as result
     DoneDryRun(Box<DryRun>),
+    /// Appstream data got updated, with the sync result of each remote
+    DoneAppstreamSync(Box<Vec<RemoteSyncResult>>),
+    /// Appstream lookup completed, with all matching components as result
+    DoneAppstreamLookup(Box<Vec<ComponentInfo>>),
+    /// Addon discovery completed, with all available addons as result
+    DoneAddons(Box<Vec<AddonInfo>>),
+    /// File got downloaded, with the local path of the file as result
+    DoneDownload(String),
+    /// Disk usage of an app got calculated
+    DoneDiskUsage(Box<DiskUsage>),
+    /// Data task got dry ran, with the size in bytes which would be freed
+    DoneDataDryRun(u64),
+    /// Permission overrides got loaded, with the keyfile data as result
+    DoneOverrides(String),
     /// Task failed. See [ResponseType.error] for more details.
     Error(Box<WorkerError>),
     /// Task got cancelled (most likely by user).
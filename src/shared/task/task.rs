@@ -17,7 +17,9 @@
 use gtk::glib;
 use serde::{Deserialize, Serialize};
 
-use crate::shared::task::{AppstreamTask, FlatpakTask};
+use crate::shared::task::{
+    AppstreamTask, BackupTask, DataTask, DownloadTask, FlatpakTask, OverrideTask,
+};
 
 #[derive(Deserialize, Serialize, Eq, PartialEq, Debug, Clone, Hash, glib::Boxed)]
 #[boxed_type(name = "Task", nullable)]
@@ -35,4 +37,8 @@ pub struct Task {
 pub enum TaskKind {
     Flatpak(Box<FlatpakTask>),
     Appstream(Box<AppstreamTask>),
+    Download(Box<DownloadTask>),
+    Data(Box<DataTask>),
+    Backup(Box<BackupTask>),
+    Override(Box<OverrideTask>),
 }
@@ -22,6 +22,8 @@ use gtk::glib::Error as GLibError;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
+use crate::shared::flatpak::PolicyViolation;
+
 #[derive(Deserialize, Clone, Serialize, Error, Debug, Eq, Hash, PartialEq, glib::Boxed)]
 #[boxed_type(name = "WorkerError")]
 pub enum WorkerError {
@@ -31,6 +33,10 @@ pub enum WorkerError {
     GLibCancelled(String),
     GLib(String),
     DryRunRuntimeNotFound(String),
+    Download(String),
+    Backup(String),
+    /// The package isn't allowed to be installed by the permission policy
+    PolicyBlocked(PolicyViolation),
 }
 
 impl Default for WorkerError {
@@ -68,6 +74,14 @@ impl fmt::Display for WorkerError {
             Self::DryRunRuntimeNotFound(runtime) => {
                 format!("Unable to find required runtime {runtime}")
             }
+            Self::Download(message) => format!("Unable to download file: {message}"),
+            Self::Backup(message) => format!("Unable to process backup: {message}"),
+            Self::PolicyBlocked(violation) => format!(
+                "{} is not permitted by the permission policy ({}): {}",
+                violation.ref_,
+                violation.rule,
+                violation.message.as_deref().unwrap_or("no reason given")
+            ),
             Self::GLibCancelled(_) => "The operation got cancelled.".into(),
             Self::GLib(message) => message.into(),
         };
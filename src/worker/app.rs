@@ -33,7 +33,9 @@ use crate::shared::config;
 use crate::shared::task::response::TaskResponse;
 use crate::shared::task::{Task, TaskKind};
 use crate::worker::dbus_server::WorkerServer;
-use crate::worker::{AppstreamWorker, FlatpakWorker};
+use crate::worker::{
+    AppstreamWorker, BackupWorker, DataWorker, DownloadWorker, FlatpakWorker, OverrideWorker,
+};
 
 /// Specifies how many tasks can be executed in parallel
 const WORKER_THREADS: usize = 4;
@@ -53,6 +55,10 @@ mod imp {
 
         pub flatpak_worker: FlatpakWorker,
         pub appstream_worker: AppstreamWorker,
+        pub download_worker: DownloadWorker,
+        pub data_worker: DataWorker,
+        pub backup_worker: BackupWorker,
+        pub override_worker: OverrideWorker,
 
         dbus_connection: RefCell<Option<Connection>>,
         thread_pool: RefCell<Option<ThreadPool>>,
@@ -72,7 +78,11 @@ mod imp {
             let (response_sender, response_receiver) = unbounded();
 
             let flatpak_worker = FlatpakWorker::new(response_sender.clone());
-            let appstream_worker = AppstreamWorker::new(response_sender);
+            let appstream_worker = AppstreamWorker::new(response_sender.clone());
+            let download_worker = DownloadWorker::new(response_sender.clone());
+            let data_worker = DataWorker::new(response_sender.clone());
+            let backup_worker = BackupWorker::new(response_sender.clone());
+            let override_worker = OverrideWorker::new(response_sender);
 
             let dbus_connection = RefCell::default();
             let thread_pool = RefCell::default();
@@ -86,6 +96,10 @@ mod imp {
                 response_receiver,
                 flatpak_worker,
                 appstream_worker,
+                download_worker,
+                data_worker,
+                backup_worker,
+                override_worker,
                 dbus_connection,
                 thread_pool,
                 hold_guard,
@@ -209,6 +223,50 @@ mod imp {
                                 }
                             ));
                         }
+                        TaskKind::Download(task) => {
+                            thread_pool.spawn(clone!(
+                                #[strong(rename_to = worker)]
+                                self.download_worker,
+                                #[strong]
+                                task,
+                                async move {
+                                    worker.process_task(*task);
+                                }
+                            ));
+                        }
+                        TaskKind::Data(task) => {
+                            thread_pool.spawn(clone!(
+                                #[strong(rename_to = worker)]
+                                self.data_worker,
+                                #[strong]
+                                task,
+                                async move {
+                                    worker.process_task(*task);
+                                }
+                            ));
+                        }
+                        TaskKind::Backup(task) => {
+                            thread_pool.spawn(clone!(
+                                #[strong(rename_to = worker)]
+                                self.backup_worker,
+                                #[strong]
+                                task,
+                                async move {
+                                    worker.process_task(*task);
+                                }
+                            ));
+                        }
+                        TaskKind::Override(task) => {
+                            thread_pool.spawn(clone!(
+                                #[strong(rename_to = worker)]
+                                self.override_worker,
+                                #[strong]
+                                task,
+                                async move {
+                                    worker.process_task(*task);
+                                }
+                            ));
+                        }
                     }
                 } else {
                     error!("Unable to start task, thread pool is not available.");
@@ -227,6 +285,10 @@ mod imp {
             match task.kind {
                 TaskKind::Flatpak(_) => self.flatpak_worker.cancel_task(&task.uuid),
                 TaskKind::Appstream(_) => self.appstream_worker.cancel_task(&task.uuid),
+                TaskKind::Download(_) => self.download_worker.cancel_task(&task.uuid),
+                TaskKind::Data(_) => self.data_worker.cancel_task(&task.uuid),
+                TaskKind::Backup(_) => self.backup_worker.cancel_task(&task.uuid),
+                TaskKind::Override(_) => self.override_worker.cancel_task(&task.uuid),
             }
         }
 
@@ -301,7 +363,6 @@ impl SkWorkerApplication {
         app.run()
     }
 
-    #[allow(dead_code)]
     pub(super) fn flatpak_worker(&self) -> FlatpakWorker {
         self.imp().flatpak_worker.clone()
     }
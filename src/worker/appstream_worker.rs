@@ -18,6 +18,8 @@ use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
 use std::sync::Arc;
 
+use appstream::enums::Icon;
+use appstream::xmltree::Element;
 use appstream::Component;
 use async_std::channel::Sender;
 use flatpak::functions::system_installations;
@@ -29,12 +31,28 @@ use gtk::{gio, glib};
 use indexmap::IndexMap;
 use xb::prelude::*;
 
+use crate::shared::appstream::{
+    AddonInfo, ComponentInfo, ComponentRelations, MediaCache, RemoteSyncResult, RemoteSyncStatus,
+};
 use crate::shared::flatpak::dry_run::DryRunPackage;
-use crate::shared::flatpak::info::RemoteInfo;
+use crate::shared::flatpak::info::{PackageInfo, RemoteInfo};
 use crate::shared::task::response::{OperationActivity, OperationStatus, TaskResponse, TaskResult};
-use crate::shared::task::{AppstreamTask, AppstreamTaskKind};
+use crate::shared::task::{AppstreamLookup, AppstreamTask, AppstreamTaskKind};
 use crate::shared::{path, WorkerError};
 
+/// Directories of the cached appstream icons, ordered by preference
+/// (hidpi icons first)
+const ICON_DIRS: [&str; 4] = ["128x128@2", "128x128", "64x64@2", "64x64"];
+
+/// An `Extension` group of the Flatpak metadata
+struct ExtensionPoint {
+    id: String,
+    versions: Vec<String>,
+    /// Whether refs with the id as prefix match too (eg.
+    /// `org.gimp.GIMP.Plugin.Resynthesizer`)
+    subdirectories: bool,
+}
+
 #[derive(Debug, Clone, Downgrade)]
 pub struct AppstreamWorker {
     sender: Arc<Sender<TaskResponse>>,
@@ -49,8 +67,10 @@ impl AppstreamWorker {
 
     pub fn process_task(&self, task: AppstreamTask) {
         let result = match &task.kind {
-            AppstreamTaskKind::Ensure => self.ensure(&task),
-            AppstreamTaskKind::Update => self.update(&task),
+            AppstreamTaskKind::Ensure => self.ensure(&task).map(|_| ()),
+            AppstreamTaskKind::Update => self.update(&task).map(|_| ()),
+            AppstreamTaskKind::Lookup(lookup) => self.lookup(&task, lookup),
+            AppstreamTaskKind::Addons(package) => self.addons(&task, package),
             _ => return,
         };
 
@@ -79,23 +99,14 @@ impl AppstreamWorker {
             .is_ok()
         {
             // Ensure that all known remotes are available from that silo
-            let mut is_missing_remote = false;
-
-            let installations = Self::all_installations();
-            'outer: for inst in installations {
+            let mut remote_hashes = Vec::new();
+            for inst in Self::all_installations() {
                 for remote in inst.list_remotes(Cancellable::NONE)? {
-                    if Self::query_remote(&silo, &remote).is_none() {
-                        debug!(
-                            "Silo is missing remote {:?}",
-                            remote.name().unwrap_or_default()
-                        );
-                        is_missing_remote = true;
-                        break 'outer;
-                    }
+                    remote_hashes.push(Self::remote_hash(&remote));
                 }
             }
 
-            if !is_missing_remote {
+            if !Self::is_missing_remote(&silo, &remote_hashes) {
                 debug!("Found silo with all remotes. Nothing to do.");
                 if task.kind != AppstreamTaskKind::Dependency {
                     let response = TaskResponse::new_result(task.clone().into(), TaskResult::Done);
@@ -159,38 +170,79 @@ impl AppstreamWorker {
         }
 
         let mut imported_source = false;
+        let mut stored_results = RemoteSyncResult::load();
+        let mut sync_results = Vec::new();
+
         for (remote_hash, (remote, inst)) in &remotes {
-            let remote_info = Some(RemoteInfo::from_flatpak(remote, inst));
-            let activity = OperationActivity::new_appstream(remote_info, OperationStatus::Updating);
+            let remote_info = RemoteInfo::from_flatpak(remote, inst);
+            let activity = OperationActivity::new_appstream(
+                Some(remote_info.clone()),
+                OperationStatus::Updating,
+            );
             let response = TaskResponse::new_activity(task.clone().into(), vec![activity]);
             self.sender.try_send(response).unwrap();
 
-            match Self::remote_builder_source(remote, inst) {
-                Ok(source) => {
-                    builder.import_source(&source);
-                    imported_source = true;
-                }
-                Err(err) => {
-                    warn!(
-                        "Skip remote {:?}: {}",
-                        remote.name().unwrap(),
-                        err.to_string()
-                    );
+            let last_success = stored_results
+                .get(remote_hash)
+                .and_then(|result| result.last_success);
+
+            let (status, last_success) = if remote.is_disabled() {
+                debug!("Skip remote {:?}: Disabled.", remote.name().unwrap());
+                builder.import_node(&Self::placeholder_node(remote_hash, "skipped", None));
+                (RemoteSyncStatus::Skipped, last_success)
+            } else if remote.appstream_dir(None).is_none() {
+                debug!(
+                    "Skip remote {:?}: No appstream data available.",
+                    remote.name().unwrap()
+                );
+                builder.import_node(&Self::placeholder_node(remote_hash, "skipped", None));
+                (RemoteSyncStatus::Skipped, last_success)
+            } else {
+                match Self::remote_builder_source(remote, inst) {
+                    Ok(source) => {
+                        builder.import_source(&source);
+                        imported_source = true;
 
-                    // Create empty placeholder entry
-                    let node = xb::BuilderNode::new("components");
-                    node.set_attr("origin", remote_hash);
-                    node.set_attr("error", &err.to_string());
-                    builder.import_node(&node);
+                        let now = glib::DateTime::now_utc().unwrap().to_unix();
+                        (RemoteSyncStatus::Success, Some(now))
+                    }
+                    Err(err) => {
+                        warn!(
+                            "Skip remote {:?}: {}",
+                            remote.name().unwrap(),
+                            err.to_string()
+                        );
+
+                        let error = err.to_string();
+                        let node = Self::placeholder_node(remote_hash, "failed", Some(&error));
+                        builder.import_node(&node);
+
+                        (RemoteSyncStatus::Failed(err.to_string()), last_success)
+                    }
                 }
-            }
+            };
+
+            let op_status = match status {
+                RemoteSyncStatus::Success => OperationStatus::Done,
+                RemoteSyncStatus::Skipped => OperationStatus::Skipped,
+                _ => OperationStatus::Failed,
+            };
 
-            let remote_info = Some(RemoteInfo::from_flatpak(remote, inst));
-            let activity = OperationActivity::new_appstream(remote_info, OperationStatus::Done);
+            let result = RemoteSyncResult::new(remote_info.clone(), status, last_success);
+            stored_results.insert(remote_hash.clone(), result.clone());
+            sync_results.push(result);
+
+            let activity = OperationActivity::new_appstream(Some(remote_info), op_status);
             let response = TaskResponse::new_activity(task.clone().into(), vec![activity]);
             self.sender.try_send(response).unwrap();
         }
 
+        // Remotes which got removed in the meantime don't need to be remembered
+        stored_results.retain(|hash, _| remotes.contains_key(hash));
+        if let Err(err) = RemoteSyncResult::store(&stored_results) {
+            warn!("Unable to store appstream sync status: {}", err.to_string());
+        }
+
         if !imported_source {
             let glib_error = glib::Error::new(flatpak::Error::Aborted, "");
             warn!("Unable to retrieve Flatpak appstream data.");
@@ -215,13 +267,221 @@ impl AppstreamWorker {
         self.sender.try_send(response).unwrap();
 
         if task.kind != AppstreamTaskKind::Dependency {
-            let response = TaskResponse::new_result(task.clone().into(), TaskResult::Done);
+            let result = TaskResult::DoneAppstreamSync(Box::new(sync_results));
+            let response = TaskResponse::new_result(task.clone().into(), result);
             self.sender.try_send(response).unwrap();
         }
 
         Ok(silo)
     }
 
+    /// Looks up all components which are matching the query, across all
+    /// remotes and installations
+    fn lookup(&self, task: &AppstreamTask, lookup: &AppstreamLookup) -> Result<(), WorkerError> {
+        debug!("Appstream lookup: {:?}", lookup);
+
+        // The silo gets only ensured as part of this task, so no result must be emitted
+        let dependency_task = AppstreamTask {
+            uuid: task.uuid.clone(),
+            kind: AppstreamTaskKind::Dependency,
+        };
+        let silo = self.ensure(&dependency_task)?;
+
+        let components = match lookup {
+            AppstreamLookup::Id(id) => Self::query_components_by_id(&silo, id),
+            AppstreamLookup::Ids(ids) => ids
+                .iter()
+                .flat_map(|id| Self::query_components_by_id(&silo, id))
+                .collect(),
+            AppstreamLookup::Provides(id) => Self::query_components_by_provides(&silo, id),
+            AppstreamLookup::Mimetype(mimetype) => {
+                Self::query_components_by_mimetype(&silo, mimetype)
+            }
+        };
+
+        let result = TaskResult::DoneAppstreamLookup(Box::new(components));
+        let response = TaskResponse::new_result(task.clone().into(), result);
+        self.sender.try_send(response).unwrap();
+
+        Ok(())
+    }
+
+    /// Discovers the addons of an app. These are either appstream `addon`
+    /// components which extend the app, or refs which match one of the
+    /// `Extension` points of the app metadata. Only addons which are provided
+    /// by the remote of the app are considered.
+    fn addons(&self, task: &AppstreamTask, package: &PackageInfo) -> Result<(), WorkerError> {
+        debug!("Appstream addons: {}", package.ref_);
+
+        // The silo gets only ensured as part of this task, so no result must be emitted
+        let dependency_task = AppstreamTask {
+            uuid: task.uuid.clone(),
+            kind: AppstreamTaskKind::Dependency,
+        };
+        let silo = self.ensure(&dependency_task)?;
+
+        let installation = Installation::from(package.remote.installation.as_ref().unwrap());
+        let remote = installation.remote_by_name(&package.remote.name, Cancellable::NONE)?;
+        let ref_ = Ref::parse(&package.ref_)?;
+        let name = ref_.name().unwrap().to_string();
+        let arch = ref_.arch().unwrap().to_string();
+
+        let mut addons: IndexMap<String, AddonInfo> = IndexMap::new();
+        let new_addon = |addon_ref: &str| AddonInfo {
+            package: PackageInfo::new(addon_ref.to_string(), package.remote.clone()),
+            ..Default::default()
+        };
+
+        // Refs which match an extension point of the app. Only the cached remote
+        // summary gets used, the appstream data was already updated anyway.
+        let remote_refs = installation
+            .list_remote_refs_sync_full(
+                &package.remote.name,
+                flatpak::QueryFlags::ONLY_CACHED,
+                Cancellable::NONE,
+            )
+            .unwrap_or_default();
+
+        for point in Self::extension_points(&installation, &ref_, &package.remote.name) {
+            for remote_ref in &remote_refs {
+                let ref_name = remote_ref.name().unwrap_or_default();
+                let matches_name = ref_name == point.id
+                    || (point.subdirectories && ref_name.starts_with(&format!("{}.", point.id)));
+                let matches_arch = remote_ref.arch().as_deref() == Some(arch.as_str());
+                let matches_version = point
+                    .versions
+                    .iter()
+                    .any(|v| remote_ref.branch().as_deref() == Some(v.as_str()));
+
+                if matches_name && matches_arch && matches_version {
+                    let addon_ref = remote_ref.format_ref().unwrap().to_string();
+                    addons
+                        .entry(addon_ref.clone())
+                        .or_insert_with(|| new_addon(&addon_ref))
+                        .extension_point = Some(point.id.clone());
+                }
+            }
+        }
+
+        // Appstream components which extend the app
+        let remote_hash = Self::remote_hash(&remote);
+        let nodes = Self::query_value(&name).and_then(|name| {
+            let xpath = format!(
+                "components[@origin='{remote_hash}']/component[@type='addon']/extends[text()='{name}']/.."
+            );
+            silo.query(&xpath, 0).ok()
+        });
+        for node in nodes.unwrap_or_default() {
+            let Ok(bundle) = node.query_text("bundle") else {
+                continue;
+            };
+            let Ok(addon_ref) = Ref::parse(&bundle) else {
+                continue;
+            };
+            if addon_ref.arch().as_deref() != Some(arch.as_str()) {
+                continue;
+            }
+
+            let addon = addons
+                .entry(bundle.to_string())
+                .or_insert_with(|| new_addon(&bundle));
+
+            if let Some(component) = Self::node_component(&node) {
+                if let Some(components_node) = node.parent() {
+                    let addon_name = addon_ref.name().unwrap().to_string();
+                    addon.icon =
+                        Self::component_icon(&components_node, &addon_name, Some(&component));
+                }
+
+                addon.appstream_component = Some(serde_json::to_string(&component).unwrap());
+                addon.relations = Self::node_relations(&node);
+            }
+        }
+
+        // Installed state
+        for addon in addons.values_mut() {
+            let addon_ref = Ref::parse(&addon.package.ref_)?;
+            addon.is_installed = installation
+                .installed_ref(
+                    addon_ref.kind(),
+                    &addon_ref.name().unwrap(),
+                    addon_ref.arch().as_deref(),
+                    addon_ref.branch().as_deref(),
+                    Cancellable::NONE,
+                )
+                .is_ok();
+        }
+
+        let addons = addons.into_values().collect();
+        let result = TaskResult::DoneAddons(Box::new(addons));
+        let response = TaskResponse::new_result(task.clone().into(), result);
+        self.sender.try_send(response).unwrap();
+
+        Ok(())
+    }
+
+    /// Returns the `Extension` points of the app metadata. The metadata of
+    /// the installed ref is preferred, otherwise it gets fetched from the
+    /// remote.
+    fn extension_points(
+        installation: &Installation,
+        ref_: &Ref,
+        remote_name: &str,
+    ) -> Vec<ExtensionPoint> {
+        let name = ref_.name().unwrap();
+        let branch = ref_.branch().unwrap().to_string();
+
+        let metadata = installation
+            .installed_ref(
+                ref_.kind(),
+                &name,
+                ref_.arch().as_deref(),
+                Some(&branch),
+                Cancellable::NONE,
+            )
+            .and_then(|installed| installed.load_metadata(Cancellable::NONE))
+            .or_else(|_| {
+                installation.fetch_remote_metadata_sync(remote_name, ref_, Cancellable::NONE)
+            });
+
+        let keyfile = glib::KeyFile::new();
+        let loaded =
+            metadata.and_then(|bytes| keyfile.load_from_bytes(&bytes, glib::KeyFileFlags::NONE));
+        if let Err(err) = loaded {
+            warn!("Unable to load metadata of {name}: {}", err.message());
+            return Vec::new();
+        }
+
+        let mut points = Vec::new();
+        for group in keyfile.groups() {
+            let group = group.as_str();
+            let Some(id) = group.strip_prefix("Extension ") else {
+                continue;
+            };
+
+            // Those subrefs get handled by Flatpak automatically
+            if id.ends_with(".Locale") || id.ends_with(".Debug") || id.ends_with(".Sources") {
+                continue;
+            }
+
+            let versions = if let Ok(versions) = keyfile.string(group, "versions") {
+                versions.split(';').map(str::to_string).collect()
+            } else if let Ok(version) = keyfile.string(group, "version") {
+                vec![version.to_string()]
+            } else {
+                vec![branch.clone()]
+            };
+
+            points.push(ExtensionPoint {
+                id: id.to_string(),
+                versions,
+                subdirectories: keyfile.boolean(group, "subdirectories").unwrap_or(false),
+            });
+        }
+
+        points
+    }
+
     fn remote_builder_source(
         remote: &Remote,
         installation: &Installation,
@@ -322,18 +582,20 @@ impl AppstreamWorker {
 
         // Component
         let query = format!("{}/{}/{}/{}", kind, name, arch, branch);
-        if let Some(component) = Self::query_component(&silo, &query, remote)? {
-            package.appstream_component = Some(serde_json::to_string(&component).unwrap());
+        let node = Self::query_component(&silo, &query, remote);
+        let component = node.as_ref().and_then(Self::node_component);
+        if let Some(component) = &component {
+            package.appstream_component = Some(serde_json::to_string(component).unwrap());
+        } else if node.is_some() {
+            warn!("Couldn't parse appstream component for {query}");
+        }
+        if let Some(node) = &node {
+            package.relations = Self::node_relations(node);
         }
 
         // Icon
         if let Some(node) = Self::query_remote(&silo, remote) {
-            let appstream_path = gio::File::for_parse_name(&node.attr("path"));
-            let icon_file = appstream_path.child(format!("icons/128x128/{}.png", name));
-
-            if let Ok((bytes, _)) = icon_file.load_bytes(Cancellable::NONE) {
-                package.icon = Some(bytes.to_vec());
-            }
+            package.icon = Self::component_icon(&node, &name, component.as_ref());
         } else {
             warn!("Unable to set icon for dry-run package, remote does not exist in silo.");
         }
@@ -341,37 +603,238 @@ impl AppstreamWorker {
         Ok(())
     }
 
-    fn query_component(
-        silo: &xb::Silo,
-        ref_str: &str,
-        remote: &Remote,
-    ) -> Result<Option<Component>, WorkerError> {
+    /// Returns the project license of a ref from the cached silo, without
+    /// syncing the appstream data
+    pub fn project_license(ref_str: &str, remote: &Remote) -> Option<String> {
+        let xmlb = gio::File::for_path(path::APPSTREAM_CACHE.clone());
+        let silo = xb::Silo::new();
+        silo.load_from_file(&xmlb, xb::SiloLoadFlags::NONE, Cancellable::NONE)
+            .ok()?;
+
+        let node = Self::query_component(&silo, ref_str, remote)?;
+        Self::node_component(&node)?.project_license.map(|l| l.0)
+    }
+
+    /// Returns the component node of a ref which is provided by the remote
+    fn query_component(silo: &xb::Silo, ref_str: &str, remote: &Remote) -> Option<xb::Node> {
         let ref_escaped = ref_str.replace('/', r"\/");
         let remote_hash = Self::remote_hash(remote);
         let xpath = format!(
             "components[@origin='{remote_hash}']/component/bundle[text()='{ref_escaped}']/.."
         );
 
-        if let Ok(node) = silo.query_first(&xpath) {
-            let xml = node.export(xb::NodeExportFlags::NONE).unwrap().to_string();
-            let element = appstream::xmltree::Element::parse(xml.as_bytes()).unwrap();
+        silo.query_first(&xpath).ok()
+    }
 
-            if let Ok(component) = Component::try_from(&element) {
-                return Ok(Some(component));
-            } else {
-                warn!("Couldn't find appstream component for {ref_str}");
+    /// Returns all components with the given component id
+    fn query_components_by_id(silo: &xb::Silo, id: &str) -> Vec<ComponentInfo> {
+        let Some(id) = Self::query_value(id) else {
+            return Vec::new();
+        };
+
+        let xpath = format!("components/component/id[text()='{id}']/..");
+        Self::query_components(silo, &[xpath])
+    }
+
+    /// Returns all components which have the given id in `<provides>`
+    fn query_components_by_provides(silo: &xb::Silo, id: &str) -> Vec<ComponentInfo> {
+        let Some(id) = Self::query_value(id) else {
+            return Vec::new();
+        };
+
+        let xpath = format!("components/component/provides/id[text()='{id}']/../..");
+        Self::query_components(silo, &[xpath])
+    }
+
+    /// Returns all components which are able to handle the given mimetype
+    fn query_components_by_mimetype(silo: &xb::Silo, mimetype: &str) -> Vec<ComponentInfo> {
+        let Some(mimetype) = Self::query_value(mimetype) else {
+            return Vec::new();
+        };
+
+        let xpaths = [
+            format!("components/component/provides/mediatype[text()='{mimetype}']/../.."),
+            // Legacy `<mimetypes>` element, which is still used by a lot of components
+            format!("components/component/mimetypes/mimetype[text()='{mimetype}']/../.."),
+        ];
+        Self::query_components(silo, &xpaths)
+    }
+
+    /// Returns the components for all nodes matching the xpaths. A component
+    /// is returned for each remote (in all installations) which provides it.
+    fn query_components(silo: &xb::Silo, xpaths: &[String]) -> Vec<ComponentInfo> {
+        let mut nodes = Vec::new();
+        for xpath in xpaths {
+            // libxmlb returns an error if there are no results
+            if let Ok(mut result) = silo.query(xpath, 0) {
+                nodes.append(&mut result);
+            }
+        }
+
+        let installations = Self::all_installations();
+        let mut components: Vec<ComponentInfo> = Vec::new();
+
+        for node in nodes {
+            let Some(components_node) = node.parent() else {
+                continue;
+            };
+            let Ok(bundle) = node.query_text("bundle") else {
+                continue;
+            };
+            let Ok(ref_) = Ref::parse(&bundle) else {
+                warn!("Unable to parse bundle ref {bundle:?}");
+                continue;
+            };
+            let Some(component) = Self::node_component(&node) else {
+                continue;
+            };
+
+            let name = ref_.name().unwrap().to_string();
+            let icon = Self::component_icon(&components_node, &name, Some(&component));
+            let json = serde_json::to_string(&component).unwrap();
+            let relations = Self::node_relations(&node);
+
+            let origin = components_node.attr("origin");
+            for (remote, inst) in Self::remotes_by_hash(&installations, &origin) {
+                let remote_info = RemoteInfo::from_flatpak(&remote, &inst);
+                let package = PackageInfo::new(bundle.to_string(), remote_info);
+
+                // A component can be matched by multiple xpaths
+                if components.iter().any(|c| c.package == package) {
+                    continue;
+                }
+
+                components.push(ComponentInfo {
+                    package,
+                    icon: icon.clone(),
+                    appstream_component: json.clone(),
+                    relations: relations.clone(),
+                });
+            }
+        }
+
+        components
+    }
+
+    fn node_element(node: &xb::Node) -> Option<Element> {
+        let xml = node.export(xb::NodeExportFlags::NONE).ok()?.to_string();
+        Element::parse(xml.as_bytes()).ok()
+    }
+
+    fn node_component(node: &xb::Node) -> Option<Component> {
+        Component::try_from(&Self::node_element(node)?).ok()
+    }
+
+    fn node_relations(node: &xb::Node) -> ComponentRelations {
+        Self::node_element(node)
+            .map(|element| ComponentRelations::from_element(&element))
+            .unwrap_or_default()
+    }
+
+    /// Loads the icon of a component. Cached icons from the appstream
+    /// directory of the remote (`components_node`) are preferred, with the
+    /// largest size first. Otherwise a remote icon gets downloaded.
+    fn component_icon(
+        components_node: &xb::Node,
+        name: &str,
+        component: Option<&Component>,
+    ) -> Option<Vec<u8>> {
+        let appstream_path = gio::File::for_parse_name(&components_node.attr("path"));
+
+        // The cached icon file name usually matches the ref name, but
+        // components are allowed to specify a different one
+        let mut file_names = vec![format!("{name}.png")];
+        if let Some(component) = component {
+            for icon in &component.icons {
+                if let Icon::Cached { path, .. } = icon {
+                    let file_name = path.to_string_lossy().to_string();
+                    if !file_names.contains(&file_name) {
+                        file_names.push(file_name);
+                    }
+                }
+            }
+        }
+
+        for dir in ICON_DIRS {
+            for file_name in &file_names {
+                let icon_file = appstream_path.child(format!("icons/{dir}/{file_name}"));
+
+                if let Ok((bytes, _)) = icon_file.load_bytes(Cancellable::NONE) {
+                    return Some(bytes.to_vec());
+                }
+            }
+        }
+
+        // Remote icon, with the largest size first
+        let mut remote_icons: Vec<(&url::Url, u32)> = component?
+            .icons
+            .iter()
+            .filter_map(|icon| match icon {
+                Icon::Remote { url, width, .. } => Some((url, width.unwrap_or(0))),
+                _ => None,
+            })
+            .collect();
+        remote_icons.sort_by_key(|(_, width)| std::cmp::Reverse(*width));
+
+        for (url, _) in remote_icons {
+            match MediaCache::icons().fetch(url.as_str()) {
+                Ok(path) => return std::fs::read(path).ok(),
+                Err(err) => warn!("Unable to retrieve remote icon {url}: {err}"),
             }
         }
 
-        Ok(None)
+        None
+    }
+
+    /// Returns the value if it can be safely used as string literal in a
+    /// xpath query. The xpath syntax of libxmlb has no escape sequences, so
+    /// values which contain quotes get rejected. Valid ids and mimetypes
+    /// never contain them.
+    fn query_value(value: &str) -> Option<&str> {
+        if value.contains(['\'', '"']) {
+            warn!("Rejecting appstream query value {value:?}");
+            return None;
+        }
+
+        Some(value)
     }
 
     fn query_remote(silo: &xb::Silo, remote: &Remote) -> Option<xb::Node> {
-        let remote_hash = Self::remote_hash(remote);
+        Self::query_origin(silo, &Self::remote_hash(remote))
+    }
+
+    /// Whether any of the remotes isn't included in the silo. Skipped and
+    /// failed remotes are included as placeholder.
+    fn is_missing_remote(silo: &xb::Silo, remote_hashes: &[String]) -> bool {
+        match remote_hashes
+            .iter()
+            .find(|hash| Self::query_origin(silo, hash).is_none())
+        {
+            Some(hash) => {
+                debug!("Silo is missing remote {hash}");
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn query_origin(silo: &xb::Silo, remote_hash: &str) -> Option<xb::Node> {
         let xpath = format!("components[@origin='{remote_hash}']");
         silo.query_first(&xpath).ok()
     }
 
+    /// Empty entry for a remote without appstream data, so that the remote
+    /// doesn't count as missing from the silo and doesn't get synced again
+    fn placeholder_node(remote_hash: &str, status: &str, error: Option<&str>) -> xb::BuilderNode {
+        let node = xb::BuilderNode::new("components");
+        node.set_attr("origin", remote_hash);
+        node.set_attr("status", status);
+        if let Some(error) = error {
+            node.set_attr("error", error);
+        }
+        node
+    }
+
     // Based on the gnome-software fixups
     // https://gitlab.gnome.org/GNOME/gnome-software/-/blob/35e2d0e4191d0c81bf48e5f05bbb1c110572f917/plugins/flatpak/gs-flatpak.c#L677
     fn add_source_fixups(source: &xb::BuilderSource, remote: &Remote) {
@@ -485,7 +948,22 @@ impl AppstreamWorker {
         hasher.finish().to_string()
     }
 
-    fn all_installations() -> Vec<Installation> {
+    /// Returns all remotes (in all installations) with the given remote hash
+    fn remotes_by_hash(installations: &[Installation], hash: &str) -> Vec<(Remote, Installation)> {
+        let mut remotes = Vec::new();
+
+        for inst in installations {
+            for remote in inst.list_remotes(Cancellable::NONE).unwrap_or_default() {
+                if Self::remote_hash(&remote) == hash {
+                    remotes.push((remote, inst.clone()));
+                }
+            }
+        }
+
+        remotes
+    }
+
+    pub fn all_installations() -> Vec<Installation> {
         let mut installations = Vec::new();
 
         // User installation
@@ -504,3 +982,34 @@ impl AppstreamWorker {
         installations
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn skipped_remote_is_not_missing() {
+        let builder = xb::Builder::new();
+        builder.import_node(&AppstreamWorker::placeholder_node(
+            "disabled", "skipped", None,
+        ));
+        builder.import_node(&AppstreamWorker::placeholder_node(
+            "broken",
+            "failed",
+            Some("No network"),
+        ));
+        let silo = builder
+            .compile(xb::BuilderCompileFlags::NONE, Cancellable::NONE)
+            .unwrap();
+
+        // A second `ensure()` only syncs again if a remote is missing
+        let hashes = ["disabled".to_string(), "broken".to_string()];
+        assert!(!AppstreamWorker::is_missing_remote(&silo, &hashes));
+
+        let node = AppstreamWorker::query_origin(&silo, "disabled").unwrap();
+        assert_eq!(node.attr("status").as_str(), "skipped");
+
+        let hashes = ["disabled".to_string(), "unknown".to_string()];
+        assert!(AppstreamWorker::is_missing_remote(&silo, &hashes));
+    }
+}
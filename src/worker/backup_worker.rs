@@ -0,0 +1,331 @@
+// Souk - backup_worker.rs
+// Copyright (C) 2024  Felix Häcker <haeckerfelix@gnome.org>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::Path;
+use std::sync::Arc;
+
+use async_std::channel::Sender;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use flatpak::prelude::*;
+use flatpak::{Installation, Ref};
+use gio::Cancellable;
+use glib::Downgrade;
+use gtk::{gio, glib};
+
+use crate::shared::flatpak::backup::{
+    BackupManifest, BackupOperationKind, BACKUP_MANIFEST, BACKUP_VERSION,
+};
+use crate::shared::flatpak::info::{InstallationInfo, PackageInfo, RemoteInfo};
+use crate::shared::path;
+use crate::shared::task::response::{OperationActivity, OperationStatus, TaskResponse, TaskResult};
+use crate::shared::task::{BackupTask, BackupTaskKind, DataRemoval, FlatpakTask};
+use crate::shared::WorkerError;
+use crate::worker::{DataWorker, SkWorkerApplication};
+
+type Archive = tar::Archive<GzDecoder<File>>;
+
+#[derive(Debug, Clone, Downgrade)]
+pub struct BackupWorker {
+    sender: Arc<Sender<TaskResponse>>,
+}
+
+impl BackupWorker {
+    pub fn new(sender: Sender<TaskResponse>) -> Self {
+        Self {
+            sender: Arc::new(sender),
+        }
+    }
+
+    pub fn process_task(&self, task: BackupTask) {
+        let result = match &task.kind {
+            BackupTaskKind::Export { packages, path } => {
+                self.export(&task, packages, Path::new(path))
+            }
+            BackupTaskKind::Import { path, installation } => {
+                self.import(&task, Path::new(path), installation)
+            }
+            BackupTaskKind::None => return,
+        };
+
+        let result = match result {
+            Ok(()) => TaskResult::Done,
+            Err(err) => TaskResult::Error(Box::new(err)),
+        };
+
+        let response = TaskResponse::new_result(task.into(), result);
+        self.sender.try_send(response).unwrap();
+    }
+
+    pub fn cancel_task(&self, task_uuid: &str) {
+        warn!("Unable to cancel backup task: {}", task_uuid);
+    }
+
+    fn export(
+        &self,
+        task: &BackupTask,
+        packages: &[PackageInfo],
+        path: &Path,
+    ) -> Result<(), WorkerError> {
+        info!("Export backup: {}", path.display());
+
+        for package in packages {
+            self.send_activity(task, package, OperationStatus::Pending);
+        }
+
+        let result = self.write_archive(task, packages, path);
+
+        // Don't leave an incomplete archive behind
+        if result.is_err() {
+            let _ = fs::remove_file(path);
+        }
+
+        result
+    }
+
+    fn write_archive(
+        &self,
+        task: &BackupTask,
+        packages: &[PackageInfo],
+        path: &Path,
+    ) -> Result<(), WorkerError> {
+        let file = File::create(path)?;
+        let mut builder = tar::Builder::new(GzEncoder::new(file, Compression::default()));
+        // Symlinks can point outside of the data directory
+        builder.follow_symlinks(false);
+
+        let manifest = BackupManifest::new(packages.to_vec());
+        let manifest = serde_json::to_vec_pretty(&manifest).unwrap();
+
+        let mut header = tar::Header::new_gnu();
+        header.set_size(manifest.len() as u64);
+        header.set_mode(0o644);
+        builder.append_data(&mut header, BACKUP_MANIFEST, manifest.as_slice())?;
+
+        for package in packages {
+            self.send_activity(task, package, OperationStatus::Processing);
+
+            let data_dir = DataWorker::app_data_dir(package)?;
+            if data_dir.exists() {
+                let app_id = data_dir.file_name().unwrap();
+                builder.append_dir_all(app_id, &data_dir)?;
+            }
+
+            self.send_activity(task, package, OperationStatus::Done);
+        }
+
+        builder.into_inner()?.finish()?;
+        Ok(())
+    }
+
+    fn import(
+        &self,
+        task: &BackupTask,
+        path: &Path,
+        installation_info: &InstallationInfo,
+    ) -> Result<(), WorkerError> {
+        info!("Import backup: {}", path.display());
+
+        let manifest = Self::read_manifest(path)?;
+        let installation = Installation::from(installation_info);
+
+        // The packages get restored into the selected installation
+        let packages: Vec<PackageInfo> = manifest
+            .packages
+            .iter()
+            .map(|package| {
+                let remote = RemoteInfo::new(
+                    package.remote.name.clone(),
+                    package.remote.repository_url.clone(),
+                    Some(installation_info.clone()),
+                );
+                PackageInfo::new(package.ref_.clone(), remote)
+            })
+            .collect();
+
+        for package in &packages {
+            self.send_activity(task, package, OperationStatus::Pending);
+        }
+
+        // Install missing apps through the regular transaction path. The
+        // transaction reports its progress as part of this task. A failed
+        // installation doesn't abort the import, the data gets restored anyway.
+        let flatpak_worker = SkWorkerApplication::default().flatpak_worker();
+        let mut failed = Vec::new();
+        for package in &packages {
+            let f_ref = Ref::parse(&package.ref_)?;
+            let installed = installation
+                .installed_ref(
+                    f_ref.kind(),
+                    &f_ref.name().unwrap(),
+                    f_ref.arch().as_deref(),
+                    f_ref.branch().as_deref(),
+                    Cancellable::NONE,
+                )
+                .is_ok();
+
+            if installed {
+                continue;
+            }
+
+            if installation
+                .remote_by_name(&package.remote.name, Cancellable::NONE)
+                .is_err()
+            {
+                // Remotes don't get added automatically, since the archive
+                // doesn't contain their signing keys
+                warn!(
+                    "Unable to install {}, remote \"{}\" is not configured. Only the data gets restored.",
+                    package.ref_, package.remote.name
+                );
+                failed.push(format!(
+                    "{} (remote \"{}\" at {} is not configured)",
+                    package.ref_, package.remote.name, package.remote.repository_url
+                ));
+                continue;
+            }
+
+            let mut flatpak_task = FlatpakTask::new_install(package, false, false);
+            flatpak_task.uuid = task.uuid.clone();
+            match flatpak_worker.install_flatpak(&flatpak_task, true) {
+                Ok(()) => (),
+                Err(err @ WorkerError::GLibCancelled(_)) => return Err(err),
+                Err(err) => {
+                    warn!("Unable to install {}: {err}", package.ref_);
+                    failed.push(format!("{} ({err})", package.ref_));
+                }
+            }
+        }
+
+        self.restore_data(task, path, &packages)?;
+
+        if !failed.is_empty() {
+            let msg = format!("Unable to install {}", failed.join(", "));
+            return Err(WorkerError::Backup(msg));
+        }
+
+        Ok(())
+    }
+
+    fn restore_data(
+        &self,
+        task: &BackupTask,
+        path: &Path,
+        packages: &[PackageInfo],
+    ) -> Result<(), WorkerError> {
+        let mut by_app_id = HashMap::new();
+        for package in packages {
+            let app_id = Ref::parse(&package.ref_)?.name().unwrap().to_string();
+            by_app_id.insert(app_id, package);
+        }
+
+        fs::create_dir_all(path::APP_DATA_DIR.as_path())?;
+
+        let mut restored = HashSet::new();
+        let mut current: Option<&PackageInfo> = None;
+
+        let mut archive = Self::open_archive(path)?;
+        for entry in archive.entries()? {
+            let mut entry = entry?;
+            let entry_path = entry.path()?.into_owned();
+
+            // Only the data directories of the apps in the manifest get restored
+            let Some(app_id) = entry_path
+                .components()
+                .next()
+                .map(|c| c.as_os_str().to_string_lossy().to_string())
+            else {
+                continue;
+            };
+            let Some(package) = by_app_id.get(&app_id).copied() else {
+                continue;
+            };
+
+            if restored.insert(app_id.clone()) {
+                if let Some(previous) = current.replace(package) {
+                    self.send_activity(task, previous, OperationStatus::Done);
+                }
+                self.send_activity(task, package, OperationStatus::Processing);
+
+                // Existing data gets moved aside instead of getting mixed with the
+                // restored data
+                let data_dir = path::APP_DATA_DIR.join(&app_id);
+                DataWorker::remove_data_dir(&data_dir, DataRemoval::Backup)?;
+            }
+
+            // Refuses to unpack entries outside of the target directory
+            entry.unpack_in(path::APP_DATA_DIR.as_path())?;
+        }
+
+        if let Some(previous) = current {
+            self.send_activity(task, previous, OperationStatus::Done);
+        }
+
+        // Apps without any data in the archive
+        for (app_id, package) in &by_app_id {
+            if !restored.contains(app_id) {
+                self.send_activity(task, package, OperationStatus::Skipped);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn open_archive(path: &Path) -> Result<Archive, WorkerError> {
+        let file = File::open(path)?;
+        Ok(tar::Archive::new(GzDecoder::new(file)))
+    }
+
+    fn read_manifest(path: &Path) -> Result<BackupManifest, WorkerError> {
+        let mut archive = Self::open_archive(path)?;
+        for entry in archive.entries()? {
+            let mut entry = entry?;
+            if entry.path()? != Path::new(BACKUP_MANIFEST) {
+                continue;
+            }
+
+            let mut json = String::new();
+            entry.read_to_string(&mut json)?;
+
+            let manifest: BackupManifest =
+                serde_json::from_str(&json).map_err(|err| WorkerError::Backup(err.to_string()))?;
+
+            if manifest.version != BACKUP_VERSION {
+                let msg = format!("Unsupported version {}", manifest.version);
+                return Err(WorkerError::Backup(msg));
+            }
+
+            return Ok(manifest);
+        }
+
+        Err(WorkerError::Backup(format!("{BACKUP_MANIFEST} is missing")))
+    }
+
+    fn send_activity(&self, task: &BackupTask, package: &PackageInfo, status: OperationStatus) {
+        let operation = match task.kind {
+            BackupTaskKind::Export { .. } => BackupOperationKind::Export,
+            _ => BackupOperationKind::Restore,
+        };
+
+        let activity = OperationActivity::new_backup(package, operation, status);
+        let response = TaskResponse::new_activity(task.clone().into(), vec![activity]);
+        self.sender.try_send(response).unwrap();
+    }
+}
@@ -0,0 +1,377 @@
+// Souk - data_worker.rs
+// Copyright (C) 2024  Felix Häcker <haeckerfelix@gnome.org>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use async_std::channel::Sender;
+use flatpak::prelude::*;
+use flatpak::{Installation, InstalledRef, Ref, RefKind};
+use gio::Cancellable;
+use glib::{Downgrade, KeyFile};
+use gtk::prelude::*;
+use gtk::{gio, glib};
+
+use crate::shared::flatpak::info::{InstallationInfo, PackageInfo};
+use crate::shared::flatpak::{DiskUsage, RefDiskUsage};
+use crate::shared::path;
+use crate::shared::task::response::{TaskResponse, TaskResult};
+use crate::shared::task::{DataRemoval, DataTask, DataTaskKind};
+use crate::shared::WorkerError;
+use crate::worker::AppstreamWorker;
+
+/// Number of data backups which get kept per app
+const MAX_APP_DATA_BACKUPS: usize = 3;
+
+/// `errno` of a rename across filesystems
+const EXDEV: i32 = 18;
+
+#[derive(Debug, Clone, Downgrade)]
+pub struct DataWorker {
+    sender: Arc<Sender<TaskResponse>>,
+}
+
+impl DataWorker {
+    pub fn new(sender: Sender<TaskResponse>) -> Self {
+        Self {
+            sender: Arc::new(sender),
+        }
+    }
+
+    pub fn process_task(&self, task: DataTask) {
+        let result = match task.kind {
+            DataTaskKind::DiskUsage => self
+                .disk_usage(&task.package)
+                .map(|usage| TaskResult::DoneDiskUsage(Box::new(usage))),
+            DataTaskKind::ClearCache => self.clear_cache(&task.package, task.dry_run),
+            DataTaskKind::ResetData(removal) => {
+                self.reset_data(&task.package, removal, task.dry_run)
+            }
+            DataTaskKind::None => return,
+        };
+
+        let result = match result {
+            Ok(result) => result,
+            Err(err) => TaskResult::Error(Box::new(err)),
+        };
+
+        let response = TaskResponse::new_result(task.into(), result);
+        self.sender.try_send(response).unwrap();
+    }
+
+    pub fn cancel_task(&self, task_uuid: &str) {
+        warn!("Unable to cancel data task: {}", task_uuid);
+    }
+
+    /// Returns the sandbox data directory of an app, eg.
+    /// `~/.var/app/de.haeckerfelix.Souk`
+    pub fn app_data_dir(package: &PackageInfo) -> Result<PathBuf, WorkerError> {
+        let name = Ref::parse(&package.ref_)?.name().unwrap().to_string();
+        Ok(path::APP_DATA_DIR.join(name))
+    }
+
+    fn disk_usage(&self, package: &PackageInfo) -> Result<DiskUsage, WorkerError> {
+        info!("Calculate disk usage: {}", package.ref_);
+
+        let f_inst = Installation::from(package.remote.installation.as_ref().unwrap());
+        let f_ref = Ref::parse(&package.ref_)?;
+        let installed_ref = f_inst.installed_ref(
+            f_ref.kind(),
+            &f_ref.name().unwrap(),
+            f_ref.arch().as_deref(),
+            f_ref.branch().as_deref(),
+            Cancellable::NONE,
+        )?;
+
+        let installed: HashMap<String, InstalledRef> = f_inst
+            .list_installed_refs(Cancellable::NONE)?
+            .into_iter()
+            .filter_map(|r| Some((r.format_ref()?.to_string(), r)))
+            .collect();
+
+        // Refs which are used by any other installed app
+        let mut used_by_others = HashSet::new();
+        for (ref_, other) in &installed {
+            if other.kind() == RefKind::App && ref_ != &package.ref_ {
+                used_by_others.extend(Self::used_refs(&f_inst, other, &installed));
+            }
+        }
+
+        let mut exclusive_refs: Vec<RefDiskUsage> =
+            Self::used_refs(&f_inst, &installed_ref, &installed)
+                .into_iter()
+                .filter(|ref_| !used_by_others.contains(ref_))
+                .filter_map(|ref_| {
+                    let size = installed.get(&ref_)?.installed_size();
+                    Some(RefDiskUsage { ref_, size })
+                })
+                .collect();
+        exclusive_refs.sort_by(|a, b| b.size.cmp(&a.size));
+
+        let data_dir = Self::app_data_dir(package)?;
+
+        Ok(DiskUsage {
+            deploy_size: installed_ref.installed_size(),
+            exclusive_refs,
+            cache_size: Self::dir_size(&data_dir.join("cache")),
+            config_size: Self::dir_size(&data_dir.join("config")),
+            data_size: Self::dir_size(&data_dir.join("data")),
+        })
+    }
+
+    fn clear_cache(&self, package: &PackageInfo, dry_run: bool) -> Result<TaskResult, WorkerError> {
+        let cache_dir = Self::app_data_dir(package)?.join("cache");
+        if dry_run {
+            return Ok(TaskResult::DoneDataDryRun(Self::dir_size(&cache_dir)));
+        }
+
+        info!("Clear cache: {}", cache_dir.display());
+        if !cache_dir.exists() {
+            return Ok(TaskResult::Done);
+        }
+
+        // Only the content gets removed, the app expects the directory to exist
+        for entry in fs::read_dir(&cache_dir)? {
+            let entry = entry?;
+            if entry.file_type()?.is_dir() {
+                fs::remove_dir_all(entry.path())?;
+            } else {
+                fs::remove_file(entry.path())?;
+            }
+        }
+
+        Ok(TaskResult::Done)
+    }
+
+    fn reset_data(
+        &self,
+        package: &PackageInfo,
+        removal: DataRemoval,
+        dry_run: bool,
+    ) -> Result<TaskResult, WorkerError> {
+        let data_dir = Self::app_data_dir(package)?;
+        if dry_run {
+            return Ok(TaskResult::DoneDataDryRun(Self::dir_size(&data_dir)));
+        }
+
+        Self::remove_data_dir(&data_dir, removal)?;
+        Ok(TaskResult::Done)
+    }
+
+    /// Whether the data directory of the app is still used by another installed
+    /// ref with the same app id, e.g. a different branch or the same app in a
+    /// different installation. The ref itself in `installation` is ignored.
+    pub fn is_data_shared(
+        ref_: &str,
+        installation: &InstallationInfo,
+    ) -> Result<bool, WorkerError> {
+        let f_ref = Ref::parse(ref_)?;
+        let name = f_ref.name().unwrap();
+
+        for f_inst in AppstreamWorker::all_installations() {
+            let is_own = InstallationInfo::from(&f_inst).path == installation.path;
+            let installed = f_inst.list_installed_refs_by_kind(RefKind::App, Cancellable::NONE)?;
+
+            let shared = installed.iter().any(|r| {
+                r.name().as_deref() == Some(name.as_str())
+                    && !(is_own && r.format_ref().as_deref() == Some(ref_))
+            });
+            if shared {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+
+    /// Moves an app data directory into the trash or into a timestamped
+    /// backup, or deletes it. Nothing happens if the directory doesn't exist.
+    /// Older backups of the app get removed.
+    pub fn remove_data_dir(data_dir: &Path, removal: DataRemoval) -> Result<(), WorkerError> {
+        if !data_dir.exists() {
+            return Ok(());
+        }
+
+        match removal {
+            DataRemoval::Trash => {
+                info!("Move data to trash: {}", data_dir.display());
+                gio::File::for_path(data_dir).trash(Cancellable::NONE)?;
+            }
+            DataRemoval::Backup => {
+                let name = data_dir.file_name().unwrap().to_string_lossy();
+                let timestamp = glib::DateTime::now_local()
+                    .and_then(|now| now.format("%Y%m%d-%H%M%S"))
+                    .map_err(|err| WorkerError::GLib(err.to_string()))?;
+
+                fs::create_dir_all(path::APP_DATA_BACKUP_DIR.as_path())?;
+                let backup_dir = path::APP_DATA_BACKUP_DIR.join(format!("{name}-{timestamp}"));
+
+                info!(
+                    "Move data to backup: {} -> {}",
+                    data_dir.display(),
+                    backup_dir.display()
+                );
+                match fs::rename(data_dir, &backup_dir) {
+                    Ok(()) => (),
+                    // The backup dir is on a different filesystem than the data
+                    Err(err) if err.raw_os_error() == Some(EXDEV) => {
+                        if let Err(err) = Self::copy_dir(data_dir, &backup_dir) {
+                            let _ = fs::remove_dir_all(&backup_dir);
+                            return Err(err.into());
+                        }
+                        fs::remove_dir_all(data_dir)?;
+                    }
+                    Err(err) => return Err(err.into()),
+                }
+
+                Self::prune_backups(&name);
+            }
+            DataRemoval::Delete => {
+                info!("Delete data: {}", data_dir.display());
+                fs::remove_dir_all(data_dir)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Copies a directory recursively, symlinks get copied as they are
+    fn copy_dir(source: &Path, target: &Path) -> std::io::Result<()> {
+        fs::create_dir(target)?;
+
+        for entry in fs::read_dir(source)? {
+            let entry = entry?;
+            let target = target.join(entry.file_name());
+            let file_type = entry.file_type()?;
+
+            if file_type.is_symlink() {
+                std::os::unix::fs::symlink(fs::read_link(entry.path())?, target)?;
+            } else if file_type.is_dir() {
+                Self::copy_dir(&entry.path(), &target)?;
+            } else {
+                fs::copy(entry.path(), target)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Removes the oldest data backups of an app, so that at most
+    /// [MAX_APP_DATA_BACKUPS] are kept
+    fn prune_backups(name: &str) {
+        let Ok(entries) = fs::read_dir(path::APP_DATA_BACKUP_DIR.as_path()) else {
+            return;
+        };
+
+        // Backups are named `<app-id>-<YYYYmmdd-HHMMSS>`, so they sort by age
+        let prefix = format!("{name}-");
+        let mut backups: Vec<PathBuf> = entries
+            .flatten()
+            .filter(|entry| {
+                let file_name = entry.file_name().to_string_lossy().to_string();
+                file_name.strip_prefix(&prefix).is_some_and(|timestamp| {
+                    timestamp.len() == 15
+                        && timestamp.chars().enumerate().all(|(i, c)| {
+                            if i == 8 {
+                                c == '-'
+                            } else {
+                                c.is_ascii_digit()
+                            }
+                        })
+                })
+            })
+            .map(|entry| entry.path())
+            .collect();
+        backups.sort();
+
+        let excess = backups.len().saturating_sub(MAX_APP_DATA_BACKUPS);
+        for backup in &backups[..excess] {
+            info!("Remove old data backup: {}", backup.display());
+            if let Err(err) = fs::remove_dir_all(backup) {
+                warn!("Unable to remove data backup {}: {err}", backup.display());
+            }
+        }
+    }
+
+    /// Returns the installed refs which are used by an app: the runtime, and
+    /// the installed extensions of the app and of the runtime
+    fn used_refs(
+        f_inst: &Installation,
+        app: &InstalledRef,
+        installed: &HashMap<String, InstalledRef>,
+    ) -> HashSet<String> {
+        let mut refs = HashSet::new();
+        refs.extend(Self::related_refs(f_inst, app));
+
+        if let Some(runtime) = Self::runtime_ref(app) {
+            if let Some(installed_runtime) = installed.get(&runtime) {
+                refs.extend(Self::related_refs(f_inst, installed_runtime));
+            }
+            refs.insert(runtime);
+        }
+
+        refs.retain(|ref_| installed.contains_key(ref_));
+        refs
+    }
+
+    fn related_refs(f_inst: &Installation, installed_ref: &InstalledRef) -> Vec<String> {
+        let (Some(origin), Some(ref_)) = (installed_ref.origin(), installed_ref.format_ref())
+        else {
+            return Vec::new();
+        };
+
+        f_inst
+            .list_installed_related_refs_sync(&origin, &ref_, Cancellable::NONE)
+            .map(|related| {
+                related
+                    .iter()
+                    .filter_map(|r| r.format_ref().map(|r| r.to_string()))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Reads the runtime ref from the metadata of an app
+    fn runtime_ref(app: &InstalledRef) -> Option<String> {
+        let metadata = app.load_metadata(Cancellable::NONE).ok()?;
+        let keyfile = KeyFile::new();
+        keyfile
+            .load_from_bytes(&metadata, glib::KeyFileFlags::NONE)
+            .ok()?;
+
+        let runtime = keyfile.string("Application", "runtime").ok()?;
+        Some(format!("runtime/{runtime}"))
+    }
+
+    /// Returns the size of all files in the directory. Symlinks are not
+    /// followed, unreadable entries are ignored.
+    pub fn dir_size(path: &Path) -> u64 {
+        let Ok(entries) = fs::read_dir(path) else {
+            return 0;
+        };
+
+        entries
+            .flatten()
+            .map(|entry| match entry.metadata() {
+                Ok(metadata) if metadata.is_dir() => Self::dir_size(&entry.path()),
+                Ok(metadata) => metadata.len(),
+                Err(_) => 0,
+            })
+            .sum()
+    }
+}
@@ -0,0 +1,348 @@
+// Souk - download_worker.rs
+// Copyright (C) 2024  Felix Häcker <haeckerfelix@gnome.org>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::collections::HashMap;
+use std::fs;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use async_std::channel::Sender;
+use gio::Cancellable;
+use glib::Downgrade;
+use gtk::prelude::*;
+use gtk::{gio, glib};
+use isahc::http::header::CONTENT_TYPE;
+use url::Url;
+
+use crate::shared::task::response::{OperationActivity, OperationStatus, TaskResponse, TaskResult};
+use crate::shared::task::DownloadTask;
+use crate::shared::{http, path, WorkerError};
+
+/// Minimum interval between two progress activity responses
+const ACTIVITY_INTERVAL: Duration = Duration::from_millis(250);
+
+#[derive(Debug, Clone, Downgrade)]
+pub struct DownloadWorker {
+    downloads: Arc<Mutex<HashMap<String, Cancellable>>>,
+    sender: Arc<Sender<TaskResponse>>,
+}
+
+impl DownloadWorker {
+    pub fn new(sender: Sender<TaskResponse>) -> Self {
+        Self {
+            downloads: Arc::default(),
+            sender: Arc::new(sender),
+        }
+    }
+
+    pub fn process_task(&self, task: DownloadTask) {
+        let cancellable = Cancellable::new();
+        self.downloads
+            .lock()
+            .unwrap()
+            .insert(task.uuid.clone(), cancellable.clone());
+
+        let result = self.download(&task, &cancellable);
+        self.downloads.lock().unwrap().remove(&task.uuid);
+
+        let result = match result {
+            Ok(path) => TaskResult::DoneDownload(path.to_string_lossy().to_string()),
+            // Download got cancelled (probably by user)
+            Err(WorkerError::GLibCancelled(_)) => TaskResult::Cancelled,
+            Err(err) => TaskResult::Error(Box::new(err)),
+        };
+
+        let response = TaskResponse::new_result(task.into(), result);
+        self.sender.try_send(response).unwrap();
+    }
+
+    pub fn cancel_task(&self, task_uuid: &str) {
+        let downloads = self.downloads.lock().unwrap();
+        if let Some(cancellable) = downloads.get(task_uuid) {
+            cancellable.cancel();
+        } else {
+            warn!("Unable to cancel download task: {}", task_uuid);
+        }
+    }
+
+    /// Downloads the file into the cache directory, and returns the path of
+    /// the downloaded file
+    fn download(
+        &self,
+        task: &DownloadTask,
+        cancellable: &Cancellable,
+    ) -> Result<PathBuf, WorkerError> {
+        info!("Download file: {}", task.url);
+        self.send_activity(task, OperationStatus::Pending, 0, 0);
+
+        let file_name = Self::file_name(&task.url)?;
+        // Redirects get followed, the checks below apply to the final response
+        let mut response = isahc::send(http::get_request(&task.url)?)?;
+
+        if !response.status().is_success() {
+            let msg = format!("Server responded with {}", response.status());
+            return Err(WorkerError::Download(msg));
+        }
+
+        // Reject unexpected content, eg. html error pages
+        if !task.content_types.is_empty() {
+            let content_type = response
+                .headers()
+                .get(CONTENT_TYPE)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.split(';').next())
+                .map(|value| value.trim().to_lowercase())
+                .unwrap_or_default();
+
+            if !task.content_types.contains(&content_type) {
+                let msg = format!("Unexpected content type \"{content_type}\"");
+                return Err(WorkerError::Download(msg));
+            }
+        }
+
+        // The announced size is only a hint, the received bytes get checked as well
+        let total_size = response.body().len();
+        if total_size.is_some_and(|size| size > task.max_size) {
+            return Err(Self::too_large_error(task));
+        }
+
+        // Each download gets its own directory, so files with the same name don't clash
+        let dir = path::DOWNLOAD_DIR.join(&task.uuid);
+        fs::create_dir_all(&dir)?;
+
+        let path = dir.join(&file_name);
+        let part_path = dir.join(format!("{file_name}.part"));
+
+        let result = self.receive_body(
+            task,
+            response.body_mut(),
+            &part_path,
+            total_size,
+            cancellable,
+        );
+
+        if let Err(err) = result {
+            let _ = fs::remove_dir_all(&dir);
+            return Err(err);
+        }
+
+        fs::rename(&part_path, &path)?;
+        self.send_activity(task, OperationStatus::Done, 100, 0);
+
+        Ok(path)
+    }
+
+    fn receive_body(
+        &self,
+        task: &DownloadTask,
+        body: &mut impl Read,
+        path: &Path,
+        total_size: Option<u64>,
+        cancellable: &Cancellable,
+    ) -> Result<(), WorkerError> {
+        let mut file = fs::File::create(path)?;
+        let mut buffer = vec![0; 64 * 1024];
+        let mut received: u64 = 0;
+
+        let start_time = Instant::now();
+        let mut last_activity = Instant::now();
+
+        loop {
+            if cancellable.is_cancelled() {
+                return Err(WorkerError::GLibCancelled(String::new()));
+            }
+
+            let len = body.read(&mut buffer)?;
+            if len == 0 {
+                break;
+            }
+
+            received += len as u64;
+            if received > task.max_size {
+                return Err(Self::too_large_error(task));
+            }
+
+            file.write_all(&buffer[..len])?;
+
+            if last_activity.elapsed() >= ACTIVITY_INTERVAL {
+                let progress = total_size
+                    .filter(|size| *size > 0)
+                    .map(|size| (received * 100 / size) as i32)
+                    .unwrap_or(0);
+                let elapsed = start_time.elapsed().as_secs_f64();
+                let download_rate = (received as f64 / elapsed) as u64;
+
+                self.send_activity(task, OperationStatus::Downloading, progress, download_rate);
+                last_activity = Instant::now();
+            }
+        }
+
+        file.flush()?;
+        Ok(())
+    }
+
+    fn send_activity(
+        &self,
+        task: &DownloadTask,
+        status: OperationStatus,
+        progress: i32,
+        download_rate: u64,
+    ) {
+        let activity = OperationActivity::new_download(&task.url, status, progress, download_rate);
+        let response = TaskResponse::new_activity(task.clone().into(), vec![activity]);
+        self.sender.try_send(response).unwrap();
+    }
+
+    /// Returns the file name of the url, eg. "app.flatpakref"
+    fn file_name(url: &str) -> Result<String, WorkerError> {
+        let url = Url::parse(url).map_err(|err| WorkerError::Download(err.to_string()))?;
+
+        if url.scheme() != "http" && url.scheme() != "https" {
+            let msg = format!("Unsupported url scheme \"{}\"", url.scheme());
+            return Err(WorkerError::Download(msg));
+        }
+
+        url.path_segments()
+            .and_then(|mut segments| segments.next_back())
+            .filter(|name| !name.is_empty() && *name != "." && *name != "..")
+            .map(|name| name.to_string())
+            .ok_or_else(|| WorkerError::Download("Url doesn't contain a file name".into()))
+    }
+
+    fn too_large_error(task: &DownloadTask) -> WorkerError {
+        let size = glib::format_size(task.max_size);
+        WorkerError::Download(format!("File is larger than {size}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, BufReader};
+    use std::net::TcpListener;
+    use std::sync::Once;
+    use std::thread;
+
+    use super::*;
+
+    /// Redirects the cache directory, before it gets accessed the first time
+    fn setup() {
+        static INIT: Once = Once::new();
+        INIT.call_once(|| {
+            let dir = std::env::temp_dir().join(format!("souk-test-{}", std::process::id()));
+            std::env::set_var("XDG_CACHE_HOME", dir);
+        });
+    }
+
+    /// Serves a single request with the passed status line, headers and
+    /// body, and returns the url of the file
+    fn serve_response(status: &'static str, headers: String, body: Vec<u8>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+
+            // Skip the request, up to the empty line after the headers
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut line = String::new();
+            while reader.read_line(&mut line).unwrap() > 2 {
+                line.clear();
+            }
+
+            let header = format!(
+                "HTTP/1.1 {status}\r\n{headers}Content-Length: {}\r\nConnection: close\r\n\r\n",
+                body.len()
+            );
+            stream.write_all(header.as_bytes()).unwrap();
+            let _ = stream.write_all(&body);
+        });
+
+        format!("http://127.0.0.1:{port}/files/app.flatpakref")
+    }
+
+    fn serve(content_type: &'static str, body: Vec<u8>) -> String {
+        let headers = format!("Content-Type: {content_type}\r\n");
+        serve_response("200 OK", headers, body)
+    }
+
+    /// Redirects to `target`, like download links of Flathub or GitHub do
+    fn serve_redirect(target: &str) -> String {
+        let headers = format!("Location: {target}\r\nContent-Type: text/html\r\n");
+        serve_response("302 Found", headers, Vec::new())
+    }
+
+    fn download(task: &DownloadTask) -> Result<PathBuf, WorkerError> {
+        let (sender, _receiver) = async_std::channel::unbounded();
+        let worker = DownloadWorker::new(sender);
+        worker.download(task, &Cancellable::new())
+    }
+
+    #[test]
+    fn download_file() {
+        setup();
+        let body = b"[Flatpak Ref]\nName=org.example.App\n".to_vec();
+        let url = serve("application/vnd.flatpak.ref", body.clone());
+
+        let task = DownloadTask::new(&url, 1024, &["application/vnd.flatpak.ref"]);
+        let path = download(&task).unwrap();
+
+        assert_eq!(path.file_name().unwrap(), "app.flatpakref");
+        assert_eq!(path.parent().unwrap(), path::DOWNLOAD_DIR.join(&task.uuid));
+        assert_eq!(fs::read(&path).unwrap(), body);
+
+        path::remove_download(&path);
+        assert!(!path::DOWNLOAD_DIR.join(&task.uuid).exists());
+    }
+
+    #[test]
+    fn follow_redirect() {
+        setup();
+        let body = b"[Flatpak Ref]\nName=org.example.App\n".to_vec();
+        let url = serve_redirect(&serve("application/vnd.flatpak.ref", body.clone()));
+
+        let task = DownloadTask::new(&url, 1024, &["application/vnd.flatpak.ref"]);
+        let path = download(&task).unwrap();
+        assert_eq!(fs::read(&path).unwrap(), body);
+        path::remove_download(&path);
+
+        // The checks apply to the final response
+        let url = serve_redirect(&serve("application/vnd.flatpak.ref", vec![b'x'; 2048]));
+        let task = DownloadTask::new(&url, 1024, &["application/vnd.flatpak.ref"]);
+        assert!(matches!(download(&task), Err(WorkerError::Download(_))));
+    }
+
+    #[test]
+    fn reject_unexpected_content_type() {
+        setup();
+        let url = serve("text/html", b"<html></html>".to_vec());
+
+        let task = DownloadTask::new(&url, 1024, &["application/vnd.flatpak.ref"]);
+        assert!(matches!(download(&task), Err(WorkerError::Download(_))));
+        assert!(!path::DOWNLOAD_DIR.join(&task.uuid).exists());
+    }
+
+    #[test]
+    fn reject_too_large_file() {
+        setup();
+        let url = serve("application/vnd.flatpak.ref", vec![b'x'; 2048]);
+
+        let task = DownloadTask::new(&url, 1024, &["application/vnd.flatpak.ref"]);
+        assert!(matches!(download(&task), Err(WorkerError::Download(_))));
+        assert!(!path::DOWNLOAD_DIR.join(&task.uuid).exists());
+    }
+}
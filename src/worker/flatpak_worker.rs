@@ -20,22 +20,29 @@ use std::path::PathBuf;
 use std::rc::Rc;
 use std::sync::{Arc, Mutex};
 
-use ::appstream::Collection;
+use ::appstream::xmltree::Element;
+use ::appstream::{Collection, Component};
 use async_std::channel::Sender;
 use flatpak::prelude::*;
-use flatpak::{BundleRef, Installation, Ref, Remote, Transaction, TransactionOperationType};
+use flatpak::{
+    BundleRef, Installation, Ref, Remote, Transaction, TransactionOperation,
+    TransactionOperationType,
+};
 use gio::Cancellable;
 use glib::{clone, Downgrade, KeyFile};
 use gtk::{gio, glib};
 use isahc::ReadResponseExt;
 
+use crate::shared::appstream::ComponentRelations;
 use crate::shared::flatpak::dry_run::{DryRun, DryRunPackage};
-use crate::shared::flatpak::info::RemoteInfo;
-use crate::shared::flatpak::FlatpakOperationKind;
-use crate::shared::task::response::{OperationActivity, TaskResponse, TaskResult};
-use crate::shared::task::{FlatpakTask, FlatpakTaskKind};
+use crate::shared::flatpak::info::{PackageInfo, RemoteInfo};
+use crate::shared::flatpak::{
+    FlatpakOperationKind, PermissionChange, PermissionPolicy, PolicyViolation,
+};
+use crate::shared::task::response::{OperationActivity, OperationStatus, TaskResponse, TaskResult};
+use crate::shared::task::{DataRemoval, FlatpakTask, FlatpakTaskKind};
 use crate::shared::WorkerError;
-use crate::worker::SkWorkerApplication;
+use crate::worker::{AppstreamWorker, DataWorker, SkWorkerApplication};
 
 #[derive(Debug, Clone, Downgrade)]
 pub struct FlatpakWorker {
@@ -56,9 +63,9 @@ impl FlatpakWorker {
         let result = match task.kind {
             FlatpakTaskKind::Install => {
                 if task.dry_run {
-                    unimplemented!();
+                    self.install_flatpak_dry_run(&task)
                 } else {
-                    self.install_flatpak(&task)
+                    self.install_flatpak(&task, false)
                 }
             }
             FlatpakTaskKind::InstallBundleFile => {
@@ -75,15 +82,26 @@ impl FlatpakWorker {
                     self.install_flatpak_ref_file(&task)
                 }
             }
+            FlatpakTaskKind::ChangeAddons => {
+                if task.dry_run {
+                    self.change_flatpak_addons_dry_run(&task)
+                } else {
+                    self.change_flatpak_addons(&task)
+                }
+            }
             FlatpakTaskKind::Update => {
-                unimplemented!();
+                if task.dry_run {
+                    self.update_flatpak_dry_run(&task)
+                } else {
+                    self.update_flatpak(&task)
+                }
             }
             FlatpakTaskKind::UpdateInstallation => {
                 unimplemented!();
             }
             FlatpakTaskKind::Uninstall => {
                 if task.dry_run {
-                    unimplemented!();
+                    self.uninstall_flatpak_dry_run(&task)
                 } else {
                     self.uninstall_flatpak(&task)
                 }
@@ -114,7 +132,13 @@ impl FlatpakWorker {
         }
     }
 
-    fn install_flatpak(&self, task: &FlatpakTask) -> Result<(), WorkerError> {
+    /// If `skip_task_result` is set, no [TaskResult::Done] gets emitted, so
+    /// the installation can be part of another task (eg. a backup import).
+    pub fn install_flatpak(
+        &self,
+        task: &FlatpakTask,
+        skip_task_result: bool,
+    ) -> Result<(), WorkerError> {
         let ref_ = task.ref_.as_ref().unwrap();
         let remote = task.remote.as_ref().unwrap();
         info!("Install Flatpak: {}", ref_);
@@ -125,7 +149,27 @@ impl FlatpakWorker {
 
         let transaction = self.new_transaction(task)?;
         transaction.add_install(&remote.name, ref_, &[])?;
-        self.run_transaction(task, transaction, false)?;
+        for addon in &task.install_addons {
+            transaction.add_install(&remote.name, addon, &[])?;
+        }
+        self.run_transaction(task, transaction, skip_task_result)?;
+
+        Ok(())
+    }
+
+    fn install_flatpak_dry_run(&self, task: &FlatpakTask) -> Result<(), WorkerError> {
+        let ref_ = task.ref_.as_ref().unwrap();
+        let remote = task.remote.as_ref().unwrap();
+        info!("Install Flatpak (dry run): {}", ref_);
+
+        // Addons are not included, the targeted ref has to be the last operation
+        let transaction = self.new_transaction(task)?;
+        transaction.add_install(&remote.name, ref_, &[])?;
+        let res = self.run_dry_run_transaction(task, transaction)?;
+
+        let result = TaskResult::DoneDryRun(Box::new(res));
+        let response = TaskResponse::new_result(task.clone().into(), result);
+        self.sender.try_send(response).unwrap();
 
         Ok(())
     }
@@ -184,15 +228,6 @@ impl FlatpakWorker {
             res.package.icon = Some(bytes.to_vec());
         }
 
-        // Appstream
-        if let Some(compressed) = bundle.appstream() {
-            let collection = Collection::from_gzipped_bytes(&compressed).unwrap();
-            let component = &collection.components[0];
-
-            let json = serde_json::to_string(component).unwrap();
-            res.package.appstream_component = Some(json);
-        }
-
         let result = TaskResult::DoneDryRun(Box::new(res));
         let response = TaskResponse::new_result(task.clone().into(), result);
         self.sender.try_send(response).unwrap();
@@ -267,11 +302,180 @@ impl FlatpakWorker {
 
         let transaction = self.new_transaction(task)?;
         transaction.add_uninstall(ref_)?;
+        self.run_transaction(task, transaction, task.remove_data.is_some())?;
+
+        if let Some(removal) = task.remove_data {
+            self.remove_app_data(task, removal)?;
+        }
+
+        Ok(())
+    }
+
+    fn uninstall_flatpak_dry_run(&self, task: &FlatpakTask) -> Result<(), WorkerError> {
+        let ref_ = task.ref_.as_ref().unwrap();
+        let remote = task.remote.as_ref().unwrap();
+        info!("Uninstall Flatpak (dry run): {}", ref_);
+
+        // The temporary dry run installation doesn't contain any installed refs, so
+        // the result gets assembled from the installed ref directly
+        let installation = Installation::from(&task.installation);
+        let f_ref = Ref::parse(ref_)?;
+        let installed_ref = installation.installed_ref(
+            f_ref.kind(),
+            &f_ref.name().unwrap(),
+            f_ref.arch().as_deref(),
+            f_ref.branch().as_deref(),
+            Cancellable::NONE,
+        )?;
+
+        let metadata = installed_ref.load_metadata(Cancellable::NONE)?.to_vec();
+        let package = DryRunPackage {
+            info: PackageInfo::new(ref_.clone(), remote.clone()),
+            operation_kind: FlatpakOperationKind::Uninstall,
+            installed_size: installed_ref.installed_size(),
+            metadata: String::from_utf8(metadata).unwrap_or_default(),
+            old_version: installed_ref.appdata_version().map(|v| v.to_string()),
+            ..Default::default()
+        };
+
+        // The data directory is shared with other installed refs of the same app,
+        // in that case it doesn't get removed
+        let removed_data_size = if task.remove_data.is_some()
+            && !DataWorker::is_data_shared(ref_, &task.installation)?
+        {
+            let data_dir = DataWorker::app_data_dir(&package.info)?;
+            Some(DataWorker::dir_size(&data_dir))
+        } else {
+            None
+        };
+
+        let res = DryRun {
+            package,
+            removed_data_size,
+            ..Default::default()
+        };
+
+        let result = TaskResult::DoneDryRun(Box::new(res));
+        let response = TaskResponse::new_result(task.clone().into(), result);
+        self.sender.try_send(response).unwrap();
+
+        Ok(())
+    }
+
+    /// Removes the sandbox data of the uninstalled app, as last step of the
+    /// uninstall task
+    fn remove_app_data(&self, task: &FlatpakTask, removal: DataRemoval) -> Result<(), WorkerError> {
+        let activity = Self::remove_data_activity(task, OperationStatus::Processing).unwrap();
+        let response = TaskResponse::new_activity(task.clone().into(), vec![activity]);
+        self.sender.try_send(response).unwrap();
+
+        let ref_ = task.ref_.clone().unwrap();
+        if DataWorker::is_data_shared(&ref_, &task.installation)? {
+            info!("Keeping app data, it's still used by another installed ref: {ref_}");
+        } else {
+            let package = PackageInfo::new(ref_, task.remote.clone().unwrap());
+            let data_dir = DataWorker::app_data_dir(&package)?;
+            DataWorker::remove_data_dir(&data_dir, removal)?;
+        }
+
+        let activity = Self::remove_data_activity(task, OperationStatus::Done).unwrap();
+        let response = TaskResponse::new_activity(task.clone().into(), vec![activity]);
+        self.sender.try_send(response).unwrap();
+
+        let response = TaskResponse::new_result(task.clone().into(), TaskResult::Done);
+        self.sender.try_send(response).unwrap();
+
+        Ok(())
+    }
+
+    /// The "remove data" step of an uninstall task, if the task removes the
+    /// app data
+    fn remove_data_activity(
+        task: &FlatpakTask,
+        status: OperationStatus,
+    ) -> Option<OperationActivity> {
+        let removal = task.remove_data?;
+        let package = PackageInfo::new(task.ref_.clone()?, task.remote.clone()?);
+        Some(OperationActivity::new_remove_data(
+            &package, removal, status,
+        ))
+    }
+
+    fn update_flatpak(&self, task: &FlatpakTask) -> Result<(), WorkerError> {
+        let ref_ = task.ref_.as_ref().unwrap();
+        info!("Update Flatpak: {}", ref_);
+
+        let transaction = self.new_transaction(task)?;
+        transaction.add_update(ref_, &[], None)?;
+        self.run_transaction(task, transaction, false)?;
+
+        Ok(())
+    }
+
+    fn update_flatpak_dry_run(&self, task: &FlatpakTask) -> Result<(), WorkerError> {
+        let ref_ = task.ref_.as_ref().unwrap();
+        let remote = task.remote.as_ref().unwrap();
+        info!("Update Flatpak (dry run): {}", ref_);
+
+        // The ref isn't installed in the temporary dry run installation, so the update
+        // gets simulated as installation. The operation kind gets set to `Update` by
+        // comparing with the real installation.
+        let transaction = self.new_transaction(task)?;
+        transaction.add_install(&remote.name, ref_, &[])?;
+        let res = self.run_dry_run_transaction(task, transaction)?;
+
+        let result = TaskResult::DoneDryRun(Box::new(res));
+        let response = TaskResponse::new_result(task.clone().into(), result);
+        self.sender.try_send(response).unwrap();
+
+        Ok(())
+    }
+
+    fn change_flatpak_addons(&self, task: &FlatpakTask) -> Result<(), WorkerError> {
+        let ref_ = task.ref_.as_ref().unwrap();
+        let remote = task.remote.as_ref().unwrap();
+        info!(
+            "Change addons of Flatpak {}: install {:?}, uninstall {:?}",
+            ref_, task.install_addons, task.uninstall_addons
+        );
+
+        let transaction = self.new_transaction(task)?;
+        for addon in &task.install_addons {
+            transaction.add_install(&remote.name, addon, &[])?;
+        }
+        for addon in &task.uninstall_addons {
+            transaction.add_uninstall(addon)?;
+        }
         self.run_transaction(task, transaction, false)?;
 
         Ok(())
     }
 
+    fn change_flatpak_addons_dry_run(&self, task: &FlatpakTask) -> Result<(), WorkerError> {
+        let ref_ = task.ref_.as_ref().unwrap();
+        let remote = task.remote.as_ref().unwrap();
+        info!("Change addons of Flatpak {} (dry run)", ref_);
+
+        // The temporary dry run installation doesn't contain the installed addons, so
+        // only the addons which get installed can be simulated. The last addon is
+        // the targeted ref of the transaction.
+        let res = if task.install_addons.is_empty() {
+            DryRun::default()
+        } else {
+            let transaction = self.new_transaction(task)?;
+            for addon in &task.install_addons {
+                transaction.add_install(&remote.name, addon, &[])?;
+            }
+            self.run_dry_run_transaction(task, transaction)?
+        };
+
+        let result = TaskResult::DoneDryRun(Box::new(res));
+        let response = TaskResponse::new_result(task.clone().into(), result);
+        self.sender.try_send(response).unwrap();
+
+        Ok(())
+    }
+
     /// If `skip_task_result` is set, no [TaskResult::Done] gets emitted.
     /// Required if the Flatpak transaction is only part of a task and therefore
     /// does not complete it.
@@ -283,11 +487,14 @@ impl FlatpakWorker {
     ) -> Result<(), WorkerError> {
         transaction.connect_add_new_remote(move |_, _, _, _, _| true);
 
+        let policy_violation: Rc<RefCell<Option<PolicyViolation>>> = Rc::default();
         transaction.connect_ready(clone!(
             #[strong]
             task,
             #[weak(rename_to = sender)]
             self.sender,
+            #[strong]
+            policy_violation,
             #[upgrade_or]
             true,
             move |transaction| {
@@ -296,6 +503,34 @@ impl FlatpakWorker {
                     return true;
                 }
 
+                // Enforce the permission policy, in case the installation didn't get dry ran
+                // beforehand
+                let policy = PermissionPolicy::load();
+                for op in transaction.operations() {
+                    if op.operation_type() == TransactionOperationType::Uninstall {
+                        continue;
+                    }
+
+                    let metadata = op
+                        .metadata()
+                        .map(|m| m.to_data().to_string())
+                        .unwrap_or_default();
+                    let license = Self::operation_license(transaction, &op);
+                    let violations = Self::check_policy(
+                        &policy,
+                        &op.get_ref().unwrap(),
+                        &op.remote().unwrap(),
+                        &metadata,
+                        license.as_deref(),
+                    );
+
+                    if let Some(violation) = violations.into_iter().find(|v| v.blocking) {
+                        warn!("Transaction blocked by permission policy: {violation:?}");
+                        policy_violation.replace(Some(violation));
+                        return false;
+                    }
+                }
+
                 let mut operation_activities = Vec::new();
                 for op in transaction.operations() {
                     operation_activities.push(OperationActivity::from_flatpak_operation(
@@ -306,6 +541,12 @@ impl FlatpakWorker {
                     ));
                 }
 
+                // Data removal happens after the transaction
+                if let Some(activity) = Self::remove_data_activity(&task, OperationStatus::Pending)
+                {
+                    operation_activities.push(activity);
+                }
+
                 let response =
                     TaskResponse::new_activity(task.clone().into(), operation_activities);
                 sender.try_send(response).unwrap();
@@ -396,11 +637,16 @@ impl FlatpakWorker {
 
         // Start the actual Flatpak transaction
         // This is going to block the thread till completion
-        transaction.run(Some(&cancellable))?;
+        let transaction_result = transaction.run(Some(&cancellable));
 
         let mut transactions = self.transactions.lock().unwrap();
         transactions.remove(&task.uuid);
 
+        if let Some(violation) = policy_violation.take() {
+            return Err(WorkerError::PolicyBlocked(violation));
+        }
+        transaction_result?;
+
         Ok(())
     }
 
@@ -525,7 +771,12 @@ impl FlatpakWorker {
                     // example, to determine new permissions.
                     let utf8 = installed_ref.load_metadata(Cancellable::NONE)?.to_vec();
                     let metadata = String::from_utf8(utf8).unwrap();
+                    package.permission_changes = Some(PermissionChange::from_metadata(
+                        &metadata,
+                        &package.metadata,
+                    ));
                     package.old_metadata = Some(metadata);
+                    package.old_version = installed_ref.appdata_version().map(|v| v.to_string());
                 }
             } else if operation.operation_type() == TransactionOperationType::InstallBundle {
                 debug!("[install] {op_ref_str}: is not installed.");
@@ -535,9 +786,22 @@ impl FlatpakWorker {
                 package.operation_kind = FlatpakOperationKind::Install;
             }
 
-            // Retrieve appstream data unless it is a Flatpak bundle which includes the data
-            // in the bundle file itself (and doesn't have a "real" remote)
-            if operation.operation_type() != TransactionOperationType::InstallBundle {
+            // Retrieve appstream data, Flatpak bundles include the data in the bundle file
+            // itself (and don't have a "real" remote)
+            if let Some(file) = operation.bundle_path() {
+                let bundle = BundleRef::new(&file)?;
+                if let Some(compressed) = bundle.appstream() {
+                    let collection = Collection::from_gzipped_bytes(&compressed).unwrap();
+                    let component = &collection.components[0];
+
+                    let json = serde_json::to_string(component).unwrap();
+                    package.appstream_component = Some(json);
+
+                    if let Some(relations) = Self::bundle_relations(&compressed) {
+                        package.relations = relations;
+                    }
+                }
+            } else {
                 let appstream_worker = SkWorkerApplication::default().appstream_worker();
                 appstream_worker.set_dry_run_package_appstream(
                     &task.clone().into(),
@@ -588,10 +852,74 @@ impl FlatpakWorker {
         // Remove temporary dry run installation directory again
         Self::cleanup_dry_run_installation(&task.uuid);
 
+        // Check the packages against the permission policy
+        let policy = PermissionPolicy::load();
+        let mut violations = Vec::new();
+        for package in std::iter::once(&result.package).chain(&result.runtimes) {
+            if matches!(
+                package.operation_kind,
+                FlatpakOperationKind::None | FlatpakOperationKind::Uninstall
+            ) {
+                continue;
+            }
+
+            let license = package
+                .appstream_component
+                .as_deref()
+                .and_then(|json| serde_json::from_str::<Component>(json).ok())
+                .and_then(|component| component.project_license)
+                .map(|license| license.0);
+
+            violations.append(&mut Self::check_policy(
+                &policy,
+                &package.info.ref_,
+                &package.info.remote.name,
+                &package.metadata,
+                license.as_deref(),
+            ));
+        }
+        if let Some(violation) = violations.iter().find(|v| v.blocking) {
+            warn!("Dry run blocked by permission policy: {violation:?}");
+            return Err(WorkerError::PolicyBlocked(violation.clone()));
+        }
+        result.policy_warnings = violations;
+
         debug!("Dry run transaction done: {}", task.uuid);
         Ok(result.clone())
     }
 
+    fn check_policy(
+        policy: &PermissionPolicy,
+        ref_: &str,
+        remote: &str,
+        metadata: &str,
+        license: Option<&str>,
+    ) -> Vec<PolicyViolation> {
+        let keyfile = KeyFile::new();
+        let metadata = keyfile
+            .load_from_data(metadata, glib::KeyFileFlags::NONE)
+            .ok()
+            .map(|_| &keyfile);
+
+        policy.evaluate(ref_, remote, metadata, license)
+    }
+
+    /// The project license of the package of an operation
+    fn operation_license(transaction: &Transaction, op: &TransactionOperation) -> Option<String> {
+        if let Some(file) = op.bundle_path() {
+            let compressed = BundleRef::new(&file).ok()?.appstream()?;
+            let collection = Collection::from_gzipped_bytes(&compressed).ok()?;
+            let component = collection.components.into_iter().next()?;
+            return component.project_license.map(|license| license.0);
+        }
+
+        let remote = transaction
+            .installation()?
+            .remote_by_name(&op.remote()?, Cancellable::NONE)
+            .ok()?;
+        AppstreamWorker::project_license(&op.get_ref()?, &remote)
+    }
+
     fn new_transaction(&self, task: &FlatpakTask) -> Result<Transaction, WorkerError> {
         let installation = Installation::from(&task.installation);
 
@@ -693,6 +1021,28 @@ impl FlatpakWorker {
         Ok(glib::Bytes::from_owned(response.bytes()?))
     }
 
+    /// The relations aren't part of the parsed appstream component, so they
+    /// have to get extracted from the gzipped appstream xml of the bundle
+    fn bundle_relations(compressed: &glib::Bytes) -> Option<ComponentRelations> {
+        let decompressor = gio::ZlibDecompressor::new(gio::ZlibCompressorFormat::Gzip);
+        let base_stream = gio::MemoryInputStream::from_bytes(compressed);
+        let stream = gio::ConverterInputStream::new(&base_stream, &decompressor);
+
+        let mut xml = Vec::new();
+        let mut buffer = [0; 8192];
+        loop {
+            let len = stream.read(&mut buffer, Cancellable::NONE).ok()?;
+            if len == 0 {
+                break;
+            }
+            xml.extend_from_slice(&buffer[..len]);
+        }
+
+        let collection = Element::parse(xml.as_slice()).ok()?;
+        let component = collection.get_child("component")?;
+        Some(ComponentRelations::from_element(component))
+    }
+
     fn parse_ref_file(keyfile: &KeyFile) -> Result<String, WorkerError> {
         let kind = if let Ok(is_runtime) = keyfile.boolean("Flatpak Ref", "IsRuntime") {
             if is_runtime {
@@ -16,11 +16,22 @@
 
 mod app;
 mod appstream_worker;
+/// Export / import of app data archives
+mod backup_worker;
+/// Disk usage and maintenance of the sandbox data of apps
+mod data_worker;
 /// Parsing appstream metadata, creation of xmlb exports
 mod dbus_server;
+mod download_worker;
 /// Handling of Flatpak transactions / dry running
 mod flatpak_worker;
+/// Reading / writing of Flatpak permission overrides
+mod override_worker;
 
 pub use app::SkWorkerApplication;
 use appstream_worker::AppstreamWorker;
+use backup_worker::BackupWorker;
+use data_worker::DataWorker;
+use download_worker::DownloadWorker;
 use flatpak_worker::FlatpakWorker;
+use override_worker::OverrideWorker;
@@ -0,0 +1,136 @@
+// Souk - override_worker.rs
+// Copyright (C) 2024  Felix Häcker <haeckerfelix@gnome.org>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use async_std::channel::Sender;
+use glib::{Downgrade, KeyFile, KeyFileFlags};
+use gtk::glib;
+
+use crate::shared::task::response::{TaskResponse, TaskResult};
+use crate::shared::task::{OverrideTask, OverrideTaskKind};
+use crate::shared::WorkerError;
+
+/// Name of the overrides file which applies to all apps
+const GLOBAL_OVERRIDES: &str = "global";
+
+#[derive(Debug, Clone, Downgrade)]
+pub struct OverrideWorker {
+    sender: Arc<Sender<TaskResponse>>,
+}
+
+impl OverrideWorker {
+    pub fn new(sender: Sender<TaskResponse>) -> Self {
+        Self {
+            sender: Arc::new(sender),
+        }
+    }
+
+    pub fn process_task(&self, task: OverrideTask) {
+        let result = match &task.kind {
+            OverrideTaskKind::Load => self.load(&task).map(TaskResult::DoneOverrides),
+            OverrideTaskKind::Save(data) => self.save(&task, data).map(|_| TaskResult::Done),
+            OverrideTaskKind::Reset => self.reset(&task).map(|_| TaskResult::Done),
+            OverrideTaskKind::None => return,
+        };
+
+        let result = match result {
+            Ok(result) => result,
+            Err(err) => TaskResult::Error(Box::new(err)),
+        };
+
+        let response = TaskResponse::new_result(task.into(), result);
+        self.sender.try_send(response).unwrap();
+    }
+
+    pub fn cancel_task(&self, task_uuid: &str) {
+        warn!("Unable to cancel override task: {}", task_uuid);
+    }
+
+    /// Returns the path of the overrides file, eg.
+    /// `~/.local/share/flatpak/overrides/de.haeckerfelix.Souk`
+    fn overrides_path(task: &OverrideTask) -> Result<PathBuf, WorkerError> {
+        let name = task.app_id.as_deref().unwrap_or(GLOBAL_OVERRIDES);
+
+        // Make sure that the app id can't point outside of the overrides dir
+        if name.is_empty() || name.contains('/') || name.starts_with('.') {
+            return Err(WorkerError::IO(format!("Invalid app id: {name}")));
+        }
+
+        Ok(PathBuf::from(&task.installation.path)
+            .join("overrides")
+            .join(name))
+    }
+
+    fn load(&self, task: &OverrideTask) -> Result<String, WorkerError> {
+        let path = Self::overrides_path(task)?;
+        if !path.exists() {
+            return Ok(String::new());
+        }
+
+        debug!("Load overrides: {}", path.display());
+        Ok(fs::read_to_string(path)?)
+    }
+
+    /// The overrides of system installations are only writable with
+    /// administrator privileges, which the worker usually doesn't have
+    fn write_error(task: &OverrideTask, err: io::Error) -> WorkerError {
+        if err.kind() == ErrorKind::PermissionDenied && !task.installation.is_user {
+            let msg = format!(
+                "Changing the overrides of the system installation \"{}\" requires administrator privileges",
+                task.installation.name
+            );
+            return WorkerError::IO(msg);
+        }
+
+        err.into()
+    }
+
+    fn save(&self, task: &OverrideTask, data: &str) -> Result<(), WorkerError> {
+        // Without any entries the file would be pointless
+        if data.trim().is_empty() {
+            return self.reset(task);
+        }
+
+        // Don't write anything which Flatpak can't parse later
+        let keyfile = KeyFile::new();
+        keyfile.load_from_data(data, KeyFileFlags::KEEP_COMMENTS)?;
+
+        let path = Self::overrides_path(task)?;
+        info!("Save overrides: {}", path.display());
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|err| Self::write_error(task, err))?;
+        }
+        fs::write(&path, keyfile.to_data().as_bytes())
+            .map_err(|err| Self::write_error(task, err))?;
+
+        Ok(())
+    }
+
+    fn reset(&self, task: &OverrideTask) -> Result<(), WorkerError> {
+        let path = Self::overrides_path(task)?;
+        if path.exists() {
+            info!("Reset overrides: {}", path.display());
+            fs::remove_file(path).map_err(|err| Self::write_error(task, err))?;
+        }
+
+        Ok(())
+    }
+}